@@ -1,5 +1,7 @@
 mod charts;
 mod criterion;
+mod k6;
+mod vegeta;
 mod wrk;
 
 mod copy_button;
@@ -8,8 +10,14 @@ mod metric_panel;
 mod share_modal;
 
 pub use copy_button::CopyButton;
-pub use criterion::{CriterionBenchmark, CriterionGroupChart};
+pub use criterion::CriterionGroupSection;
 pub use dashboard_header::DashboardHeader;
+pub use k6::{ChecksPanel, ThresholdPanel, TrendTable};
 pub use metric_panel::MetricPanel;
 pub use share_modal::ShareModal;
-pub use wrk::{LatencyChart, LatencyPercentileChart, RequestsPerSecChart, WrkConfig};
+pub use vegeta::SuccessRatioPanel;
+pub use wrk::{
+    ConnectionTimesPanel, ErrorRatePanel, LatencyChart, LatencyOverTimeChart,
+    LatencyPercentileChart, PhaseTimingPanel, RequestsPerSecChart, ResponseTimeHistogram,
+    StatusCodePanel, TargetRatePanel, WrkConfig,
+};
@@ -1,3 +1,4 @@
+use super::data::format_ops_per_sec;
 use super::{
     CriterionComparison, CriterionIterationTimesChart, CriterionPdfChart, CriterionRegressionChart,
     CriterionStatDistributionChart, CriterionStatsTable,
@@ -42,6 +43,23 @@ pub fn criterion_benchmark(props: &CriterionBenchmarkProps) -> Html {
         }
     });
 
+    let extra_metrics_html = bench.extra_metrics.iter().map(|metric| {
+        let label = match metric.unit.as_str() {
+            _ if !metric.name.is_empty() => metric.name.clone(),
+            "B/op" => "Allocated".to_string(),
+            "allocs/op" => "Allocations".to_string(),
+            _ => "Per Iteration".to_string(),
+        };
+        let value = if metric.value.fract() == 0.0 {
+            format!("{:.0} {}", metric.value, metric.unit)
+        } else {
+            format!("{:.2} {}", metric.value, metric.unit)
+        }
+        .trim_end()
+        .to_string();
+        html! { <MetricPanel class="panel-extra-metric" value={value} label={label} /> }
+    });
+
     let has_samples = !bench.iteration_count.is_empty() && !bench.measured_values.is_empty();
 
     let iteration_times_html = if has_samples && bench.slope.is_none() {
@@ -51,9 +69,10 @@ pub fn criterion_benchmark(props: &CriterionBenchmarkProps) -> Html {
                 <CriterionIterationTimesChart
                     iteration_count={bench.iteration_count.clone()}
                     measured_values={bench.measured_values.clone()}
+                    fences={bench.outliers.as_ref().and_then(|o| o.fences)}
                 />
                 <p class="chart-description">
-                    { "This chart shows the time taken by each iteration of the benchmark. This is used when the benchmark does not do linear regression." }
+                    { "This chart shows the time taken by each iteration of the benchmark. This is used when the benchmark does not do linear regression. Mild outliers are shown in orange and severe outliers in red." }
                 </p>
             </div>
         })
@@ -67,18 +86,31 @@ pub fn criterion_benchmark(props: &CriterionBenchmarkProps) -> Html {
         <div class="criterion-benchmark">
             <h3 class="benchmark-name">{ &bench.name }</h3>
             <div class="benchmark-metrics">
-                <div class="metric-panel panel-time">
-                    <h3>{ "Time" }</h3>
-                    <div class="metric-content">
-                        <div class="main-value">{ format_latency(bench.time.estimate) }</div>
-                        <div class="confidence-interval">
-                            { format!("[{} .. {}]", format_latency(bench.time.lower_bound), format_latency(bench.time.upper_bound)) }
+                if let Some(ops) = &bench.ops_per_sec {
+                    <div class="metric-panel panel-time">
+                        <h3>{ "Throughput" }</h3>
+                        <div class="metric-content">
+                            <div class="main-value">{ format_ops_per_sec(ops.estimate) }</div>
+                            <div class="confidence-interval">
+                                { format!("[{} .. {}]", format_ops_per_sec(ops.lower_bound), format_ops_per_sec(ops.upper_bound)) }
+                            </div>
                         </div>
                     </div>
-                </div>
+                } else {
+                    <div class="metric-panel panel-time">
+                        <h3>{ "Time" }</h3>
+                        <div class="metric-content">
+                            <div class="main-value">{ format_latency(bench.time.estimate) }</div>
+                            <div class="confidence-interval">
+                                { format!("[{} .. {}]", format_latency(bench.time.lower_bound), format_latency(bench.time.upper_bound)) }
+                            </div>
+                        </div>
+                    </div>
+                }
                 { for change_html }
                 { for outliers_html }
                 { for throughput_html }
+                { for extra_metrics_html }
             </div>
             <div class="benchmark-stats">
                 <CriterionStatsTable metrics={bench.clone()} />
@@ -103,9 +135,10 @@ pub fn criterion_benchmark(props: &CriterionBenchmarkProps) -> Html {
                                     iteration_count={bench.iteration_count.clone()}
                                     measured_values={bench.measured_values.clone()}
                                     slope={bench.slope.clone()}
+                                    fences={bench.outliers.as_ref().and_then(|o| o.fences)}
                                 />
                                 <p class="chart-description">
-                                    { "This chart shows the linear regression of total time vs. number of iterations, with the confidence interval shaded." }
+                                    { "This chart shows the linear regression of total time vs. number of iterations, with the confidence interval shaded. Mild outliers are shown in orange and severe outliers in red." }
                                 </p>
                             </div>
                         }
@@ -118,6 +118,26 @@ fn render_change_distributions(change: Option<&ChangeStats>) -> Option<Html> {
     })
 }
 
+/// " (p = 0.01 < 0.05)" like Criterion's own output, just the p-value when the
+/// significance level isn't known, or nothing when no t-test result was recorded.
+fn format_significance(change: &ChangeStats) -> String {
+    if change.significance_threshold > 0.0 {
+        let op = if change.p_value < change.significance_threshold {
+            '<'
+        } else {
+            '>'
+        };
+        format!(
+            " (p = {:.2} {op} {:.2})",
+            change.p_value, change.significance_threshold
+        )
+    } else if change.p_value > 0.0 {
+        format!(" (p = {:.2})", change.p_value)
+    } else {
+        String::new()
+    }
+}
+
 fn render_change_table(change: Option<&ChangeStats>) -> Option<Html> {
     let change = change?;
 
@@ -154,8 +174,30 @@ fn render_change_table(change: Option<&ChangeStats>) -> Option<Html> {
                 </tbody>
             </table>
             <div class="criterion-confidence">
-                { format!("{} (p = {:.2})", result_label, change.p_value) }
+                { format!("{result_label}{}", format_significance(change)) }
             </div>
+            if change.noise_threshold > 0.0 {
+                <div class="criterion-confidence">
+                    { format!("Noise threshold: \u{00b1}{:.2}%", change.noise_threshold) }
+                </div>
+            }
         </div>
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_significance_skips_unknown_p_value() {
+        let mut change = ChangeStats::default();
+        assert_eq!(format_significance(&change), "");
+        change.p_value = 0.2;
+        assert_eq!(format_significance(&change), " (p = 0.20)");
+        change.significance_threshold = 0.05;
+        assert_eq!(format_significance(&change), " (p = 0.20 > 0.05)");
+        change.p_value = 0.0;
+        assert_eq!(format_significance(&change), " (p = 0.00 < 0.05)");
+    }
+}
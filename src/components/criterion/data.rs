@@ -1,4 +1,8 @@
 #![allow(clippy::cast_precision_loss)]
+use crate::parser::criterion::{CriterionMetrics, OutlierClass, TukeyFences};
+
+const MILD_OUTLIER_COLOR: &str = "rgb(255, 127, 0)";
+const SEVERE_OUTLIER_COLOR: &str = "rgb(228, 26, 28)";
 
 /// Compute per-iteration time in milliseconds from raw Criterion sample data.
 ///
@@ -113,6 +117,71 @@ pub fn compute_regression_points(
     }
 }
 
+/// Colour for a sample classified as an outlier, as in Criterion's own report: orange
+/// for mild and red for severe. `None` for samples inside the fences.
+pub fn outlier_color(fences: Option<&TukeyFences>, ns_per_iteration: f64) -> Option<&'static str> {
+    match fences?.classify(ns_per_iteration) {
+        OutlierClass::MildLow | OutlierClass::MildHigh => Some(MILD_OUTLIER_COLOR),
+        OutlierClass::SevereLow | OutlierClass::SevereHigh => Some(SEVERE_OUTLIER_COLOR),
+        OutlierClass::NotAnOutlier => None,
+    }
+}
+
+/// A throughput score with an SI prefix, e.g. "1.52 Mops/s".
+pub fn format_ops_per_sec(value: f64) -> String {
+    let (scaled, prefix) = if value >= 1e9 {
+        (value / 1e9, "G")
+    } else if value >= 1e6 {
+        (value / 1e6, "M")
+    } else if value >= 1e3 {
+        (value / 1e3, "k")
+    } else {
+        (value, "")
+    };
+    format!("{scaled:.2} {prefix}ops/s")
+}
+
+/// How many times slower a benchmark is than the fastest one it was compared with.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RelativeSpeed {
+    pub name: String,
+    pub ratio: f64,
+    /// Uncertainty of `ratio` from both standard deviations, when they are known
+    pub error: Option<f64>,
+}
+
+/// Ranks `benchmarks` from fastest to slowest by mean time, as in hyperfine's summary.
+/// The error propagates the relative standard deviations of both means the same way.
+pub fn relative_speeds(benchmarks: &[CriterionMetrics]) -> Vec<RelativeSpeed> {
+    let mut ranked: Vec<(&CriterionMetrics, f64, Option<f64>)> = benchmarks
+        .iter()
+        .map(|b| {
+            let mean = b.mean.as_ref().unwrap_or(&b.time).estimate;
+            (b, mean, b.std_dev.as_ref().map(|sd| sd.estimate))
+        })
+        .filter(|(_, mean, _)| *mean > 0.0 && mean.is_finite())
+        .collect();
+    ranked.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    let Some(&(_, fastest_mean, fastest_sd)) = ranked.first() else {
+        return Vec::new();
+    };
+    ranked
+        .into_iter()
+        .map(|(bench, mean, sd)| {
+            let ratio = mean / fastest_mean;
+            let error = sd.zip(fastest_sd).map(|(sd, fastest_sd)| {
+                ratio * ((sd / mean).powi(2) + (fastest_sd / fastest_mean).powi(2)).sqrt()
+            });
+            RelativeSpeed {
+                name: bench.name.clone(),
+                ratio,
+                error,
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
@@ -271,4 +340,74 @@ mod tests {
         assert!(compute_regression_points(&[], &[], true).is_empty());
         assert!(compute_regression_points(&[], &[], false).is_empty());
     }
+
+    // --- outlier_color ---
+
+    #[test]
+    fn outlier_color_by_class() {
+        let fences = TukeyFences {
+            low_severe: 70.0,
+            low_mild: 85.0,
+            high_mild: 115.0,
+            high_severe: 130.0,
+        };
+        assert_eq!(outlier_color(Some(&fences), 100.0), None);
+        assert_eq!(
+            outlier_color(Some(&fences), 120.0),
+            Some(MILD_OUTLIER_COLOR)
+        );
+        assert_eq!(outlier_color(Some(&fences), 80.0), Some(MILD_OUTLIER_COLOR));
+        assert_eq!(
+            outlier_color(Some(&fences), 60.0),
+            Some(SEVERE_OUTLIER_COLOR)
+        );
+        assert_eq!(outlier_color(None, 1_000.0), None);
+    }
+
+    // --- relative_speeds ---
+
+    fn bench(name: &str, mean: f64, std_dev: Option<f64>) -> CriterionMetrics {
+        let interval = |estimate| crate::parser::criterion::ConfidenceInterval {
+            estimate,
+            ..Default::default()
+        };
+        CriterionMetrics {
+            name: name.to_string(),
+            time: interval(mean),
+            mean: Some(interval(mean)),
+            std_dev: std_dev.map(interval),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn relative_speeds_rank_fastest_first() {
+        let speeds = relative_speeds(&[
+            bench("grep", 211.36, Some(1.024)),
+            bench("rg", 51.59, Some(0.4818)),
+            bench("grep-rs", 103.5, Some(2.4404)),
+        ]);
+        let names: Vec<_> = speeds.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["rg", "grep-rs", "grep"]);
+        assert!((speeds[0].ratio - 1.0).abs() < 1e-9);
+        assert!((speeds[1].ratio - 2.006_203).abs() < 1e-6);
+        assert!((speeds[1].error.unwrap() - 0.050_879).abs() < 1e-5);
+        assert!((speeds[2].ratio - 4.096_918).abs() < 1e-6);
+    }
+
+    #[test]
+    fn relative_speeds_without_std_dev() {
+        let speeds = relative_speeds(&[bench("a", 2.0, None), bench("b", 1.0, Some(0.1))]);
+        assert!((speeds[1].ratio - 2.0).abs() < 1e-9);
+        assert!(speeds[1].error.is_none());
+        assert!(relative_speeds(&[bench("zero", 0.0, None)]).is_empty());
+    }
+
+    #[test]
+    fn format_ops_per_sec_prefixes() {
+        assert_eq!(format_ops_per_sec(1_523_433.3), "1.52 Mops/s");
+        assert_eq!(format_ops_per_sec(2_500.0), "2.50 kops/s");
+        assert_eq!(format_ops_per_sec(12.0), "12.00 ops/s");
+        assert_eq!(format_ops_per_sec(3.2e9), "3.20 Gops/s");
+    }
 }
@@ -0,0 +1,39 @@
+use super::{CriterionBenchmark, CriterionGroupChart, CriterionRelativeSpeed};
+use crate::parser::criterion::CriterionGroup;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct CriterionGroupSectionProps {
+    pub group: CriterionGroup,
+}
+
+/// A benchmark group as a collapsible section: its comparison chart or relative speed
+/// ranking followed by each benchmark. Standalone benchmarks are shown on their own.
+#[function_component(CriterionGroupSection)]
+pub fn criterion_group_section(props: &CriterionGroupSectionProps) -> Html {
+    let group = &props.group;
+    let benchmarks = group.benchmarks.iter().map(|bench| {
+        html! { <CriterionBenchmark metrics={bench.clone()} /> }
+    });
+
+    if group.is_standalone() {
+        return html! { for benchmarks };
+    }
+
+    let count = match group.benchmarks.len() {
+        1 => "1 benchmark".to_string(),
+        n => format!("{n} benchmarks"),
+    };
+
+    html! {
+        <details class="criterion-group" open=true>
+            <summary class="criterion-group-summary">
+                <h2 class="criterion-group-name">{ &group.name }</h2>
+                <span class="criterion-group-count">{ count }</span>
+            </summary>
+            <CriterionGroupChart group={group.clone()} />
+            <CriterionRelativeSpeed group={group.clone()} />
+            { for benchmarks }
+        </details>
+    }
+}
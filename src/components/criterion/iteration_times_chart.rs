@@ -1,6 +1,7 @@
 #![allow(clippy::cast_possible_truncation)]
 #![allow(clippy::cast_sign_loss)]
 #![allow(clippy::cast_precision_loss)]
+use crate::parser::criterion::TukeyFences;
 use web_sys::CanvasRenderingContext2d;
 use yew::prelude::*;
 
@@ -10,6 +11,8 @@ use crate::drawing::{
 };
 use crate::hooks::use_canvas;
 
+use super::data::outlier_color;
+
 const PRIMARY_COLOR: &str = "rgb(31, 120, 180)";
 
 struct PlotArea {
@@ -25,6 +28,8 @@ struct PlotArea {
 pub struct CriterionIterationTimesChartProps {
     pub iteration_count: Vec<f64>,
     pub measured_values: Vec<f64>,
+    #[prop_or_default]
+    pub fences: Option<TukeyFences>,
 }
 
 #[function_component]
@@ -131,7 +136,7 @@ fn draw_iteration_times_chart(
         y_max,
     };
 
-    draw_scatter_points(context, &area, &points, &m);
+    draw_scatter_points(context, &area, &points, props.fences.as_ref(), &m);
     draw_axis_titles(
         context,
         width,
@@ -146,10 +151,12 @@ fn draw_scatter_points(
     context: &CanvasRenderingContext2d,
     area: &PlotArea,
     points: &[(f64, f64)],
+    fences: Option<&TukeyFences>,
     m: &ChartMargins,
 ) {
-    context.set_fill_style_str(PRIMARY_COLOR);
     for (x, y) in points {
+        let color = outlier_color(fences, y * 1_000_000.0);
+        context.set_fill_style_str(color.unwrap_or(PRIMARY_COLOR));
         let px = map_x(*x, area.x_min, area.x_max, area.width, m);
         let py = map_y(*y, area.y_min, area.y_max, area.height, m);
         context.begin_path();
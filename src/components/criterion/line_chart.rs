@@ -1,8 +1,9 @@
 #![allow(clippy::cast_possible_truncation)]
 #![allow(clippy::cast_sign_loss)]
 
+use super::data::format_ops_per_sec;
 use crate::hooks::use_canvas;
-use crate::parser::criterion::CriterionMetrics;
+use crate::parser::criterion::{split_numeric_input, CriterionMetrics};
 use std::collections::BTreeMap;
 use web_sys::CanvasRenderingContext2d;
 use yew::prelude::*;
@@ -25,10 +26,11 @@ pub struct CriterionLineChartProps {
 
 #[function_component(CriterionLineChart)]
 pub fn criterion_line_chart(props: &CriterionLineChartProps) -> Html {
-    let series = build_series(&props.benchmarks);
+    let throughput = is_throughput(&props.benchmarks);
+    let series = build_series(&props.benchmarks, throughput);
     let series_clone = series.clone();
     let canvas_ref = use_canvas(move |ctx, w, h| {
-        draw_chart(ctx, w, h, &series_clone);
+        draw_chart(ctx, w, h, &series_clone, throughput);
     });
 
     html! {
@@ -56,19 +58,27 @@ pub fn criterion_line_chart(props: &CriterionLineChartProps) -> Html {
     }
 }
 
-fn build_series(benchmarks: &[CriterionMetrics]) -> Vec<ChartSeries> {
+/// Whether every benchmark was measured in throughput mode, so the chart plots ops/s.
+fn is_throughput(benchmarks: &[CriterionMetrics]) -> bool {
+    !benchmarks.is_empty() && benchmarks.iter().all(|b| b.ops_per_sec.is_some())
+}
+
+fn build_series(benchmarks: &[CriterionMetrics], throughput: bool) -> Vec<ChartSeries> {
     let mut grouped: BTreeMap<String, Vec<(f64, f64)>> = BTreeMap::new();
 
     for benchmark in benchmarks {
         if let Some((function_name, input_size)) = split_benchmark_name(&benchmark.name) {
-            let mean_ms = benchmark
-                .mean
-                .as_ref()
-                .map_or(benchmark.time.estimate, |m| m.estimate);
+            let value = match &benchmark.ops_per_sec {
+                Some(ops) if throughput => ops.estimate,
+                _ => benchmark
+                    .mean
+                    .as_ref()
+                    .map_or(benchmark.time.estimate, |m| m.estimate),
+            };
             grouped
                 .entry(function_name)
                 .or_default()
-                .push((input_size, mean_ms));
+                .push((input_size, value));
         }
     }
 
@@ -87,12 +97,16 @@ fn build_series(benchmarks: &[CriterionMetrics]) -> Vec<ChartSeries> {
 }
 
 fn split_benchmark_name(name: &str) -> Option<(String, f64)> {
-    let (prefix, last) = name.rsplit_once('/')?;
-    let numeric: f64 = last.parse().ok()?;
-    Some((prefix.to_string(), numeric))
+    split_numeric_input(name).map(|(prefix, numeric)| (prefix.to_string(), numeric))
 }
 
-fn draw_chart(context: &CanvasRenderingContext2d, width: f64, height: f64, series: &[ChartSeries]) {
+fn draw_chart(
+    context: &CanvasRenderingContext2d,
+    width: f64,
+    height: f64,
+    series: &[ChartSeries],
+    throughput: bool,
+) {
     context.clear_rect(0.0, 0.0, width, height);
     context.set_fill_style_str("white");
     context.fill_rect(0.0, 0.0, width, height);
@@ -154,6 +168,11 @@ fn draw_chart(context: &CanvasRenderingContext2d, width: f64, height: f64, serie
         x_max,
         y_min,
         y_max,
+        if throughput {
+            format_ops_per_sec
+        } else {
+            format_ms
+        },
         x_to_px,
         y_to_px,
     );
@@ -192,7 +211,12 @@ fn draw_chart(context: &CanvasRenderingContext2d, width: f64, height: f64, serie
 
     context.save();
     let _ = context.rotate(-std::f64::consts::PI / 2.0);
-    let _ = context.fill_text("Mean Time (ms)", -(height / 2.0), 16.0);
+    let y_title = if throughput {
+        "Throughput (ops/s)"
+    } else {
+        "Mean Time (ms)"
+    };
+    let _ = context.fill_text(y_title, -(height / 2.0), 16.0);
     context.restore();
 }
 
@@ -209,6 +233,7 @@ fn draw_grid_and_axes(
     x_max: f64,
     y_min: f64,
     y_max: f64,
+    format_y: fn(f64) -> String,
     x_to_px: impl Fn(f64) -> f64,
     y_to_px: impl Fn(f64) -> f64,
 ) {
@@ -247,7 +272,7 @@ fn draw_grid_and_axes(
 
         context.set_text_align("right");
         context.set_text_baseline("middle");
-        let _ = context.fill_text(&format_ms(value), axis_left - 8.0, y);
+        let _ = context.fill_text(&format_y(value), axis_left - 8.0, y);
     }
 
     context.set_stroke_style_str("#bdbdbd");
@@ -302,6 +327,13 @@ mod tests {
         assert_eq!(result, Some(("bench".to_string(), 3.15)));
     }
 
+    #[test]
+    fn split_benchmark_name_named_param() {
+        let result = split_benchmark_name("BenchmarkSort/size=1000");
+        assert_eq!(result, Some(("BenchmarkSort".to_string(), 1000.0)));
+        assert_eq!(split_benchmark_name("BenchmarkSort/algo=quick"), None);
+    }
+
     #[test]
     fn split_benchmark_name_no_slash() {
         assert_eq!(split_benchmark_name("noslash"), None);
@@ -1,7 +1,7 @@
 #![allow(clippy::cast_possible_truncation)]
 #![allow(clippy::cast_sign_loss)]
 #![allow(clippy::cast_precision_loss)]
-use crate::parser::criterion::ConfidenceInterval;
+use crate::parser::criterion::{ConfidenceInterval, TukeyFences};
 use web_sys::CanvasRenderingContext2d;
 use yew::prelude::*;
 
@@ -11,7 +11,7 @@ use crate::drawing::{
 };
 use crate::hooks::use_canvas;
 
-use super::data::compute_regression_points;
+use super::data::{compute_regression_points, outlier_color};
 
 const PRIMARY_COLOR: &str = "rgb(31, 120, 180)";
 const BAND_COLOR: &str = "rgba(31, 120, 180, 0.2)";
@@ -30,6 +30,8 @@ pub struct CriterionRegressionChartProps {
     pub iteration_count: Vec<f64>,
     pub measured_values: Vec<f64>,
     pub slope: Option<ConfidenceInterval>,
+    #[prop_or_default]
+    pub fences: Option<TukeyFences>,
 }
 
 #[function_component]
@@ -120,7 +122,7 @@ fn draw_regression_chart(
         y_max,
     };
 
-    draw_scatter_points(context, &area, &points, &m);
+    draw_scatter_points(context, &area, &points, props.fences.as_ref(), &m);
 
     if let Some(slope) = &props.slope {
         draw_regression_confidence_band(context, &area, slope, &m);
@@ -134,10 +136,17 @@ fn draw_scatter_points(
     context: &CanvasRenderingContext2d,
     area: &PlotArea,
     points: &[(f64, f64)],
+    fences: Option<&TukeyFences>,
     m: &ChartMargins,
 ) {
-    context.set_fill_style_str(PRIMARY_COLOR);
     for (x, y) in points {
+        // points are (iterations, total ms); fences are in ns per iteration
+        let color = if *x > 0.0 {
+            outlier_color(fences, y * 1_000_000.0 / x)
+        } else {
+            None
+        };
+        context.set_fill_style_str(color.unwrap_or(PRIMARY_COLOR));
         let px = map_x(*x, area.x_min, area.x_max, area.width, m);
         let py = map_y(*y, area.y_min, area.y_max, area.height, m);
         context.begin_path();
@@ -0,0 +1,50 @@
+use super::data::relative_speeds;
+use crate::parser::criterion::CriterionGroup;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct CriterionRelativeSpeedProps {
+    pub group: CriterionGroup,
+}
+
+/// hyperfine-style summary ranking a group's benchmarks against the fastest one.
+/// Groups over numeric inputs get the comparison chart instead.
+#[function_component(CriterionRelativeSpeed)]
+pub fn criterion_relative_speed(props: &CriterionRelativeSpeedProps) -> Html {
+    let group = &props.group;
+
+    if group.benchmarks.len() < 2 || group.has_numeric_inputs() {
+        return html! {};
+    }
+    let speeds = relative_speeds(&group.benchmarks);
+    let Some((fastest, slower)) = speeds.split_first() else {
+        return html! {};
+    };
+
+    html! {
+        <div class="criterion-relative-speed">
+            <h4 class="chart-title">{ "Relative Speed" }</h4>
+            <p class="relative-speed-fastest">
+                <code>{ &fastest.name }</code>{ " ran" }
+            </p>
+            <ul class="relative-speed-list">
+                { for slower.iter().map(|speed| {
+                    let ratio = match speed.error {
+                        Some(error) => format!("{:.2} \u{b1} {error:.2}", speed.ratio),
+                        None => format!("{:.2}", speed.ratio),
+                    };
+                    html! {
+                        <li>
+                            <span class="relative-speed-ratio">{ ratio }</span>
+                            { " times faster than " }
+                            <code>{ &speed.name }</code>
+                        </li>
+                    }
+                }) }
+            </ul>
+            <p class="chart-description">
+                { "Ratios compare mean times; the uncertainty combines both standard deviations." }
+            </p>
+        </div>
+    }
+}
@@ -9,6 +9,8 @@ use yew::prelude::*;
 use crate::drawing::{draw_axes, draw_x_grid_and_labels, map_x, map_y, ChartMargins, GridConfig};
 use crate::hooks::use_canvas;
 
+use super::data::compute_kde;
+
 #[derive(Clone, Debug, PartialEq, Properties)]
 pub struct CriterionStatDistributionChartProps {
     pub ci: ConfidenceInterval,
@@ -54,32 +56,12 @@ fn draw_chart(
 
     let ci = &props.ci;
 
-    let mean = ci.estimate;
-    let mut std = (ci.upper_bound - ci.lower_bound) / (2.0 * 1.96);
-    if std <= 0.0 || !std.is_finite() {
-        std = (mean.abs() * 0.01).max(1e-9);
-    }
-
-    let x_min = mean - 4.0 * std;
-    let x_max = mean + 4.0 * std;
-
-    let points = 150;
-    let mut kde_points = Vec::with_capacity(points);
-    let step = (x_max - x_min) / (points as f64 - 1.0);
-    let mut y_max = 0.0;
-
-    let norm = 1.0 / (std * (2.0 * std::f64::consts::PI).sqrt());
-
-    for i in 0..points {
-        let x = x_min + i as f64 * step;
-        let exponent = -0.5 * ((x - mean) / std).powi(2);
-        let y = norm * exponent.exp();
-        if y > y_max {
-            y_max = y;
-        }
-        kde_points.push((x, y));
-    }
-    y_max *= 1.1;
+    let (x_min, x_max, kde_points) = if ci.distribution.len() >= 2 {
+        bootstrap_density(&ci.distribution)
+    } else {
+        gaussian_density(ci)
+    };
+    let y_max = kde_points.iter().map(|(_, y)| *y).fold(0.0, f64::max) * 1.1;
 
     let area = MiniPlotArea {
         width,
@@ -110,6 +92,39 @@ fn draw_chart(
     draw_labels(context, width, height, &m, &props.label);
 }
 
+/// Density of the bootstrap distribution itself, estimated from its stored quantiles.
+fn bootstrap_density(distribution: &[f64]) -> (f64, f64, Vec<(f64, f64)>) {
+    let points = compute_kde(distribution, 150);
+    let x_min = points.first().map_or(0.0, |(x, _)| *x);
+    let x_max = points.last().map_or(1.0, |(x, _)| *x);
+    (x_min, x_max, points)
+}
+
+/// A normal curve matching the interval, for estimates whose distribution wasn't kept.
+fn gaussian_density(ci: &ConfidenceInterval) -> (f64, f64, Vec<(f64, f64)>) {
+    let mean = ci.estimate;
+    let mut std = (ci.upper_bound - ci.lower_bound) / (2.0 * 1.96);
+    if std <= 0.0 || !std.is_finite() {
+        std = (mean.abs() * 0.01).max(1e-9);
+    }
+
+    let x_min = mean - 4.0 * std;
+    let x_max = mean + 4.0 * std;
+
+    let points = 150_usize;
+    let step = (x_max - x_min) / (points as f64 - 1.0);
+    let norm = 1.0 / (std * (2.0 * std::f64::consts::PI).sqrt());
+
+    let kde_points = (0..points)
+        .map(|i| {
+            let x = x_min + i as f64 * step;
+            let exponent = -0.5 * ((x - mean) / std).powi(2);
+            (x, norm * exponent.exp())
+        })
+        .collect();
+    (x_min, x_max, kde_points)
+}
+
 fn draw_ci_band(
     context: &CanvasRenderingContext2d,
     area: &MiniPlotArea,
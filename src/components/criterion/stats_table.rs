@@ -1,4 +1,5 @@
-use crate::parser::criterion::{ConfidenceInterval, CriterionMetrics, Throughput};
+use super::data::format_ops_per_sec;
+use crate::parser::criterion::{ConfidenceInterval, CriterionMetrics, RateInterval, Throughput};
 use yew::prelude::*;
 
 #[derive(Properties, PartialEq)]
@@ -9,6 +10,7 @@ pub struct CriterionStatsTableProps {
 #[derive(Clone)]
 enum StatValue {
     Interval(ConfidenceInterval),
+    Rate(RateInterval),
     Throughput(Throughput),
 }
 
@@ -18,6 +20,9 @@ pub fn criterion_stats_table(props: &CriterionStatsTableProps) -> Html {
 
     let mut rows: Vec<(String, StatValue)> = Vec::new();
 
+    if let Some(ops) = &metrics.ops_per_sec {
+        rows.push(("Throughput".to_string(), StatValue::Rate(ops.clone())));
+    }
     if let Some(slope) = &metrics.slope {
         rows.push(("Slope".to_string(), StatValue::Interval(slope.clone())));
     }
@@ -88,6 +93,11 @@ fn format_stat_row(label: &str, value: &StatValue) -> (String, String, String) {
             format_timing(ci.estimate),
             format_timing(ci.upper_bound),
         ),
+        StatValue::Rate(ci) => (
+            format_ops_per_sec(ci.lower_bound),
+            format_ops_per_sec(ci.estimate),
+            format_ops_per_sec(ci.upper_bound),
+        ),
         StatValue::Throughput(throughput) => {
             let estimate = format_throughput(throughput.per_iteration, &throughput.unit);
             (estimate.clone(), estimate.clone(), estimate)
@@ -1,4 +1,5 @@
 use crate::components::CopyButton;
+use crate::parser::{CommitInfo, MachineInfo};
 use yew::prelude::*;
 use yew_router::prelude::*;
 
@@ -10,6 +11,8 @@ pub struct HeaderProps {
     pub tags: Vec<String>,
     pub tests: usize,
     pub benchmarks: usize,
+    pub machine: Option<MachineInfo>,
+    pub commit: Option<CommitInfo>,
 }
 
 #[function_component(DashboardHeader)]
@@ -21,6 +24,8 @@ pub fn dashboard_header(props: &HeaderProps) -> Html {
         tags,
         tests,
         benchmarks,
+        machine,
+        commit,
     } = props;
 
     let Some(navigator) = use_navigator() else {
@@ -95,7 +100,58 @@ pub fn dashboard_header(props: &HeaderProps) -> Html {
                         <span class="metadata-value">{ benchmarks }</span>
                     </div>
                 }
+                if let Some(machine) = machine {
+                    { machine_rows(machine) }
+                }
+                if let Some(commit) = commit {
+                    { commit_rows(commit) }
+                }
             </div>
         </header>
     }
 }
+
+/// Metadata rows for the fields the benchmark tool reported about its machine.
+fn machine_rows(machine: &MachineInfo) -> Html {
+    let load_avg = machine
+        .load_avg
+        .iter()
+        .map(|load| format!("{load:.2}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let rows = [
+        ("Host:", machine.host.clone()),
+        ("OS:", machine.os.clone()),
+        ("CPU:", machine.cpu_summary()),
+        ("Caches:", machine.caches.join(", ")),
+        ("Runtime:", machine.runtime.clone()),
+        ("Load Average:", load_avg),
+        ("Build:", machine.build_type.clone()),
+        ("Executable:", machine.executable.clone()),
+        ("Run At:", machine.date.clone()),
+    ];
+    filled_rows(rows)
+}
+
+/// Metadata rows for the revision the benchmark tool recorded the run against.
+fn commit_rows(commit: &CommitInfo) -> Html {
+    let rows = [
+        ("Commit:", commit.short_id()),
+        ("Branch:", commit.branch.clone()),
+        ("Project:", commit.project.clone()),
+        ("Committed At:", commit.time.clone()),
+    ];
+    filled_rows(rows)
+}
+
+/// One metadata row per labelled value, skipping values the tool left empty.
+fn filled_rows<const N: usize>(rows: [(&str, String); N]) -> Html {
+    html! {
+        { for rows.into_iter().filter(|(_, value)| !value.is_empty()).map(|(label, value)| html! {
+            <div class="metadata-row">
+                <span class="metadata-label">{ label }</span>
+                <span class="metadata-value">{ value }</span>
+            </div>
+        }) }
+    }
+}
@@ -0,0 +1,7 @@
+mod checks_panel;
+mod threshold_panel;
+mod trend_table;
+
+pub use checks_panel::ChecksPanel;
+pub use threshold_panel::ThresholdPanel;
+pub use trend_table::TrendTable;
@@ -0,0 +1,39 @@
+use crate::parser::K6Check;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct ChecksPanelProps {
+    pub checks: Vec<K6Check>,
+    /// Fraction of all checks that passed, 0.0–1.0
+    pub pass_rate: Option<f64>,
+}
+
+#[function_component(ChecksPanel)]
+pub fn checks_panel(props: &ChecksPanelProps) -> Html {
+    html! {
+        <div class="metric-panel panel-checks">
+            <h3>{ "Checks" }</h3>
+            <div class="metric-content">
+                if let Some(rate) = props.pass_rate {
+                    <div class="main-value">{ format!("{:.2}%", rate * 100.0) }</div>
+                    <div class="metric-label">{ "Pass rate" }</div>
+                }
+                { for props.checks.iter().map(|check| html! {
+                    <div class={classes!("metric-row", if check.fails == 0 { "check-pass" } else { "check-fail" })}>
+                        <div class="metric-label">{ &check.name }</div>
+                        <div class="metric-value">{ check_result(check) }</div>
+                    </div>
+                }) }
+            </div>
+        </div>
+    }
+}
+
+/// `✓` for a check without failures, otherwise its passes out of all runs.
+fn check_result(check: &K6Check) -> String {
+    match (check.passes, check.fails) {
+        (0, 0) => "✓".to_string(),
+        (passes, 0) => format!("✓ {passes}"),
+        (passes, fails) => format!("✗ {passes} / {}", passes + fails),
+    }
+}
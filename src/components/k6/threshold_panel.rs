@@ -0,0 +1,33 @@
+use crate::parser::K6Threshold;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct ThresholdPanelProps {
+    pub thresholds: Vec<K6Threshold>,
+}
+
+/// A pass/fail badge per k6 threshold.
+#[function_component(ThresholdPanel)]
+pub fn threshold_panel(props: &ThresholdPanelProps) -> Html {
+    let failed = props.thresholds.iter().filter(|t| !t.ok).count();
+
+    html! {
+        <div class="metric-panel panel-thresholds">
+            <h3>{ "Thresholds" }</h3>
+            <div class="metric-label">
+                { format!("{} of {} passed", props.thresholds.len() - failed, props.thresholds.len()) }
+            </div>
+            <div class="threshold-badges">
+                { for props.thresholds.iter().map(|threshold| html! {
+                    <span class={classes!("threshold-badge", if threshold.ok { "pass" } else { "fail" })}>
+                        { if threshold.ok { "✓ " } else { "✗ " } }
+                        { &threshold.metric }
+                        if !threshold.expression.is_empty() {
+                            <code>{ &threshold.expression }</code>
+                        }
+                    </span>
+                }) }
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{
+    components::wrk::format_latency,
+    parser::{format_bytes, K6Contains, K6Trend},
+};
+use std::collections::BTreeMap;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct TrendTableProps {
+    pub trends: BTreeMap<String, K6Trend>,
+}
+
+/// Every k6 trend, including custom ones, with the summary columns k6 prints by default.
+#[function_component(TrendTable)]
+pub fn trend_table(props: &TrendTableProps) -> Html {
+    html! {
+        <div class="metric-panel panel-trends">
+            <h3>{ "Trends" }</h3>
+            <table class="phase-table">
+                <thead>
+                    <tr>
+                        <th>{ "Metric" }</th>
+                        <th>{ "Average" }</th>
+                        <th>{ "Median" }</th>
+                        <th>{ "p(90)" }</th>
+                        <th>{ "p(95)" }</th>
+                        <th>{ "Max" }</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for props.trends.iter().map(|(name, trend)| {
+                        let format = |value: f64| format_value(trend.contains, value);
+                        let percentile = |key: &str| {
+                            trend.percentiles.get(key).map_or_else(|| "—".to_string(), |v| format(*v))
+                        };
+                        html! {
+                            <tr>
+                                <td>{ name }</td>
+                                <td>{ format(trend.avg) }</td>
+                                <td>{ format(trend.med) }</td>
+                                <td>{ percentile("p(90)") }</td>
+                                <td>{ percentile("p(95)") }</td>
+                                <td>{ format(trend.max) }</td>
+                            </tr>
+                        }
+                    }) }
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+fn format_value(contains: K6Contains, value: f64) -> String {
+    match contains {
+        K6Contains::Time => format_latency(value),
+        K6Contains::Data => format_bytes(value),
+        K6Contains::Number => format!("{value:.2}"),
+    }
+}
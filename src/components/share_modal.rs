@@ -1,27 +1,165 @@
-use gloo_file::{callbacks::read_as_text, File};
-use std::collections::HashMap;
-use web_sys::{Event, HtmlInputElement};
+use crate::parser::{self, InputFormat, ParseError, ParseReport};
+use gloo_file::{
+    callbacks::{read_as_bytes, read_as_text},
+    File,
+};
+use std::{collections::HashMap, path::Path};
+use web_sys::{Event, HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
 
 #[derive(Clone, PartialEq, Properties)]
 pub struct ShareModalProps {
     pub on_close: Callback<()>,
-    pub on_share: Callback<(String, String, Vec<String>)>,
+    /// Input text, the format picked by hand (`None` to auto-detect), description and tags
+    pub on_share: Callback<(String, Option<InputFormat>, String, Vec<String>)>,
+}
+
+/// Criterion's HTML report output, which is skipped when a folder or zip is uploaded.
+const REPORT_ASSETS: [&str; 4] = ["html", "svg", "css", "js"];
+
+fn has_extension(path: &str, extensions: &[&str]) -> bool {
+    Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| extensions.iter().any(|ext| e.eq_ignore_ascii_case(ext)))
 }
 
 pub enum Msg {
-    Files(Vec<File>),
+    /// Selected files with their path inside an uploaded folder, or just their name
+    Files(Vec<(String, File)>),
     LoadedText(String, String),
+    LoadedZip(String, Vec<u8>),
     DescriptionChanged(String),
     TagsChanged(String),
+    FormatChanged(Option<InputFormat>),
     Submit,
 }
 
 pub struct ShareModal {
     readers: HashMap<String, gloo_file::callbacks::FileReader>,
-    files_content: Vec<String>,
+    /// Loaded files as (path, content); several are joined into one bundle
+    files: Vec<(String, String)>,
     description: String,
     tags: String,
+    /// Format override; `None` auto-detects
+    format: Option<InputFormat>,
+    /// Result of parsing the current input, shown before it is encoded
+    report: Option<Result<ParseReport, ParseError>>,
+}
+
+impl ShareModal {
+    /// The text to parse: a single file as is, or several joined into a bundle.
+    fn input(&self) -> Option<String> {
+        match self.files.as_slice() {
+            [] => None,
+            [(_, content)] => Some(content.clone()),
+            files => Some(parser::bundle::join(files)),
+        }
+    }
+
+    fn update_report(&mut self) {
+        self.report = self.input().map(|content| match self.format {
+            Some(format) => parser::parse_as(&content, format),
+            None => parser::parse_with_diagnostics(&content),
+        });
+    }
+
+    fn view_format_picker(&self, ctx: &Context<Self>) -> Html {
+        let on_format_change = ctx.link().callback(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            Msg::FormatChanged(select.value().parse().ok())
+        });
+
+        html! {
+            <div class="form-group">
+                <label for="input-format">{ "Format:" }</label>
+                <select id="input-format" onchange={on_format_change}>
+                    <option value="" selected={self.format.is_none()}>{ "Auto-detect" }</option>
+                    { for InputFormat::ALL.iter().map(|f| html! {
+                        <option value={f.id()} selected={self.format == Some(*f)}>{ f.name() }</option>
+                    }) }
+                </select>
+            </div>
+        }
+    }
+
+    /// File pickers for one or more files (or a zip archive) and for a whole folder.
+    fn view_upload(ctx: &Context<Self>) -> Html {
+        let on_file_change = ctx.link().callback(move |e: Event| {
+            let mut selected_files = Vec::new();
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Some(files) = input.files() {
+                if let Some(iter) = js_sys::try_iter(&files).ok().flatten() {
+                    let files = iter
+                        .filter_map(Result::ok)
+                        .map(web_sys::File::from)
+                        .map(|file| {
+                            // set for files picked through the folder input
+                            let path = js_sys::Reflect::get(&file, &"webkitRelativePath".into())
+                                .ok()
+                                .and_then(|p| p.as_string())
+                                .filter(|p| !p.is_empty())
+                                .unwrap_or_else(|| file.name());
+                            (path, File::from(file))
+                        });
+                    selected_files.extend(files);
+                }
+            }
+            Msg::Files(selected_files)
+        });
+
+        html! {
+            <>
+            <input
+                type="file"
+                onchange={on_file_change.clone()}
+                multiple=true
+                aria-label="Upload benchmark results files or a zip archive"
+            />
+            <label class="folder-upload">
+                { "Or a target/criterion folder: " }
+                <input
+                    type="file"
+                    onchange={on_file_change}
+                    webkitdirectory=true
+                    aria-label="Upload a benchmark results folder"
+                />
+            </label>
+            </>
+        }
+    }
+
+    fn view_report(&self) -> Html {
+        match &self.report {
+            None => html! {},
+            Some(Err(e)) => html! {
+                <div class="parse-diagnostics parse-error" role="alert">{ e.to_string() }</div>
+            },
+            Some(Ok(report)) => html! {
+                <div class="parse-diagnostics">
+                    <div class="parse-summary">
+                        { format!(
+                            "{} {} output: {} result(s)",
+                            if self.format.is_some() { "Parsed as" } else { "Detected" },
+                            report.formats.iter().map(ToString::to_string).collect::<Vec<_>>().join(" + "),
+                            report.results.len(),
+                        ) }
+                    </div>
+                    if report.candidates.len() > 1 {
+                        <div class="parse-candidates">
+                            { "Candidates: " }
+                            { report.candidates.iter().map(|c| format!("{} ({:.0}%)", c.format, c.confidence * 100.0)).collect::<Vec<_>>().join(", ") }
+                        </div>
+                    }
+                    if !report.warnings.is_empty() {
+                        <ul class="parse-warnings">
+                            { for report.warnings.iter().map(|w| html! { <li>{ w.to_string() }</li> }) }
+                        </ul>
+                    }
+                </div>
+            },
+        }
+    }
 }
 
 impl Component for ShareModal {
@@ -31,34 +169,71 @@ impl Component for ShareModal {
     fn create(_ctx: &Context<Self>) -> Self {
         Self {
             readers: HashMap::new(),
-            files_content: Vec::new(),
+            files: Vec::new(),
             description: String::new(),
             tags: String::new(),
+            format: None,
+            report: None,
         }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::Files(files) => {
-                self.files_content.clear();
-                for file in files {
-                    let file_name = file.name();
+                self.files.clear();
+                self.report = None;
+                for (file_name, file) in files {
+                    if has_extension(&file_name, &REPORT_ASSETS) {
+                        continue;
+                    }
                     let task = {
                         let link = ctx.link().clone();
                         let file_name = file_name.clone();
 
-                        read_as_text(&file, move |res| {
-                            let text = res.unwrap_or_default();
-                            link.send_message(Msg::LoadedText(file_name.clone(), text));
-                        })
+                        if has_extension(&file_name, &["zip"]) {
+                            read_as_bytes(&file, move |res| {
+                                let bytes = res.unwrap_or_default();
+                                link.send_message(Msg::LoadedZip(file_name.clone(), bytes));
+                            })
+                        } else {
+                            read_as_text(&file, move |res| {
+                                let text = res.unwrap_or_default();
+                                link.send_message(Msg::LoadedText(file_name.clone(), text));
+                            })
+                        }
                     };
                     self.readers.insert(file_name, task);
                 }
                 true
             }
             Msg::LoadedText(file_name, content) => {
-                self.files_content.push(content);
+                // typing replaces whatever was uploaded
+                if file_name == "manual_input" {
+                    self.files.clear();
+                }
+                self.files.push((file_name.clone(), content));
                 self.readers.remove(&file_name);
+                if self.readers.is_empty() {
+                    self.update_report();
+                }
+                true
+            }
+            Msg::LoadedZip(file_name, bytes) => {
+                match parser::bundle::unzip(&bytes) {
+                    Some(entries) => self.files.extend(
+                        entries
+                            .into_iter()
+                            .filter(|(path, _)| !has_extension(path, &REPORT_ASSETS))
+                            .filter_map(|(path, content)| {
+                                Some((path, String::from_utf8(content).ok()?))
+                            }),
+                    ),
+                    None => log::warn!("Could not read zip archive {file_name}"),
+                }
+                self.readers.remove(&file_name);
+                if self.readers.is_empty() {
+                    self.update_report();
+                }
                 true
             }
             Msg::DescriptionChanged(value) => {
@@ -69,17 +244,25 @@ impl Component for ShareModal {
                 self.tags = value;
                 true
             }
+            Msg::FormatChanged(format) => {
+                self.format = format;
+                self.update_report();
+                true
+            }
             Msg::Submit => {
-                if let Some(content) = self.files_content.first() {
+                if let Some(content) = self.input() {
                     let tags = self
                         .tags
                         .split(',')
                         .map(|s| s.trim().to_string())
                         .filter(|s| !s.is_empty())
                         .collect::<Vec<_>>();
-                    ctx.props()
-                        .on_share
-                        .emit((content.clone(), self.description.clone(), tags));
+                    ctx.props().on_share.emit((
+                        content,
+                        self.format,
+                        self.description.clone(),
+                        tags,
+                    ));
                 }
                 true
             }
@@ -88,21 +271,6 @@ impl Component for ShareModal {
 
     fn view(&self, ctx: &Context<Self>) -> Html {
         let on_close = ctx.props().on_close.clone();
-        let on_file_change = ctx.link().callback(move |e: Event| {
-            let mut selected_files = Vec::new();
-            let input: HtmlInputElement = e.target_unchecked_into();
-            if let Some(files) = input.files() {
-                if let Some(iter) = js_sys::try_iter(&files).ok().flatten() {
-                    let files = iter
-                        .filter_map(Result::ok)
-                        .map(web_sys::File::from)
-                        .map(File::from);
-                    selected_files.extend(files);
-                }
-            }
-            Msg::Files(selected_files)
-        });
-
         let on_textarea_change = ctx.link().callback(move |e: Event| {
             let input: HtmlInputElement = e.target_unchecked_into();
             Msg::LoadedText("manual_input".to_string(), input.value())
@@ -119,6 +287,7 @@ impl Component for ShareModal {
         });
 
         let on_submit = ctx.link().callback(|_| Msg::Submit);
+        let can_submit = !matches!(self.report, Some(Err(_)));
 
         html! {
             <div
@@ -141,21 +310,18 @@ impl Component for ShareModal {
                     <div class="modal-body">
                         <div class="form-group">
                             <label for="test-data">
-                                { "Paste benchmark results (wrk, Criterion.rs) or upload a file:" }
+                                { "Paste benchmark results (wrk, hey, ab, oha, bombardier, Vegeta, k6, HdrHistogram, Criterion.rs, libtest, Google Benchmark, hyperfine, Go, JMH, pytest-benchmark, asv) or upload a file:" }
                             </label>
                             <textarea
                                 id="test-data"
                                 rows="10"
-                                value={self.files_content.first().unwrap_or(&String::new()).clone()}
+                                value={self.input().unwrap_or_default()}
                                 onchange={on_textarea_change}
                             />
-                            <input
-                                type="file"
-                                onchange={on_file_change}
-                                multiple=false
-                                aria-label="Upload benchmark results file"
-                            />
+                            { Self::view_upload(ctx) }
+                            { self.view_report() }
                         </div>
+                        { self.view_format_picker(ctx) }
                         <div class="form-group">
                             <label for="description">{ "Description:" }</label>
                             <input
@@ -176,7 +342,7 @@ impl Component for ShareModal {
                         </div>
                     </div>
                     <div class="modal-footer">
-                        <button class="submit-button" onclick={on_submit}>{ "Share" }</button>
+                        <button class="submit-button" onclick={on_submit} disabled={!can_submit}>{ "Share" }</button>
                     </div>
                 </div>
             </div>
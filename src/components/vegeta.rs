@@ -0,0 +1,3 @@
+mod success_ratio_panel;
+
+pub use success_ratio_panel::SuccessRatioPanel;
@@ -0,0 +1,46 @@
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct SuccessRatioPanelProps {
+    /// Fraction of successful requests, 0.0–1.0
+    pub success: f64,
+    /// Successful requests per second
+    pub throughput: f64,
+    /// Vegeta's error set
+    pub errors: Vec<String>,
+}
+
+#[function_component(SuccessRatioPanel)]
+pub fn success_ratio_panel(props: &SuccessRatioPanelProps) -> Html {
+    let SuccessRatioPanelProps {
+        success,
+        throughput,
+        errors,
+    } = props;
+
+    let class = classes!(
+        "metric-panel",
+        "panel-success",
+        (*success < 1.0).then_some("has-failures")
+    );
+
+    html! {
+        <div {class}>
+            <h3>{ "Success" }</h3>
+            <div class="metric-content">
+                <div class="main-value">{ format!("{:.2}%", success * 100.0) }</div>
+                <div class="metric-label">{ "Success ratio" }</div>
+                <div class="metric-row">
+                    <div class="metric-label">{ "Throughput" }</div>
+                    <div class="metric-value">{ format!("{throughput:.2} req/s") }</div>
+                </div>
+            </div>
+            if !errors.is_empty() {
+                <h4>{ "Error Set" }</h4>
+                <ul class="error-set">
+                    { for errors.iter().map(|error| html! { <li>{ error }</li> }) }
+                </ul>
+            }
+        </div>
+    }
+}
@@ -1,9 +1,23 @@
 mod config;
+mod connection_times_panel;
+mod error_rate_panel;
 mod latency_chart;
+mod latency_over_time_chart;
 mod latency_percentile_chart;
+mod phase_timing_panel;
 mod requests_per_sec_chart;
+mod response_time_histogram;
+mod status_code_panel;
+mod target_rate_panel;
 
 pub use config::WrkConfig;
-pub use latency_chart::LatencyChart;
+pub use connection_times_panel::ConnectionTimesPanel;
+pub use error_rate_panel::ErrorRatePanel;
+pub use latency_chart::{format_latency, LatencyChart};
+pub use latency_over_time_chart::LatencyOverTimeChart;
 pub use latency_percentile_chart::LatencyPercentileChart;
+pub use phase_timing_panel::PhaseTimingPanel;
 pub use requests_per_sec_chart::RequestsPerSecChart;
+pub use response_time_histogram::ResponseTimeHistogram;
+pub use status_code_panel::StatusCodePanel;
+pub use target_rate_panel::TargetRatePanel;
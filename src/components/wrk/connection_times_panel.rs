@@ -0,0 +1,41 @@
+use crate::parser::ConnectionTime;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct ConnectionTimesPanelProps {
+    pub times: Vec<ConnectionTime>,
+}
+
+/// `ab`'s connect/processing/waiting/total breakdown, in milliseconds.
+#[function_component(ConnectionTimesPanel)]
+pub fn connection_times_panel(props: &ConnectionTimesPanelProps) -> Html {
+    html! {
+        <div class="metric-panel panel-connection-times">
+            <h3>{ "Connection Times (ms)" }</h3>
+            <table class="phase-table">
+                <thead>
+                    <tr>
+                        <th>{ "Phase" }</th>
+                        <th>{ "Min" }</th>
+                        <th>{ "Mean" }</th>
+                        <th>{ "+/- SD" }</th>
+                        <th>{ "Median" }</th>
+                        <th>{ "Max" }</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for props.times.iter().map(|time| html! {
+                        <tr>
+                            <td>{ &time.name }</td>
+                            <td>{ time.min }</td>
+                            <td>{ time.mean }</td>
+                            <td>{ time.stddev }</td>
+                            <td>{ time.median }</td>
+                            <td>{ time.max }</td>
+                        </tr>
+                    }) }
+                </tbody>
+            </table>
+        </div>
+    }
+}
@@ -0,0 +1,40 @@
+use crate::parser::ErrorCounts;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct ErrorRatePanelProps {
+    pub errors: ErrorCounts,
+    pub total_requests: u64,
+}
+
+#[function_component(ErrorRatePanel)]
+pub fn error_rate_panel(props: &ErrorRatePanelProps) -> Html {
+    let ErrorRatePanelProps {
+        errors,
+        total_requests,
+    } = props;
+
+    let breakdown = [
+        ("Non-2xx/3xx responses", errors.non_2xx_3xx),
+        ("Connect errors", errors.connect),
+        ("Read errors", errors.read),
+        ("Write errors", errors.write),
+        ("Timeouts", errors.timeout),
+    ];
+
+    html! {
+        <div class="metric-panel panel-errors">
+            <h3>{ "Errors" }</h3>
+            <div class="metric-content">
+                <div class="main-value">{ format!("{:.2}%", errors.error_rate(*total_requests)) }</div>
+                <div class="metric-label">{ "Error rate" }</div>
+                { for breakdown.iter().map(|(label, count)| html! {
+                    <div class="metric-row">
+                        <div class="metric-label">{ label }</div>
+                        <div class="metric-value">{ count }</div>
+                    </div>
+                }) }
+            </div>
+        </div>
+    }
+}
@@ -8,6 +8,9 @@ pub struct ChartProps {
     pub max: f64,
     pub stddev_percent: f64,
     pub distribution: HashMap<String, f64>,
+    /// Fastest request, for tools that report it (e.g. Vegeta)
+    #[prop_or_default]
+    pub min: Option<f64>,
 }
 
 #[function_component(LatencyChart)]
@@ -24,17 +27,22 @@ pub fn latency_chart(props: &ChartProps) -> Html {
             <h3>{ "Latency" }</h3>
             <div class="metric-content">
                 <MetricRow label="Average" value={props.avg} />
+                if let Some(min) = props.min {
+                    <MetricRow label="Min" value={min} />
+                }
                 <MetricRow label="Max" value={props.max} />
-                <MetricRow label="Standard Deviation" value={props.stddev} />
-                <div class="metric-row">
-                    <div class="metric-label">{ "Standard Deviation Percent" }</div>
-                    <div class="metric-value">{ format!("{:.2}%",props.stddev_percent) }</div>
-                </div>
+                if props.stddev > 0.0 {
+                    <MetricRow label="Standard Deviation" value={props.stddev} />
+                    <div class="metric-row">
+                        <div class="metric-label">{ "Standard Deviation Percent" }</div>
+                        <div class="metric-value">{ format!("{:.2}%",props.stddev_percent) }</div>
+                    </div>
+                }
             </div>
             if !props.distribution.is_empty() {
                 <h4>{ "Latency Distribution" }</h4>
                 <div class="metric-content">
-                    { for props.distribution.iter().map(|(key, value)| html! { <MetricRow label={key.clone()} value={value} /> }) }
+                    { for latency_distribution.iter().map(|(key, value)| html! { <MetricRow label={(*key).clone()} value={**value} /> }) }
                 </div>
             }
         </div>
@@ -45,7 +53,8 @@ fn parse_percent_float(key: &str) -> f64 {
     key.trim_end_matches('%').parse().unwrap_or(0.0)
 }
 
-fn format_latency(value: f64) -> String {
+/// Formats milliseconds the way wrk prints latencies, e.g. `6.60ms` or `350.00us`.
+pub fn format_latency(value: f64) -> String {
     if value < 1.0 {
         format!("{:.2}us", value * 1000.0)
     } else {
@@ -0,0 +1,60 @@
+use crate::components::charts::{
+    LineCurveChart, LineCurveChartConfig, LineCurveChartProps, Series,
+};
+use crate::parser::LatencyInterval;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct LatencyOverTimeChartProps {
+    pub intervals: Vec<LatencyInterval>,
+}
+
+/// Per-interval p50/p90/p99/max latency from an `HdrHistogram` interval log.
+#[function_component(LatencyOverTimeChart)]
+pub fn latency_over_time_chart(props: &LatencyOverTimeChartProps) -> Html {
+    let intervals = &props.intervals;
+    let end = intervals
+        .last()
+        .map_or(0.0, |last| last.start + last.length)
+        .max(f64::EPSILON);
+
+    // plot each interval at its midpoint, scaled onto the chart's 0-100 x range
+    let series = |name: &str, color: &str, value: fn(&LatencyInterval) -> f64| {
+        (
+            Series {
+                name: name.to_string(),
+                color: color.to_string(),
+            },
+            intervals
+                .iter()
+                .map(|i| ((i.start + i.length / 2.0) / end * 100.0, value(i)))
+                .collect::<Vec<_>>(),
+        )
+    };
+
+    let chart_props = LineCurveChartProps {
+        data: vec![
+            series("p50", "#4a90e2", |i| i.p50),
+            series("p90", "#27ae60", |i| i.p90),
+            series("p99", "#e67e22", |i| i.p99),
+            series("max", "#c0392b", |i| i.max),
+        ],
+        x: (0..=10)
+            .map(|i| format!("{:.1}s", f64::from(i) * end / 10.0))
+            .collect(),
+        config: LineCurveChartConfig {
+            show_inflection_points: false,
+            stroke_width: 2,
+            show_area_chart: false,
+            x_axis_title: "Time".to_string(),
+            y_axis_title: "Latency (ms)".to_string(),
+        },
+    };
+
+    html! {
+        <div class="metric-panel panel-latency-over-time">
+            <h3>{ "Latency Over Time" }</h3>
+            <LineCurveChart ..chart_props />
+        </div>
+    }
+}
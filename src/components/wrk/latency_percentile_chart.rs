@@ -4,10 +4,19 @@ use crate::components::charts::{
 use crate::parser::PercentileBucket;
 use yew::prelude::*;
 
+/// Tail percentiles listed beneath the chart with the sample counts behind them.
+const TAIL_PERCENTILES: [f64; 4] = [0.99, 0.999, 0.9999, 0.99999];
+
 #[derive(Properties, PartialEq)]
 pub struct ChartProps {
     pub requests_per_sec: f64,
     pub percentiles: Vec<PercentileBucket>,
+    /// wrk2 `-U` spectrum measured without coordinated-omission correction
+    #[prop_or_default]
+    pub uncorrected: Vec<PercentileBucket>,
+    /// Total samples recorded by the histogram, from the `#[... Total count = N]` footer
+    #[prop_or_default]
+    pub total_count: u64,
 }
 
 #[function_component(LatencyPercentileChart)]
@@ -15,34 +24,57 @@ pub fn latency_percentile_chart(props: &ChartProps) -> Html {
     let ChartProps {
         requests_per_sec,
         percentiles,
+        uncorrected,
+        total_count,
     } = &props;
 
+    let all_buckets = || percentiles.iter().chain(uncorrected.iter());
+
     // check if the values should be displayed in s, ms or μs default is ms
-    let (y_axis_title, scale) = if percentiles.iter().any(|p| p.value > 1000.0) {
+    let (y_axis_title, scale) = if all_buckets().any(|p| p.value > 1000.0) {
         ("Latency (s)", 1000.0)
-    } else if percentiles.iter().any(|p| p.value > 1.0) {
+    } else if all_buckets().any(|p| p.value > 1.0) {
         ("Latency (ms)", 1.0)
     } else {
         ("Latency (μs)", 0.001)
     };
 
-    let data_points: Vec<(f64, f64)> = percentiles
-        .iter()
-        .map(|p| (p.percentile * 100.0, p.value / scale))
-        .collect();
+    // HdrHistogram-style log axis: each decade of 1/(1-Percentile) gets equal width.
+    let decades = axis_decades(all_buckets());
+    let to_points = |buckets: &[PercentileBucket]| -> Vec<(f64, f64)> {
+        buckets
+            .iter()
+            .map(|p| {
+                let x = p.log_position().min(decades) / decades * 100.0;
+                (x, p.value / scale)
+            })
+            .collect()
+    };
 
     let x_labels: Vec<String> = (0..=10)
-        .map(|i| format!("{:.0}%", f64::from(i) * 10.0))
+        .map(|i| decade_label(f64::from(i) * decades / 10.0))
         .collect();
 
-    let chart_props = LineCurveChartProps {
-        data: vec![(
+    let mut data = vec![(
+        Series {
+            name: format!("{requests_per_sec} req/s"),
+            color: "#4a90e2".to_string(),
+        },
+        to_points(percentiles),
+    )];
+    if !uncorrected.is_empty() {
+        data[0].0.name = format!("Corrected ({requests_per_sec} req/s)");
+        data.push((
             Series {
-                name: format!("{requests_per_sec} req/s"),
-                color: "#4a90e2".to_string(),
+                name: "Uncorrected".to_string(),
+                color: "#e67e22".to_string(),
             },
-            data_points,
-        )],
+            to_points(uncorrected),
+        ));
+    }
+
+    let chart_props = LineCurveChartProps {
+        data,
         x: x_labels,
         config: LineCurveChartConfig {
             show_inflection_points: false,
@@ -53,12 +85,153 @@ pub fn latency_percentile_chart(props: &ChartProps) -> Html {
         },
     };
 
+    let total_count = if *total_count > 0 {
+        *total_count
+    } else {
+        percentiles.last().map_or(0, |p| p.total_count)
+    };
+    let tail = tail_rows(percentiles, total_count);
+
     html! {
         <div class="metric-panel panel-percentiles full-width">
             <h3>{ "Latency Percentiles" }</h3>
             <div class="percentile-chart">
                 <LineCurveChart ..chart_props />
             </div>
+            if !tail.is_empty() {
+                <div class="percentile-tail-table">
+                    <table>
+                        <thead>
+                            <tr>
+                                <th>{ "Percentile" }</th>
+                                <th>{ "Latency" }</th>
+                                <th>{ "Samples at or below" }</th>
+                                <th>{ "Samples above" }</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            { for tail.iter().map(|bucket| html! {
+                                <tr>
+                                    <td>{ format!("{:.4}%", bucket.percentile * 100.0) }</td>
+                                    <td>{ format!("{:.3}{}", bucket.value / scale, y_axis_unit(scale)) }</td>
+                                    <td>{ bucket.total_count }</td>
+                                    <td>{ total_count.saturating_sub(bucket.total_count) }</td>
+                                </tr>
+                            }) }
+                        </tbody>
+                    </table>
+                </div>
+            }
         </div>
     }
 }
+
+/// Number of decades shown on the log axis, rounded up to 1, 2, 5 or 10 so
+/// that decade boundaries land on the chart's ten evenly spaced labels.
+fn axis_decades<'a>(buckets: impl Iterator<Item = &'a PercentileBucket>) -> f64 {
+    let max_log = buckets
+        .filter(|p| p.percentile < 1.0)
+        .map(PercentileBucket::log_position)
+        .fold(0.0, f64::max);
+    [1.0, 2.0, 5.0]
+        .into_iter()
+        .find(|d| max_log <= *d + 1e-9)
+        .unwrap_or(10.0)
+}
+
+/// Label for a position on the log axis: `0%`, `90%`, `99%`, `99.9%`, ...
+/// Positions between decades are left blank.
+#[allow(clippy::cast_possible_truncation)]
+fn decade_label(log_position: f64) -> String {
+    if (log_position - log_position.round()).abs() > 1e-9 {
+        return String::new();
+    }
+    let decade = log_position.round() as i32;
+    let percent = (1.0 - 10f64.powi(-decade)) * 100.0;
+    format!(
+        "{percent:.prec$}%",
+        prec = usize::try_from(decade - 2).unwrap_or(0)
+    )
+}
+
+/// First recorded bucket at or beyond each tail percentile, for spectra that
+/// carry the `TotalCount` column.
+fn tail_rows(percentiles: &[PercentileBucket], total_count: u64) -> Vec<PercentileBucket> {
+    if total_count == 0 {
+        return Vec::new();
+    }
+    let mut rows: Vec<PercentileBucket> = TAIL_PERCENTILES
+        .iter()
+        .filter_map(|t| {
+            percentiles
+                .iter()
+                .find(|p| p.percentile >= *t && p.total_count > 0)
+        })
+        .cloned()
+        .collect();
+    rows.dedup();
+    rows
+}
+
+fn y_axis_unit(scale: f64) -> &'static str {
+    if scale >= 1000.0 {
+        "s"
+    } else if scale >= 1.0 {
+        "ms"
+    } else {
+        "μs"
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn bucket(percentile: f64, total_count: u64) -> PercentileBucket {
+        PercentileBucket {
+            value: percentile * 10.0,
+            percentile,
+            total_count,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn axis_decades_rounds_up() {
+        assert!((axis_decades([bucket(0.5, 0)].iter()) - 1.0).abs() < f64::EPSILON);
+        assert!((axis_decades([bucket(0.99, 0)].iter()) - 2.0).abs() < f64::EPSILON);
+        assert!((axis_decades([bucket(0.999_99, 0), bucket(1.0, 0)].iter()) - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn decade_label_formats_nines() {
+        assert_eq!(decade_label(0.0), "0%");
+        assert_eq!(decade_label(1.0), "90%");
+        assert_eq!(decade_label(2.0), "99%");
+        assert_eq!(decade_label(3.0), "99.9%");
+        assert_eq!(decade_label(5.0), "99.999%");
+        assert_eq!(decade_label(2.5), "");
+    }
+
+    #[test]
+    fn tail_rows_picks_first_bucket_past_threshold() {
+        let buckets = vec![
+            bucket(0.5, 500),
+            bucket(0.990_625, 991),
+            bucket(0.999_023, 999),
+            bucket(1.0, 1000),
+        ];
+        let rows = tail_rows(&buckets, 1000);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].total_count, 991);
+        assert_eq!(rows[1].total_count, 999);
+        assert_eq!(rows[2].total_count, 1000);
+    }
+
+    #[test]
+    fn tail_rows_empty_without_counts() {
+        let buckets = vec![bucket(0.99, 0), bucket(1.0, 0)];
+        assert!(tail_rows(&buckets, 0).is_empty());
+    }
+}
@@ -0,0 +1,38 @@
+use super::latency_chart::format_latency;
+use crate::parser::PhaseTiming;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct PhaseTimingPanelProps {
+    pub phases: Vec<PhaseTiming>,
+}
+
+/// Average, fastest and slowest time per request phase (DNS, connect, write, wait, read).
+#[function_component(PhaseTimingPanel)]
+pub fn phase_timing_panel(props: &PhaseTimingPanelProps) -> Html {
+    html! {
+        <div class="metric-panel panel-phases">
+            <h3>{ "Request Phases" }</h3>
+            <table class="phase-table">
+                <thead>
+                    <tr>
+                        <th>{ "Phase" }</th>
+                        <th>{ "Average" }</th>
+                        <th>{ "Fastest" }</th>
+                        <th>{ "Slowest" }</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for props.phases.iter().map(|phase| html! {
+                        <tr>
+                            <td>{ &phase.name }</td>
+                            <td>{ format_latency(phase.avg) }</td>
+                            <td>{ format_latency(phase.min) }</td>
+                            <td>{ format_latency(phase.max) }</td>
+                        </tr>
+                    }) }
+                </tbody>
+            </table>
+        </div>
+    }
+}
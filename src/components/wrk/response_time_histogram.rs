@@ -0,0 +1,103 @@
+#![allow(clippy::cast_precision_loss)]
+use web_sys::CanvasRenderingContext2d;
+use yew::prelude::*;
+
+use crate::drawing::{
+    draw_axes, draw_axis_titles, draw_x_grid_and_labels, draw_y_grid_and_labels, format_tick_value,
+    map_x, map_y, ChartMargins, GridConfig,
+};
+use crate::hooks::use_canvas;
+use crate::parser::ResponseTimeBin;
+
+const BAR_COLOR: &str = "rgba(74, 144, 226, 0.8)";
+
+#[derive(Clone, Debug, PartialEq, Properties)]
+pub struct ResponseTimeHistogramProps {
+    pub bins: Vec<ResponseTimeBin>,
+}
+
+/// Bar chart of how many responses fell into each latency bin (oha's response time histogram).
+#[function_component(ResponseTimeHistogram)]
+pub fn response_time_histogram(props: &ResponseTimeHistogramProps) -> Html {
+    let bins = props.bins.clone();
+    let canvas_ref = use_canvas(move |ctx, w, h| {
+        draw_histogram(ctx, w, h, &bins);
+    });
+
+    html! {
+        <div class="metric-panel panel-histogram">
+            <h3>{ "Response Time Histogram" }</h3>
+            <div class="chart-wrapper">
+                <canvas
+                    ref={canvas_ref}
+                    role="img"
+                    aria-label="Response time histogram"
+                    class="chart-canvas"
+                />
+            </div>
+        </div>
+    }
+}
+
+fn draw_histogram(
+    context: &CanvasRenderingContext2d,
+    width: f64,
+    height: f64,
+    bins: &[ResponseTimeBin],
+) {
+    let m = ChartMargins::default();
+    context.set_fill_style_str("white");
+    context.fill_rect(0.0, 0.0, width, height);
+    draw_axes(context, width, height, &m);
+
+    let (Some(first), Some(last)) = (bins.first(), bins.last()) else {
+        draw_axis_titles(context, width, height, &m, "Latency (ms)", "Responses");
+        return;
+    };
+
+    // Bins are evenly spaced; pad the axis by half a bin so the outer bars fit.
+    let step = if bins.len() > 1 {
+        (last.latency - first.latency) / (bins.len() - 1) as f64
+    } else {
+        first.latency.max(1.0)
+    };
+    let x_min = (first.latency - step / 2.0).max(0.0);
+    let x_max = last.latency + step / 2.0;
+    let y_max = bins.iter().map(|b| b.count).max().unwrap_or(0).max(1) as f64 * 1.1;
+
+    draw_x_grid_and_labels(
+        context,
+        width,
+        height,
+        &m,
+        &GridConfig {
+            min: x_min,
+            max: x_max,
+            num_lines: 5,
+            fmt: format_tick_value,
+        },
+    );
+    draw_y_grid_and_labels(
+        context,
+        width,
+        height,
+        &m,
+        &GridConfig {
+            min: 0.0,
+            max: y_max,
+            num_lines: 5,
+            fmt: format_tick_value,
+        },
+    );
+
+    let bar_width = (m.plot_width(width) / bins.len() as f64 * 0.9).max(1.0);
+    let baseline = map_y(0.0, 0.0, y_max, height, &m);
+    context.set_fill_style_str(BAR_COLOR);
+    for bin in bins {
+        let center = map_x(bin.latency, x_min, x_max, width, &m);
+        let top = map_y(bin.count as f64, 0.0, y_max, height, &m);
+        context.fill_rect(center - bar_width / 2.0, top, bar_width, baseline - top);
+    }
+
+    draw_axis_titles(context, width, height, &m, "Latency (ms)", "Responses");
+}
@@ -0,0 +1,42 @@
+use std::collections::BTreeMap;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct StatusCodePanelProps {
+    pub status_codes: BTreeMap<u16, u64>,
+}
+
+#[function_component(StatusCodePanel)]
+pub fn status_code_panel(props: &StatusCodePanelProps) -> Html {
+    let total: u64 = props.status_codes.values().sum();
+
+    html! {
+        <div class="metric-panel panel-status-codes">
+            <h3>{ "Status Codes" }</h3>
+            <div class="metric-content">
+                { for props.status_codes.iter().map(|(code, count)| html! {
+                    <div class={classes!("metric-row", status_class(*code))}>
+                        <div class="metric-label">{ code }</div>
+                        <div class="metric-value">
+                            { format!("{count} ({:.2}%)", share(*count, total)) }
+                        </div>
+                    </div>
+                }) }
+            </div>
+        </div>
+    }
+}
+
+/// CSS class for the status code family, e.g. `status-2xx`.
+fn status_class(code: u16) -> String {
+    format!("status-{}xx", code / 100)
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn share(count: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 / total as f64 * 100.0
+    }
+}
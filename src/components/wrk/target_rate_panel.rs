@@ -0,0 +1,57 @@
+use crate::parser::WrkMetrics;
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct TargetRatePanelProps {
+    pub test: WrkMetrics,
+}
+
+/// wrk2 constant-throughput summary: achieved vs requested rate and the
+/// per-thread calibration wrk2 ran before measuring.
+#[function_component(TargetRatePanel)]
+pub fn target_rate_panel(props: &TargetRatePanelProps) -> Html {
+    let test = &props.test;
+    let ratio = test.target_rate_ratio();
+
+    let class = classes!(
+        "metric-panel",
+        "panel-target-rate",
+        test.missed_target_rate().then_some("target-missed"),
+    );
+
+    html! {
+        <div class={class}>
+            <h3>{ "Target Rate" }</h3>
+            <div class="metric-content">
+                if let Some(ratio) = ratio {
+                    <div class="main-value">{ format!("{:.1}%", ratio * 100.0) }</div>
+                    <div class="metric-label">{ "of target achieved" }</div>
+                    if test.missed_target_rate() {
+                        <div class="target-warning">
+                            { "wrk2 could not sustain the requested rate; latencies reflect a lower load than intended." }
+                        </div>
+                    }
+                    <div class="metric-row">
+                        <div class="metric-label">{ "Target" }</div>
+                        <div class="metric-value">{ format!("{:.0} req/s", test.target_rate) }</div>
+                    </div>
+                    <div class="metric-row">
+                        <div class="metric-label">{ "Achieved" }</div>
+                        <div class="metric-value">{ format!("{:.2} req/s", test.requests_per_sec) }</div>
+                    </div>
+                }
+                if !test.calibration.is_empty() {
+                    <h4>{ "Thread calibration" }</h4>
+                    { for test.calibration.iter().enumerate().map(|(i, c)| html! {
+                        <div class="metric-row">
+                            <div class="metric-label">{ format!("Thread {}", i + 1) }</div>
+                            <div class="metric-value">
+                                { format!("{:.3}ms mean, {}ms sampling", c.mean_latency, c.sampling_interval) }
+                            </div>
+                        </div>
+                    }) }
+                }
+            </div>
+        </div>
+    }
+}
@@ -1,8 +1,11 @@
 use crate::{
     components::{
-        CriterionBenchmark, CriterionGroupChart, DashboardHeader, LatencyChart,
-        LatencyPercentileChart, MetricPanel, RequestsPerSecChart,
+        ChecksPanel, ConnectionTimesPanel, CriterionGroupSection, DashboardHeader, ErrorRatePanel,
+        LatencyChart, LatencyOverTimeChart, LatencyPercentileChart, MetricPanel, PhaseTimingPanel,
+        RequestsPerSecChart, ResponseTimeHistogram, StatusCodePanel, SuccessRatioPanel,
+        TargetRatePanel, ThresholdPanel, TrendTable,
     },
+    parser::{criterion, K6Metrics},
     serializer::decode_dashboard,
     Route,
 };
@@ -35,7 +38,11 @@ pub fn dashboard_page() -> Html {
 
     match decode_dashboard(hash) {
         Ok(data) => {
-            if data.tests.is_empty() && data.benchmarks.is_empty() {
+            if data.tests.is_empty()
+                && data.benchmarks.is_empty()
+                && data.vegeta.is_empty()
+                && data.k6.is_empty()
+            {
                 return html! { <Redirect<Route> to={Route::Home} /> };
             }
 
@@ -47,7 +54,7 @@ pub fn dashboard_page() -> Html {
                 }
             });
 
-            let num_tests = data.tests.len();
+            let num_tests = data.tests.len() + data.vegeta.len() + data.k6.len();
             let num_benchmarks = data.benchmarks.len();
 
             html! {
@@ -59,6 +66,8 @@ pub fn dashboard_page() -> Html {
                         tags={data.tags}
                         tests={num_tests}
                         benchmarks={num_benchmarks}
+                        machine={data.machine.clone()}
+                        commit={data.commit.clone()}
                     />
                     if !data.tests.is_empty() {
                         <div class="dashboard-grid">
@@ -67,22 +76,64 @@ pub fn dashboard_page() -> Html {
                                     <MetricPanel class="panel-requests-per-sec" value={ format_requests_float(test.requests_per_sec) } label="Requests per second" />
                                     <MetricPanel class="panel-total-requests" value={ format_requests(test.total_requests) } label="Total requests" />
                                     <MetricPanel class="panel-data-transferred" value={ test.transfer_per_sec.clone() } label="Data transferred" />
-                                    <MetricPanel class="panel-threads" value={ test.threads.to_string() } label="Threads" />
+                                    if test.threads > 0 {
+                                        <MetricPanel class="panel-threads" value={ test.threads.to_string() } label="Threads" />
+                                    }
                                     <MetricPanel class="panel-connections" value={ test.connections.to_string() } label="Connections" />
+                                    <ErrorRatePanel errors={test.errors.clone()} total_requests={test.total_requests} />
+                                    if test.target_rate > 0.0 || !test.calibration.is_empty() {
+                                        <TargetRatePanel test={test.clone()} />
+                                    }
+                                    if !test.status_codes.is_empty() {
+                                        <StatusCodePanel status_codes={test.status_codes.clone()} />
+                                    }
+                                    if !test.phases.is_empty() {
+                                        <PhaseTimingPanel phases={test.phases.clone()} />
+                                    }
+                                    if !test.connection_times.is_empty() {
+                                        <ConnectionTimesPanel times={test.connection_times.clone()} />
+                                    }
                                     <RequestsPerSecChart avg={test.req.avg} stddev={test.req.stddev} max={test.req.max} stddev_percent={test.req.stddev_percent} />
                                     <LatencyChart avg={test.latency.avg} stddev={test.latency.stddev} max={test.latency.max} stddev_percent={test.latency.stddev_percent} distribution={test.latency_distribution.clone()} />
-                                    if !test.percentiles.is_empty() {
-                                        <LatencyPercentileChart requests_per_sec={test.requests_per_sec} percentiles={test.percentiles.clone()} />
+                                    if !test.percentiles.is_empty() || !test.uncorrected_percentiles.is_empty() {
+                                        <LatencyPercentileChart requests_per_sec={test.requests_per_sec} percentiles={test.percentiles.clone()} uncorrected={test.uncorrected_percentiles.clone()} total_count={test.histogram.total_count} />
+                                    }
+                                    if !test.response_times.is_empty() {
+                                        <ResponseTimeHistogram bins={test.response_times.clone()} />
+                                    }
+                                    if !test.intervals.is_empty() {
+                                        <LatencyOverTimeChart intervals={test.intervals.clone()} />
+                                    }
+                                </>
+                            }) }
+                        </div>
+                    }
+                    if !data.vegeta.is_empty() {
+                        <div class="dashboard-grid vegeta-grid">
+                            { for data.vegeta.iter().map(|attack| html! {
+                                <>
+                                    <MetricPanel class="panel-requests-per-sec" value={ format_requests_float(attack.rate) } label="Attack rate (req/s)" />
+                                    <MetricPanel class="panel-total-requests" value={ format_requests(attack.requests) } label="Total requests" />
+                                    <MetricPanel class="panel-data-transferred" value={ attack.transfer_per_sec() } label="Data received" />
+                                    <MetricPanel class="panel-duration" value={ format!("{:.2}s", attack.duration) } label="Duration" />
+                                    <SuccessRatioPanel success={attack.success} throughput={attack.throughput} errors={attack.errors.clone()} />
+                                    if !attack.status_codes.is_empty() {
+                                        <StatusCodePanel status_codes={attack.status_codes.clone()} />
                                     }
+                                    <LatencyChart avg={attack.latencies.mean} min={attack.latencies.min} stddev={0.0} max={attack.latencies.max} stddev_percent={0.0} distribution={attack.latencies.distribution()} />
                                 </>
                             }) }
                         </div>
                     }
+                    if !data.k6.is_empty() {
+                        <div class="dashboard-grid k6-grid">
+                            { for data.k6.iter().map(view_k6_summary) }
+                        </div>
+                    }
                     if !data.benchmarks.is_empty() {
                         <div class="criterion-section">
-                            <CriterionGroupChart benchmarks={data.benchmarks.clone()} />
-                            { for data.benchmarks.iter().map(|bench| html! {
-                                <CriterionBenchmark metrics={bench.clone()} />
+                            { for criterion::group_benchmarks(&data.benchmarks).into_iter().map(|group| html! {
+                                <CriterionGroupSection {group} />
                             }) }
                         </div>
                     }
@@ -107,6 +158,41 @@ pub fn dashboard_page() -> Html {
     }
 }
 
+fn view_k6_summary(summary: &K6Metrics) -> Html {
+    let requests = summary
+        .counters
+        .get("http_reqs")
+        .cloned()
+        .unwrap_or_default();
+    let vus = summary
+        .gauges
+        .get("vus_max")
+        .or_else(|| summary.gauges.get("vus"))
+        .map_or(0.0, |g| g.max.max(g.value));
+    let duration = summary.trends.get("http_req_duration");
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let total_requests = requests.count as u64;
+
+    html! {
+        <>
+            <MetricPanel class="panel-requests-per-sec" value={ format_requests_float(requests.rate) } label="Requests per second" />
+            <MetricPanel class="panel-total-requests" value={ format_requests(total_requests) } label="Total requests" />
+            <MetricPanel class="panel-data-transferred" value={ summary.transfer_per_sec() } label="Data received" />
+            <MetricPanel class="panel-vus" value={ format!("{vus:.0}") } label="Max VUs" />
+            if let Some(duration) = duration {
+                <LatencyChart avg={duration.avg} min={duration.min} stddev={0.0} max={duration.max} stddev_percent={0.0} distribution={duration.distribution()} />
+            }
+            if !summary.thresholds.is_empty() {
+                <ThresholdPanel thresholds={summary.thresholds.clone()} />
+            }
+            if !summary.checks.is_empty() || summary.check_pass_rate().is_some() {
+                <ChecksPanel checks={summary.checks.clone()} pass_rate={summary.check_pass_rate()} />
+            }
+            <TrendTable trends={summary.trends.clone()} />
+        </>
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
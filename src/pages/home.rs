@@ -1,5 +1,6 @@
 use crate::{
     components::{ShareModal, WrkConfig},
+    parser::InputFormat,
     serializer::encode_dashboard,
     Route,
 };
@@ -29,27 +30,31 @@ pub fn home_page() -> Html {
         })
     };
 
-    let on_share =
-        {
-            let navigator = navigator.clone();
-            let error_msg = error_msg.clone();
-            Callback::from(
-                move |(data, description, tags): (String, String, Vec<String>)| {
-                    match encode_dashboard(&data, description, tags) {
-                        Ok(hash) => {
-                            error_msg.set(None);
-                            navigator.push(&Route::Dashboard);
-                            if let Some(window) = web_sys::window() {
-                                let _ = window.location().set_hash(&hash);
-                            }
-                        }
-                        Err(e) => {
-                            error_msg.set(Some(e.to_string()));
+    let on_share = {
+        let navigator = navigator.clone();
+        let error_msg = error_msg.clone();
+        Callback::from(
+            move |(data, format, description, tags): (
+                String,
+                Option<InputFormat>,
+                String,
+                Vec<String>,
+            )| {
+                match encode_dashboard(&data, format, description, tags) {
+                    Ok(hash) => {
+                        error_msg.set(None);
+                        navigator.push(&Route::Dashboard);
+                        if let Some(window) = web_sys::window() {
+                            let _ = window.location().set_hash(&hash);
                         }
                     }
-                },
-            )
-        };
+                    Err(e) => {
+                        error_msg.set(Some(e.to_string()));
+                    }
+                }
+            },
+        )
+    };
 
     let on_show_modal = {
         let show_modal = show_modal.clone();
@@ -1,14 +1,48 @@
+mod ab;
+mod asv;
+mod bombardier;
+mod bootstrap;
+pub mod bundle;
+mod calibration;
+mod commit;
 pub mod criterion;
+mod diagnostics;
+mod errors;
+mod format;
+mod go_bench;
+mod google_benchmark;
+mod hdr;
+mod hey;
+mod hyperfine;
 mod is_empty;
+mod jmh;
+mod k6;
 mod latency;
+mod libtest;
+mod machine;
 mod metrics;
+mod oha;
 mod percentile;
+mod pytest_benchmark;
 mod request_sec;
+mod segment;
 mod units;
+mod vegeta;
 
+pub use ab::ConnectionTime;
+pub use commit::CommitInfo;
 pub use criterion::CriterionMetrics;
-pub use metrics::{parse_tests, WrkMetrics};
+pub use diagnostics::{Diagnostic, ParseError, ParseReport};
+pub use errors::ErrorCounts;
+pub use format::{detect, Candidate, InputFormat};
+pub use hdr::LatencyInterval;
+pub use k6::{K6Check, K6Contains, K6Metrics, K6Threshold, K6Trend};
+pub use machine::MachineInfo;
+pub use metrics::{PhaseTiming, WrkMetrics};
+pub use oha::ResponseTimeBin;
 pub use percentile::PercentileBucket;
+pub use units::format_bytes;
+pub use vegeta::VegetaMetrics;
 
 use serde::{Deserialize, Serialize};
 
@@ -17,27 +51,127 @@ use serde::{Deserialize, Serialize};
 pub enum BenchmarkResult {
     Wrk(Box<WrkMetrics>),
     Criterion(Box<CriterionMetrics>),
+    Vegeta(Box<VegetaMetrics>),
+    K6(Box<K6Metrics>),
 }
 
-/// Auto-detect input format and parse into unified results.
-/// Tries criterion formats first (JSON, sample.json, CLI), then falls back to wrk.
-pub fn parse_input(output: &str) -> Vec<BenchmarkResult> {
-    if let Some(criterion_results) = criterion::try_parse(output) {
-        return criterion_results
-            .into_iter()
-            .map(|m| BenchmarkResult::Criterion(Box::new(m)))
-            .collect();
+/// Parse into unified results as `format`, or auto-detect the input format when it is `None`.
+/// Detected formats are tried from most to least likely, see [`detect`].
+pub fn parse_report(output: &str, format: Option<InputFormat>) -> Result<ParseReport, ParseError> {
+    match format {
+        Some(format) => parse_as(output, format),
+        None => parse_with_diagnostics(output),
+    }
+}
+
+/// Auto-detects the input format, reporting the detected formats and any lines that fell
+/// back to defaults, or why nothing could be parsed at all.
+///
+/// Input mixing several tools, such as a CI log with `cargo bench` output followed by a
+/// wrk run, is split into segments that are detected and parsed separately, then merged.
+pub fn parse_with_diagnostics(output: &str) -> Result<ParseReport, ParseError> {
+    if output.trim().is_empty() {
+        return Err(ParseError::Empty);
     }
 
-    let wrk_results = parse_tests(output);
-    if !wrk_results.is_empty() {
-        return wrk_results
-            .into_iter()
-            .map(|m| BenchmarkResult::Wrk(Box::new(m)))
-            .collect();
+    let segments = segment::split(output);
+    if segments.len() > 1 {
+        let reports = segments
+            .iter()
+            .filter_map(|seg| {
+                let mut report = detect_and_parse(&seg.text).ok()?;
+                for warning in &mut report.warnings {
+                    warning.line = warning.line.map(|l| l + seg.first_line - 1);
+                }
+                Some(report)
+            })
+            .collect::<Vec<_>>();
+        if reports.len() > 1 {
+            return Ok(merge_reports(reports, detect(output)));
+        }
     }
 
-    Vec::new()
+    detect_and_parse(output)
+}
+
+fn detect_and_parse(output: &str) -> Result<ParseReport, ParseError> {
+    let candidates = detect(output);
+    let best = candidates.first().ok_or(ParseError::UnrecognizedFormat)?;
+    candidates
+        .iter()
+        .find_map(|c| parse_as(output, c.format).ok())
+        .ok_or(ParseError::NoResults {
+            format: best.format,
+        })
+}
+
+fn merge_reports(reports: Vec<ParseReport>, candidates: Vec<Candidate>) -> ParseReport {
+    let mut merged = ParseReport {
+        formats: Vec::new(),
+        candidates,
+        results: Vec::new(),
+        machine: None,
+        commit: None,
+        warnings: Vec::new(),
+    };
+    for report in reports {
+        for format in report.formats {
+            if !merged.formats.contains(&format) {
+                merged.formats.push(format);
+            }
+        }
+        merged.results.extend(report.results);
+        merged.warnings.extend(report.warnings);
+        if let Some(other) = report.machine {
+            match &mut merged.machine {
+                Some(machine) => {
+                    let conflicts = machine.merge(other);
+                    if !conflicts.is_empty() {
+                        merged.warnings.push(Diagnostic::new(format!(
+                            "segments ran on different machines ({}); showing the first",
+                            conflicts.join(", ")
+                        )));
+                    }
+                }
+                None => merged.machine = Some(other),
+            }
+        }
+        if let Some(other) = report.commit {
+            match &merged.commit {
+                Some(commit) if commit.id != other.id => {
+                    merged.warnings.push(Diagnostic::new(
+                        "segments were built from different commits; showing the first",
+                    ));
+                }
+                Some(_) => {}
+                None => merged.commit = Some(other),
+            }
+        }
+    }
+    merged
+}
+
+/// Parses `output` as `format`, skipping auto-detection.
+pub fn parse_as(output: &str, format: InputFormat) -> Result<ParseReport, ParseError> {
+    if output.trim().is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    let mut warnings = Vec::new();
+    let (machine, commit, results) = format.parse(output, &mut warnings);
+    if results.is_empty() {
+        return Err(ParseError::NoResults { format });
+    }
+    warnings.extend(format.diagnose(output, &results));
+
+    Ok(ParseReport {
+        formats: vec![format],
+        candidates: detect(output),
+        results,
+        machine,
+        commit,
+        warnings,
+    })
 }
 
 #[cfg(test)]
@@ -45,10 +179,16 @@ pub fn parse_input(output: &str) -> Vec<BenchmarkResult> {
 mod tests {
     use super::*;
 
+    fn parse_input(output: &str, format: Option<InputFormat>) -> Vec<BenchmarkResult> {
+        parse_report(output, format)
+            .map(|report| report.results)
+            .unwrap_or_default()
+    }
+
     #[test]
     fn parse_input_detects_wrk() {
         let input = include_str!("parser/fixtures/wrk1_basic.txt");
-        let results = parse_input(input);
+        let results = parse_input(input, None);
         assert!(!results.is_empty());
         assert!(matches!(results[0], BenchmarkResult::Wrk(_)));
     }
@@ -56,7 +196,7 @@ mod tests {
     #[test]
     fn parse_input_detects_criterion_cli() {
         let input = include_str!("parser/fixtures/criterion_cli_simple.txt");
-        let results = parse_input(input);
+        let results = parse_input(input, None);
         assert!(!results.is_empty());
         assert!(matches!(results[0], BenchmarkResult::Criterion(_)));
     }
@@ -64,7 +204,7 @@ mod tests {
     #[test]
     fn parse_input_detects_criterion_json() {
         let input = include_str!("parser/fixtures/criterion_json_output.json");
-        let results = parse_input(input);
+        let results = parse_input(input, None);
         assert!(!results.is_empty());
         assert!(matches!(results[0], BenchmarkResult::Criterion(_)));
     }
@@ -72,37 +212,215 @@ mod tests {
     #[test]
     fn parse_input_detects_criterion_sample_json() {
         let input = include_str!("parser/fixtures/criterion_sample.json");
-        let results = parse_input(input);
+        let results = parse_input(input, None);
         assert!(!results.is_empty());
         assert!(matches!(results[0], BenchmarkResult::Criterion(_)));
     }
 
     #[test]
     fn parse_input_empty_returns_empty() {
-        let results = parse_input("");
+        let results = parse_input("", None);
         assert!(results.is_empty());
     }
 
     #[test]
     fn parse_input_garbage_returns_empty() {
-        let results = parse_input("this is not benchmark output at all");
+        let results = parse_input("this is not benchmark output at all", None);
         assert!(results.is_empty());
     }
 
     #[test]
     fn parse_input_criterion_takes_priority_over_wrk() {
         let input = include_str!("parser/fixtures/criterion_cli_simple.txt");
-        let results = parse_input(input);
+        let results = parse_input(input, None);
         assert!(
             matches!(results[0], BenchmarkResult::Criterion(_)),
             "Criterion should be detected before wrk fallback"
         );
     }
 
+    #[test]
+    fn parse_with_diagnostics_reports_format() {
+        let report =
+            parse_with_diagnostics(include_str!("parser/fixtures/wrk1_basic.txt")).unwrap();
+        assert_eq!(report.formats, vec![InputFormat::Wrk]);
+        assert!(report.warnings.is_empty());
+
+        let report =
+            parse_with_diagnostics(include_str!("parser/fixtures/criterion_cli_simple.txt"))
+                .unwrap();
+        assert_eq!(report.formats, vec![InputFormat::CriterionCli]);
+    }
+
+    #[test]
+    fn parse_with_diagnostics_errors() {
+        assert_eq!(parse_with_diagnostics("  \n"), Err(ParseError::Empty));
+        assert_eq!(
+            parse_with_diagnostics("this is not benchmark output at all"),
+            Err(ParseError::UnrecognizedFormat)
+        );
+        assert_eq!(
+            parse_with_diagnostics("Benchmarking fib/20: Warming up for 3.0000 s"),
+            Err(ParseError::NoResults {
+                format: InputFormat::CriterionCli
+            })
+        );
+    }
+
+    #[test]
+    fn parse_with_diagnostics_warns_on_missing_summary() {
+        let report = parse_with_diagnostics("Running 10s test @ http://localhost:8080").unwrap();
+        assert_eq!(report.results.len(), 1);
+        assert_eq!(report.warnings.len(), 2);
+        assert_eq!(report.warnings[0].line, Some(1));
+    }
+
+    #[test]
+    fn parse_as_overrides_detection() {
+        let input = include_str!("parser/fixtures/wrk2_full.txt");
+        let report = parse_as(input, InputFormat::Wrk).unwrap();
+        assert_eq!(report.formats, vec![InputFormat::Wrk]);
+        assert_eq!(report.candidates[0].format, InputFormat::Wrk2);
+        assert_eq!(
+            parse_as(input, InputFormat::CriterionJson),
+            Err(ParseError::NoResults {
+                format: InputFormat::CriterionJson
+            })
+        );
+        assert!(parse_input(input, Some(InputFormat::CriterionCli)).is_empty());
+    }
+
+    #[test]
+    fn parse_input_mixed_criterion_and_wrk() {
+        let input = include_str!("parser/fixtures/mixed_criterion_wrk.txt");
+        let report = parse_with_diagnostics(input).unwrap();
+        assert_eq!(
+            report.formats,
+            vec![InputFormat::CriterionCli, InputFormat::Wrk]
+        );
+
+        let names = report
+            .results
+            .iter()
+            .map(|r| match r {
+                BenchmarkResult::Wrk(m) => m.endpoint.clone(),
+                BenchmarkResult::Criterion(m) => m.name.clone(),
+                BenchmarkResult::Vegeta(_) | BenchmarkResult::K6(_) => String::new(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["fib/20", "http://localhost:8080", "sort/1000"]);
+        assert!(report.warnings.is_empty());
+    }
+
+    #[test]
+    fn merge_reports_keeps_the_first_commit() {
+        let pytest = parse_as(
+            include_str!("parser/fixtures/pytest_benchmark.json"),
+            InputFormat::PytestBenchmark,
+        )
+        .unwrap();
+        let id = pytest.commit.as_ref().unwrap().id.clone();
+        let merged = merge_reports(vec![pytest.clone(), pytest.clone()], Vec::new());
+        assert_eq!(merged.commit.unwrap().id, id);
+        assert!(merged.warnings.is_empty());
+
+        let mut rebuilt = pytest.clone();
+        rebuilt.commit.as_mut().unwrap().id = "0123456789ab".to_string();
+        let merged = merge_reports(vec![pytest, rebuilt], Vec::new());
+        assert_eq!(merged.commit.unwrap().id, id);
+        assert_eq!(
+            merged.warnings[0].message,
+            "segments were built from different commits; showing the first"
+        );
+    }
+
+    #[test]
+    fn merge_reports_combines_machines() {
+        let go = parse_as(
+            include_str!("parser/fixtures/go_bench.txt"),
+            InputFormat::GoBench,
+        )
+        .unwrap();
+        let google = parse_as(
+            include_str!("parser/fixtures/google_benchmark.json"),
+            InputFormat::GoogleBenchmark,
+        )
+        .unwrap();
+        let merged = merge_reports(vec![go, google.clone()], Vec::new());
+        let machine = merged.machine.unwrap();
+        assert_eq!(machine.os, "linux/amd64");
+        assert_eq!(machine.host, "ci-runner-12");
+        assert_eq!(machine.num_cpus, 16);
+        assert_eq!(machine.cpu, "AMD Ryzen 9 5950X 16-Core Processor");
+        assert_eq!(merged.results.len(), 7);
+        let messages: Vec<_> = merged.warnings.iter().map(|w| w.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec!["benchmark `BM_Broken` failed: input file missing"]
+        );
+
+        let mut other_host = google.clone();
+        other_host.machine.as_mut().unwrap().host = "ci-runner-7".to_string();
+        let merged = merge_reports(vec![google, other_host], Vec::new());
+        assert_eq!(merged.machine.unwrap().host, "ci-runner-12");
+        assert!(merged
+            .warnings
+            .iter()
+            .any(|w| w.message == "segments ran on different machines (host); showing the first"));
+    }
+
+    #[test]
+    fn parse_mixed_warnings_use_original_line_numbers() {
+        let input = "fib/20                  time:   [1.9245 ms 1.9298 ms 1.9359 ms]\n\
+            Found 3 outliers among 100 measurements (3.00%)\n\
+            Running 10s test @ http://localhost:8080\n\
+            Transfer/sec:    656.56KB\n";
+        let report = parse_with_diagnostics(input).unwrap();
+        assert_eq!(report.results.len(), 2);
+        assert!(report.warnings.iter().all(|w| w.line == Some(3)));
+    }
+
+    #[test]
+    fn parse_input_detects_vegeta() {
+        for input in [
+            include_str!("parser/fixtures/vegeta_report.txt"),
+            include_str!("parser/fixtures/vegeta_report.json"),
+        ] {
+            let results = parse_input(input, None);
+            assert_eq!(results.len(), 1);
+            assert!(matches!(results[0], BenchmarkResult::Vegeta(_)));
+        }
+    }
+
+    #[test]
+    fn parse_input_detects_k6() {
+        for input in [
+            include_str!("parser/fixtures/k6_summary.txt"),
+            include_str!("parser/fixtures/k6_summary_new.txt"),
+            include_str!("parser/fixtures/k6_summary_export.json"),
+        ] {
+            let results = parse_input(input, None);
+            assert_eq!(results.len(), 1);
+            assert!(matches!(results[0], BenchmarkResult::K6(_)));
+        }
+    }
+
+    #[test]
+    fn parse_input_bombardier_not_wrk() {
+        let input = include_str!("parser/fixtures/bombardier_basic.txt");
+        let report = parse_with_diagnostics(input).unwrap();
+        assert_eq!(report.formats, vec![InputFormat::Bombardier]);
+        let BenchmarkResult::Wrk(metrics) = &report.results[0] else {
+            panic!("expected a load test result");
+        };
+        assert!((metrics.latency.avg - 6.15).abs() < 1e-9);
+        assert!((metrics.req.avg - 20318.46).abs() < 1e-9);
+    }
+
     #[test]
     fn parse_input_wrk2_detected() {
         let input = include_str!("parser/fixtures/wrk2_full.txt");
-        let results = parse_input(input);
+        let results = parse_input(input, None);
         assert!(!results.is_empty());
         assert!(matches!(results[0], BenchmarkResult::Wrk(_)));
     }
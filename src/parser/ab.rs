@@ -0,0 +1,405 @@
+use super::{
+    diagnostics::Diagnostic, errors::ErrorCounts, is_empty, latency::Latency, metrics::WrkMetrics,
+    units,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// One row of `ab`'s "Connection Times (ms)" table, in milliseconds.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct ConnectionTime {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub min: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub mean: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub stddev: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub median: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub max: f64,
+}
+
+/// parses a row of the Connection Times table from the ab output.
+/// `Processing:     3   44  12.1     42     187`
+impl TryFrom<&str> for ConnectionTime {
+    type Error = String;
+    fn try_from(line: &str) -> Result<Self, Self::Error> {
+        let (name, values) = line
+            .trim()
+            .split_once(':')
+            .ok_or("Not a connection times row")?;
+        let values = values
+            .split_whitespace()
+            .map(str::parse::<f64>)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        let [min, mean, stddev, median, max] = values[..] else {
+            return Err("Expected min, mean, sd, median and max".to_string());
+        };
+        Ok(Self {
+            name: name.trim().to_string(),
+            min,
+            mean,
+            stddev,
+            median,
+            max,
+        })
+    }
+}
+
+/// Confidence (0.0–1.0) that `output` came from `ab` (Apache HTTP server benchmarking tool).
+pub fn confidence(output: &str) -> f64 {
+    let signals = [
+        (0.4, output.contains("This is ApacheBench")),
+        (
+            0.2,
+            output.contains("Requests per second:") && output.contains("[#/sec]"),
+        ),
+        (0.2, output.contains("Connection Times (ms)")),
+        (
+            0.2,
+            output.contains("Percentage of the requests served within"),
+        ),
+    ];
+    signals
+        .iter()
+        .filter(|(_, present)| *present)
+        .map(|(weight, _)| weight)
+        .sum()
+}
+
+/// Parses one or more ab runs, each starting at its `Server Software:` line. Values that
+/// can't be read are reported in `warnings`.
+pub fn parse(output: &str, warnings: &mut Vec<Diagnostic>) -> Vec<WrkMetrics> {
+    split_runs(output)
+        .iter()
+        .map(|run| parse_run(run, warnings))
+        .collect()
+}
+
+/// Flags failures ab reports but that are usually not errors: ab counts every
+/// response whose length differs from the first one as a failed request.
+pub fn diagnose(output: &str) -> Vec<Diagnostic> {
+    output
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let length = failure_field(line.trim(), "Length")?.parse::<u64>().ok()?;
+            (length > 0).then(|| {
+                Diagnostic::at_line(
+                    i + 1,
+                    format!(
+                        "{length} requests failed ab's length check (response size differed \
+                         from the first response); they are not counted as errors"
+                    ),
+                )
+            })
+        })
+        .collect()
+}
+
+fn split_runs(output: &str) -> Vec<Vec<(usize, &str)>> {
+    let mut runs: Vec<Vec<(usize, &str)>> = Vec::new();
+    for (number, line) in output.lines().map(str::trim).enumerate() {
+        if line.starts_with("Server Software:") {
+            runs.push(Vec::new());
+        }
+        if let Some(run) = runs.last_mut() {
+            run.push((number + 1, line));
+        }
+    }
+    runs
+}
+
+fn parse_run(lines: &[(usize, &str)], warnings: &mut Vec<Diagnostic>) -> WrkMetrics {
+    let value_of = |key: &str| {
+        lines
+            .iter()
+            .find_map(|(_, l)| l.strip_prefix(key))
+            .map(str::trim)
+            .unwrap_or_default()
+    };
+
+    let scheme = if lines
+        .iter()
+        .any(|(_, l)| l.starts_with("SSL/TLS Protocol:"))
+    {
+        "https"
+    } else {
+        "http"
+    };
+    let endpoint = format!(
+        "{scheme}://{}:{}{}",
+        value_of("Server Hostname:"),
+        value_of("Server Port:"),
+        value_of("Document Path:")
+    );
+
+    let connection_times = section(lines, "Connection Times (ms)")
+        .skip(1)
+        .filter_map(|(number, l)| {
+            ConnectionTime::try_from(l)
+                .map_err(|err| {
+                    warnings.push(Diagnostic::at_line(
+                        number,
+                        format!("connection times row skipped: {err}"),
+                    ));
+                })
+                .ok()
+        })
+        .collect::<Vec<_>>();
+    let total = connection_times
+        .iter()
+        .find(|t| t.name == "Total")
+        .cloned()
+        .unwrap_or_default();
+
+    let failures = lines.iter().find(|(_, l)| l.starts_with("(Connect:"));
+    let mut failure_count = |kind: &str| {
+        let Some((number, value)) = failures.and_then(|(n, l)| Some((*n, failure_field(l, kind)?)))
+        else {
+            return 0;
+        };
+        value.parse().unwrap_or_else(|_| {
+            warnings.push(Diagnostic::at_line(
+                number,
+                format!("`{kind}` failure count `{value}` is not a count"),
+            ));
+            0
+        })
+    };
+    let connect = failure_count("Connect");
+    let read = failure_count("Receive") + failure_count("Exceptions");
+    // ab only prints `Non-2xx responses:` when there were some
+    let non_2xx_3xx = number_of(lines, "Non-2xx responses:", warnings).unwrap_or(0.0);
+    let errors = ErrorCounts {
+        connect,
+        read,
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        non_2xx_3xx: non_2xx_3xx as u64,
+        ..Default::default()
+    };
+
+    let mut summary = |key: &str| summary_number(lines, key, warnings);
+    let connections = summary("Concurrency Level:");
+    let total_requests = summary("Complete requests:");
+    let duration = summary("Time taken for tests:");
+    let requests_per_sec = summary("Requests per second:");
+    let transfer_rate = summary("Transfer rate:");
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    WrkMetrics {
+        endpoint,
+        connections: connections as u64,
+        latency: Latency {
+            avg: total.mean,
+            stddev: total.stddev,
+            max: total.max,
+            ..Default::default()
+        },
+        total_requests: total_requests as u64,
+        duration,
+        requests_per_sec,
+        transfer_per_sec: if transfer_rate > 0.0 {
+            units::format_bytes(transfer_rate * 1024.0)
+        } else {
+            String::new()
+        },
+        latency_distribution: latency_distribution(lines, warnings),
+        errors,
+        connection_times,
+        ..Default::default()
+    }
+}
+
+/// The "Percentage of the requests served" table, e.g. `99%     89`.
+fn latency_distribution(
+    lines: &[(usize, &str)],
+    warnings: &mut Vec<Diagnostic>,
+) -> HashMap<String, f64> {
+    section(lines, "Percentage of the requests served")
+        .filter_map(|(number, l)| {
+            let mut parts = l.split_whitespace();
+            let percent = parts.next().filter(|p| p.ends_with('%'))?;
+            let value = parts.next().unwrap_or_default();
+            let Ok(value) = value.parse() else {
+                warnings.push(Diagnostic::at_line(
+                    number,
+                    format!("percentile row skipped: `{value}` is not a number of ms"),
+                ));
+                return None;
+            };
+            Some((percent.to_string(), value))
+        })
+        .collect()
+}
+
+/// Lines following the one starting with `header`, up to the next blank line.
+fn section<'a>(
+    lines: &'a [(usize, &'a str)],
+    header: &'a str,
+) -> impl Iterator<Item = (usize, &'a str)> {
+    lines
+        .iter()
+        .skip_while(move |(_, l)| !l.starts_with(header))
+        .skip(1)
+        .take_while(|(_, l)| !l.is_empty())
+        .copied()
+}
+
+/// The leading number after `key`, or `None` when the line is missing. A value that
+/// can't be read is reported in `warnings`.
+fn number_of(lines: &[(usize, &str)], key: &str, warnings: &mut Vec<Diagnostic>) -> Option<f64> {
+    let (number, value) = lines
+        .iter()
+        .find_map(|(n, l)| Some((*n, l.strip_prefix(key)?.trim())))?;
+    let parsed = value.split_whitespace().next().and_then(|v| v.parse().ok());
+    if parsed.is_none() {
+        warnings.push(Diagnostic::at_line(
+            number,
+            format!("can't read `{key}` value `{value}`"),
+        ));
+    }
+    parsed
+}
+
+/// Like [`number_of`] for a line ab always prints; a missing one is reported against
+/// the run's `Server Software:` line. Either way the value falls back to 0.
+fn summary_number(lines: &[(usize, &str)], key: &str, warnings: &mut Vec<Diagnostic>) -> f64 {
+    if !lines.iter().any(|(_, l)| l.starts_with(key)) {
+        let message = format!("run has no `{key}` line");
+        warnings.push(match lines.first() {
+            Some((number, _)) => Diagnostic::at_line(*number, message),
+            None => Diagnostic::new(message),
+        });
+        return 0.0;
+    }
+    number_of(lines, key, warnings).unwrap_or(0.0)
+}
+
+/// Value of one failure category on ab's breakdown line,
+/// `(Connect: 0, Receive: 0, Length: 12, Exceptions: 0)`.
+fn failure_field<'a>(line: &'a str, kind: &str) -> Option<&'a str> {
+    line.trim_matches(['(', ')'])
+        .split(',')
+        .filter_map(|part| part.split_once(':'))
+        .find(|(k, _)| k.trim() == kind)
+        .map(|(_, v)| v.trim())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn assert_float_eq(a: f64, b: f64) {
+        const EPSILON: f64 = 1e-6;
+        assert!(
+            (a - b).abs() < EPSILON,
+            "Expected {a} to be approximately equal to {b}"
+        );
+    }
+
+    const AB_OUTPUT: &str = include_str!("fixtures/ab_basic.txt");
+
+    #[test]
+    fn parse_connection_time_row() {
+        let row = ConnectionTime::try_from("Processing:     3   44  12.1     42     187").unwrap();
+        assert_eq!(row.name, "Processing");
+        assert_float_eq(row.min, 3.0);
+        assert_float_eq(row.mean, 44.0);
+        assert_float_eq(row.stddev, 12.1);
+        assert_float_eq(row.median, 42.0);
+        assert_float_eq(row.max, 187.0);
+        assert!(ConnectionTime::try_from("Total:  4  45").is_err());
+    }
+
+    #[test]
+    fn parse_ab_summary() {
+        let runs = parse(AB_OUTPUT, &mut Vec::new());
+        assert_eq!(runs.len(), 1);
+        let metrics = &runs[0];
+
+        assert_eq!(metrics.endpoint, "http://localhost:8080/api/users");
+        assert_eq!(metrics.connections, 100);
+        assert_eq!(metrics.total_requests, 10000);
+        assert_float_eq(metrics.duration, 4.512);
+        assert_float_eq(metrics.requests_per_sec, 2216.31);
+        assert_eq!(metrics.transfer_per_sec, "1.79MB");
+        assert_float_eq(metrics.latency.avg, 45.0);
+        assert_float_eq(metrics.latency.stddev, 12.2);
+        assert_float_eq(metrics.latency.max, 189.0);
+    }
+
+    #[test]
+    fn parse_ab_tables() {
+        let metrics = &parse(AB_OUTPUT, &mut Vec::new())[0];
+        let names = metrics
+            .connection_times
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["Connect", "Processing", "Waiting", "Total"]);
+
+        assert_eq!(metrics.latency_distribution.len(), 9);
+        assert_float_eq(metrics.latency_distribution["99%"], 89.0);
+        assert_float_eq(metrics.latency_distribution["100%"], 189.0);
+    }
+
+    #[test]
+    fn parse_ab_failures() {
+        let metrics = &parse(AB_OUTPUT, &mut Vec::new())[0];
+        assert_eq!(metrics.errors.non_2xx_3xx, 37);
+        assert_eq!(metrics.errors.connect, 2);
+        assert_eq!(metrics.errors.read, 1);
+
+        let warnings = diagnose(AB_OUTPUT);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.starts_with("12 requests failed"));
+    }
+
+    #[test]
+    fn parse_ab_warnings() {
+        let mut warnings = Vec::new();
+        parse(AB_OUTPUT, &mut warnings);
+        assert!(warnings.is_empty());
+
+        let broken = AB_OUTPUT
+            .replace("Concurrency Level:      100\n", "")
+            .replace(
+                "Requests per second:    2216.31",
+                "Requests per second:    n/a",
+            )
+            .replace("Receive: 0", "Receive: ?")
+            .replace(
+                "Waiting:        2   43  12.0     41",
+                "Waiting:        2   43",
+            )
+            .replace("  99%     89", "  99%     -");
+        let metrics = &parse(&broken, &mut warnings)[0];
+        let rendered: Vec<_> = warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "line 42: connection times row skipped: Expected min, mean, sd, median and max",
+                "line 29: `Receive` failure count `?` is not a count",
+                "line 19: run has no `Concurrency Level:` line",
+                "line 33: can't read `Requests per second:` value `n/a [#/sec] (mean)`",
+                "line 53: percentile row skipped: `-` is not a number of ms",
+            ]
+        );
+        assert_eq!(metrics.connections, 0);
+        assert_float_eq(metrics.requests_per_sec, 0.0);
+        assert_eq!(metrics.errors.read, 1);
+        assert_eq!(metrics.connection_times.len(), 3);
+        assert_eq!(metrics.latency_distribution.len(), 8);
+    }
+
+    #[test]
+    fn confidence_prefers_ab() {
+        assert!((confidence(AB_OUTPUT) - 1.0).abs() < 1e-9);
+        assert!(confidence(include_str!("fixtures/hey_basic.txt")).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,326 @@
+//! airspeed velocity (asv) result files, `results/<machine>/<commit>-<env>.json` in the
+//! version 2 layout where each benchmark's values sit in the `result_columns` order.
+//! Only timing benchmarks (`time_*`) become [`CriterionMetrics`]; `mem_*` and `track_*`
+//! results have no time to show.
+
+use super::bootstrap;
+use super::commit::CommitInfo;
+use super::criterion::{ConfidenceInterval, CriterionMetrics, Outliers, TimeUnit, TukeyFences};
+use super::machine::MachineInfo;
+use super::Diagnostic;
+use serde_json::Value;
+
+const S_TO_MS: f64 = 1_000.0;
+const S_TO_NS: f64 = 1_000_000_000.0;
+
+/// Confidence (0.0–1.0) that `output` is an asv result file.
+pub fn confidence(output: &str) -> f64 {
+    let trimmed = output.trim();
+    if !trimmed.starts_with('{') {
+        return 0.0;
+    }
+    let signals = [
+        (0.4, trimmed.contains("\"result_columns\"")),
+        (0.2, trimmed.contains("\"commit_hash\"")),
+        (0.2, trimmed.contains("\"env_name\"")),
+        (0.2, trimmed.contains("\"results\"")),
+    ];
+    signals
+        .iter()
+        .filter(|(_, present)| *present)
+        .map(|(weight, _)| weight)
+        .sum()
+}
+
+/// Parses every timing benchmark, one result per parameter combination, plus the
+/// machine and commit the file was recorded for. Combinations that failed (asv records
+/// their `result` as `null`) are skipped and reported in `warnings`.
+pub fn parse(
+    output: &str,
+    warnings: &mut Vec<Diagnostic>,
+) -> (
+    Option<MachineInfo>,
+    Option<CommitInfo>,
+    Vec<CriterionMetrics>,
+) {
+    let Ok(value) = serde_json::from_str::<Value>(output.trim()) else {
+        return (None, None, Vec::new());
+    };
+    let columns: Vec<&str> = value
+        .get("result_columns")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+
+    let benchmarks = value
+        .get("results")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .filter_map(|(name, row)| {
+            let row = row.as_array()?;
+            let column = |key: &str| {
+                columns
+                    .iter()
+                    .position(|c| *c == key)
+                    .and_then(|i| row.get(i))
+            };
+            let (class, method) = split_benchmark(name);
+            method.starts_with("time").then(|| {
+                param_combinations(column("params"))
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(i, params)| {
+                        let parsed = parse_combination(class, method, &params, i, &column);
+                        if parsed.is_none() {
+                            warnings.push(Diagnostic::new(if params.is_empty() {
+                                format!("benchmark `{method}` failed in asv")
+                            } else {
+                                format!(
+                                    "benchmark `{method}` (params {}) failed in asv",
+                                    params.join(", ")
+                                )
+                            }));
+                        }
+                        parsed
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+        .flatten()
+        .collect();
+
+    let machine = value
+        .get("params")
+        .map(|params| parse_machine(params, warnings));
+    let commit = value
+        .get("commit_hash")
+        .and_then(Value::as_str)
+        .map(|id| CommitInfo {
+            id: id.to_string(),
+            ..Default::default()
+        });
+    (machine, commit, benchmarks)
+}
+
+/// `benchmarks.ArraySuite.time_sum` into its suite class (or module) and method.
+fn split_benchmark(name: &str) -> (&str, &str) {
+    let (path, method) = name.rsplit_once('.').unwrap_or(("", name));
+    let class = path.rsplit_once('.').map_or(path, |(_, class)| class);
+    (class, method)
+}
+
+/// Every combination of parameter values, in the order asv stores their results:
+/// the cartesian product with the last parameter varying fastest.
+fn param_combinations(params: Option<&Value>) -> Vec<Vec<&str>> {
+    let mut combinations = vec![Vec::new()];
+    for values in params.and_then(Value::as_array).into_iter().flatten() {
+        let values: Vec<&str> = values
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+        combinations = combinations
+            .into_iter()
+            .flat_map(|prefix| {
+                values.iter().map(move |value| {
+                    let mut combination = prefix.clone();
+                    combination.push(*value);
+                    combination
+                })
+            })
+            .collect();
+    }
+    combinations
+}
+
+/// The `index`th value of each statistics column. `result` is asv's median in
+/// seconds; the `stats_ci_99` bounds are its 99% confidence interval.
+fn parse_combination<'a>(
+    class: &str,
+    method: &str,
+    params: &[&str],
+    index: usize,
+    column: &dyn Fn(&str) -> Option<&'a Value>,
+) -> Option<CriterionMetrics> {
+    let stat = |key: &str| column(key)?.get(index)?.as_f64();
+    let median = stat("result")?;
+    let time = ConfidenceInterval {
+        lower_bound: stat("stats_ci_99_a").unwrap_or(median) * S_TO_MS,
+        estimate: median * S_TO_MS,
+        upper_bound: stat("stats_ci_99_b").unwrap_or(median) * S_TO_MS,
+        unit: TimeUnit::Milliseconds,
+        ..Default::default()
+    };
+
+    let samples: Vec<f64> = column("samples")
+        .and_then(|samples| samples.get(index))
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_f64)
+        .map(|seconds| seconds * S_TO_NS)
+        .collect();
+    let number = stat("stats_number").unwrap_or(1.0);
+    let iteration_count = vec![number; samples.len()];
+    let measured_values: Vec<f64> = samples.iter().map(|ns| ns * number).collect();
+    let estimates = bootstrap::estimate(&iteration_count, &measured_values);
+
+    let name = std::iter::once(method)
+        .chain(params.iter().copied())
+        .collect::<Vec<_>>()
+        .join("/");
+    Some(CriterionMetrics {
+        name,
+        group: Some(class.to_string()),
+        median: Some(
+            estimates
+                .as_ref()
+                .map_or_else(|| time.clone(), |e| e.median.clone()),
+        ),
+        time,
+        mean: estimates.as_ref().map(|e| e.mean.clone()),
+        std_dev: estimates.as_ref().map(|e| e.std_dev.clone()),
+        median_abs_dev: estimates.map(|e| e.median_abs_dev),
+        outliers: TukeyFences::from_samples(&samples)
+            .map(|fences| Outliers::from_samples(fences, &samples)),
+        iteration_count,
+        measured_values,
+        ..Default::default()
+    })
+}
+
+/// The machine description asv keeps under `params` alongside the environment's
+/// requirements. A `num_cpu` that can't be read is left at 0 and reported in `warnings`.
+fn parse_machine(params: &Value, warnings: &mut Vec<Diagnostic>) -> MachineInfo {
+    let text = |key: &str| {
+        params
+            .get(key)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    let python = text("python");
+    let num_cpu = text("num_cpu");
+    let num_cpus = if num_cpu.is_empty() {
+        0
+    } else {
+        num_cpu.parse().unwrap_or_else(|_| {
+            warnings.push(Diagnostic::new(format!(
+                "can't read `num_cpu` value `{num_cpu}`"
+            )));
+            0
+        })
+    };
+    MachineInfo {
+        host: text("machine"),
+        os: text("os"),
+        cpu: text("cpu"),
+        num_cpus,
+        runtime: if python.is_empty() {
+            python
+        } else {
+            format!("Python {python}")
+        },
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = include_str!("fixtures/asv_results.json");
+
+    fn assert_float_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "Expected {a} to be approximately {b}");
+    }
+
+    #[test]
+    fn parse_timing_benchmarks() {
+        let mut warnings = Vec::new();
+        let (_, _, results) = parse(OUTPUT, &mut warnings);
+        let names: Vec<_> = results.iter().map(|b| b.name.as_str()).collect();
+        // mem_ results are skipped, and so are failed (null) runs, with a warning
+        assert_eq!(names, vec!["time_sum/10", "time_sum/1000", "time_import"]);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].message, "benchmark `time_broken` failed in asv");
+
+        let large = &results[1];
+        assert_eq!(large.group_name(), "ArraySuite");
+        assert_float_eq(large.time.estimate, 0.945);
+        assert_float_eq(large.time.lower_bound, 0.931);
+        assert_float_eq(large.time.upper_bound, 0.962);
+        assert_eq!(large.iteration_count, vec![10.0; 10]);
+        assert_float_eq(large.measured_values[0], 9_450_000.0);
+        assert!(large.mean.is_some() && large.std_dev.is_some());
+
+        let import = &results[2];
+        assert_eq!(import.group_name(), "benchmarks");
+        assert!(import.iteration_count.is_empty());
+        assert_eq!(import.median.as_ref().unwrap(), &import.time);
+    }
+
+    #[test]
+    fn parse_warns_on_failed_combination() {
+        let output = OUTPUT.replacen("[0.000012, 0.000945]", "[0.000012, null]", 1);
+        let mut warnings = Vec::new();
+        let (_, _, results) = parse(&output, &mut warnings);
+        assert!(results.iter().all(|b| b.name != "time_sum/1000"));
+        let messages: Vec<_> = warnings.iter().map(|w| w.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "benchmark `time_broken` failed in asv",
+                "benchmark `time_sum` (params 1000) failed in asv",
+            ]
+        );
+    }
+
+    #[test]
+    fn param_combinations_vary_last_fastest() {
+        let params = serde_json::json!([["'a'", "'b'"], ["1", "2"]]);
+        assert_eq!(
+            param_combinations(Some(&params)),
+            vec![
+                vec!["'a'", "1"],
+                vec!["'a'", "2"],
+                vec!["'b'", "1"],
+                vec!["'b'", "2"]
+            ]
+        );
+        assert_eq!(param_combinations(None), vec![Vec::<&str>::new()]);
+    }
+
+    #[test]
+    fn parse_machine_and_commit() {
+        let (machine, commit, _) = parse(OUTPUT, &mut Vec::new());
+        let machine = machine.unwrap();
+        assert_eq!(machine.host, "bench-host-3");
+        assert_eq!(machine.num_cpus, 8);
+        assert_eq!(machine.runtime, "Python 3.11");
+        assert_eq!(
+            commit.unwrap().id,
+            "3b8e4f01c2d9a6e7f5b4c3d2e1f0a9b8c7d6e5f4"
+        );
+
+        let output = OUTPUT.replace("\"num_cpu\": \"8\"", "\"num_cpu\": \"eight\"");
+        let mut warnings = Vec::new();
+        let (machine, _, _) = parse(&output, &mut warnings);
+        assert_eq!(machine.unwrap().num_cpus, 0);
+        assert!(warnings
+            .iter()
+            .any(|w| w.message == "can't read `num_cpu` value `eight`"));
+    }
+
+    #[test]
+    fn test_confidence() {
+        assert!((confidence(OUTPUT) - 1.0).abs() < 1e-9);
+        assert!(confidence(include_str!("fixtures/hyperfine.json")) < 0.5);
+        assert!(confidence(include_str!("fixtures/pytest_benchmark.json")).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,433 @@
+use super::{
+    errors::ErrorCounts, latency::Latency, metrics::WrkMetrics, request_sec::RequestSec, units,
+    Diagnostic,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Confidence (0.0–1.0) that `output` is bombardier's text output.
+pub fn text_confidence(output: &str) -> f64 {
+    let signals = [
+        (0.4, output.contains("Bombarding ")),
+        (0.2, output.contains("Reqs/sec")),
+        (0.2, output.contains("HTTP codes:")),
+        (0.2, output.contains("Throughput:")),
+    ];
+    signals
+        .iter()
+        .filter(|(_, present)| *present)
+        .map(|(weight, _)| weight)
+        .sum()
+}
+
+/// Confidence (0.0–1.0) that `output` is `bombardier --print r --format json` output.
+pub fn json_confidence(output: &str) -> f64 {
+    let trimmed = output.trim();
+    if !trimmed.starts_with('{') {
+        return 0.0;
+    }
+    let signals = [
+        (
+            0.4,
+            trimmed.contains("\"spec\"") && trimmed.contains("\"result\""),
+        ),
+        (0.2, trimmed.contains("\"req2xx\"")),
+        (0.2, trimmed.contains("\"timeTakenSeconds\"")),
+        (0.2, trimmed.contains("\"numberOfConnections\"")),
+    ];
+    signals
+        .iter()
+        .filter(|(_, present)| *present)
+        .map(|(weight, _)| weight)
+        .sum()
+}
+
+/// Parses one or more bombardier runs, each starting at its `Bombarding ...` line.
+/// Values that can't be read are reported in `warnings`.
+pub fn parse_text(output: &str, warnings: &mut Vec<Diagnostic>) -> Vec<WrkMetrics> {
+    let mut runs: Vec<Vec<(usize, &str)>> = Vec::new();
+    for (number, line) in output.lines().map(str::trim).enumerate() {
+        if line.starts_with("Bombarding ") {
+            runs.push(Vec::new());
+        }
+        if let Some(run) = runs.last_mut() {
+            run.push((number + 1, line));
+        }
+    }
+    runs.iter().map(|run| parse_run(run, warnings)).collect()
+}
+
+fn parse_run(lines: &[(usize, &str)], warnings: &mut Vec<Diagnostic>) -> WrkMetrics {
+    let latency = stats_row(lines, "Latency", latency_stats, warnings)
+        .map(|[avg, stddev, max]| Latency {
+            avg,
+            stddev,
+            max,
+            ..Default::default()
+        })
+        .unwrap_or_default();
+    let req = stats_row(lines, "Reqs/sec", request_stats, warnings)
+        .map(|[avg, stddev, max]| RequestSec {
+            avg,
+            stddev,
+            max,
+            ..Default::default()
+        })
+        .unwrap_or_default();
+
+    // `Bombarding <url> for 10s using 125 connection(s)`, or `with 10000 request(s)`
+    let (header_line, header) = lines.first().copied().unwrap_or_default();
+    let word_after = |word: &str| {
+        let mut tokens = header.split_whitespace();
+        tokens.find(|t| *t == word)?;
+        tokens.next()
+    };
+    let connections = header
+        .split_whitespace()
+        .rev()
+        .nth(1)
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+    let duration = word_after("for").map_or(0.0, |d| {
+        units::parse_go_duration(d).unwrap_or_else(|err| {
+            warnings.push(Diagnostic::at_line(
+                header_line,
+                format!("can't read the run duration: {err}"),
+            ));
+            0.0
+        }) / 1000.0
+    });
+
+    let codes = section(lines, |l| l.starts_with("HTTP codes:"))
+        .take_while(|(_, l)| !l.ends_with(':'))
+        .flat_map(|(number, l)| l.split(',').map(move |part| (number, part)))
+        .filter_map(|(number, part)| {
+            let (class, count) = part.split_once(" - ")?;
+            let Ok(count) = count.trim().parse::<u64>() else {
+                warnings.push(Diagnostic::at_line(
+                    number,
+                    format!("HTTP code count `{}` skipped", part.trim()),
+                ));
+                return None;
+            };
+            Some((class.trim(), count))
+        })
+        .collect::<HashMap<_, _>>();
+    let code = |class: &str| codes.get(class).copied().unwrap_or(0);
+
+    let mut errors = ErrorCounts {
+        non_2xx_3xx: code("1xx") + code("4xx") + code("5xx"),
+        ..Default::default()
+    };
+    for (number, line) in
+        section(lines, |l| l == "Errors:").take_while(|(_, l)| !l.starts_with("Throughput:"))
+    {
+        let Some((message, count)) = line.rsplit_once(" - ") else {
+            continue;
+        };
+        match count.trim().parse() {
+            Ok(count) => errors.add_message(message, count),
+            Err(_) => warnings.push(Diagnostic::at_line(
+                number,
+                format!(
+                    "error `{message}` skipped: `{}` is not a count",
+                    count.trim()
+                ),
+            )),
+        }
+    }
+
+    WrkMetrics {
+        endpoint: word_after("Bombarding").unwrap_or_default().to_string(),
+        connections,
+        requests_per_sec: req.avg,
+        latency,
+        req,
+        total_requests: codes.values().sum(),
+        duration,
+        transfer_per_sec: lines
+            .iter()
+            .find_map(|(_, l)| l.strip_prefix("Throughput:"))
+            .map(|t| t.trim().trim_end_matches("/s").to_string())
+            .unwrap_or_default(),
+        latency_distribution: latency_distribution(lines, warnings),
+        errors,
+        ..Default::default()
+    }
+}
+
+/// The `Latency`/`Reqs/sec` row's average, standard deviation and max. A row that can't
+/// be read is skipped with a warning.
+fn stats_row(
+    lines: &[(usize, &str)],
+    label: &str,
+    parse: fn(&[&str]) -> Result<[f64; 3], String>,
+    warnings: &mut Vec<Diagnostic>,
+) -> Option<[f64; 3]> {
+    let (number, values) = lines
+        .iter()
+        .filter_map(|(number, l)| Some((*number, l.strip_prefix(label)?)))
+        // skip the `Latency Distribution` header
+        .find(|(_, rest)| rest.trim_start().starts_with(|c: char| c.is_ascii_digit()))?;
+    parse(&values.split_whitespace().collect::<Vec<_>>())
+        .map_err(|err| {
+            warnings.push(Diagnostic::at_line(
+                number,
+                format!("`{label}` row skipped: {err}"),
+            ));
+        })
+        .ok()
+}
+
+/// The `--latencies` percentiles; rows that can't be read are skipped with a warning.
+fn latency_distribution(
+    lines: &[(usize, &str)],
+    warnings: &mut Vec<Diagnostic>,
+) -> HashMap<String, f64> {
+    section(lines, |l| l == "Latency Distribution")
+        .map_while(|(number, l)| {
+            let (percent, value) = l.split_once(char::is_whitespace)?;
+            percent.ends_with('%').then(|| {
+                units::parse_go_duration(value)
+                    .map(|value| (percent.to_string(), value))
+                    .map_err(|err| {
+                        warnings.push(Diagnostic::at_line(
+                            number,
+                            format!("latency distribution row skipped: {err}"),
+                        ));
+                    })
+                    .ok()
+            })
+        })
+        .flatten()
+        .collect()
+}
+
+/// Lines after the first one matching `header`.
+fn section<'a>(
+    lines: &'a [(usize, &'a str)],
+    header: impl Fn(&str) -> bool,
+) -> impl Iterator<Item = (usize, &'a str)> {
+    lines
+        .iter()
+        .skip_while(move |(_, l)| !header(l))
+        .skip(1)
+        .copied()
+}
+
+/// bombardier's `Latency` row has average, standard deviation and max, but no
+/// `+/- Stdev` column like wrk's, so it can't go through `Latency::try_from`.
+fn latency_stats(values: &[&str]) -> Result<[f64; 3], String> {
+    let [avg, stddev, max, ..] = values else {
+        return Err(format!("expected 3 columns, found {}", values.len()));
+    };
+    Ok([
+        units::parse_go_duration(avg)?,
+        units::parse_go_duration(stddev)?,
+        units::parse_go_duration(max)?,
+    ])
+}
+
+/// The `Reqs/sec` row, which like `Latency` has no `+/- Stdev` column.
+fn request_stats(values: &[&str]) -> Result<[f64; 3], String> {
+    let [avg, stddev, max, ..] = values else {
+        return Err(format!("expected 3 columns, found {}", values.len()));
+    };
+    Ok([
+        units::parse_count(avg)?,
+        units::parse_count(stddev)?,
+        units::parse_count(max)?,
+    ])
+}
+
+/// Parses `bombardier --print r --format json` output. Latencies are in microseconds.
+/// Entries that can't be read are skipped and reported in `warnings`.
+#[allow(clippy::cast_precision_loss)]
+pub fn parse_json(output: &str, warnings: &mut Vec<Diagnostic>) -> Option<WrkMetrics> {
+    let json: Value = serde_json::from_str(output.trim()).ok()?;
+    let spec = json.get("spec")?;
+    let result = json.get("result")?;
+    let number = |value: &Value, key: &str| value.get(key).and_then(Value::as_f64).unwrap_or(0.0);
+    let count = |key: &str| result.get(key).and_then(Value::as_u64).unwrap_or(0);
+
+    let latency = result.get("latency").unwrap_or(&Value::Null);
+    let rps = result.get("rps").unwrap_or(&Value::Null);
+    let duration = number(result, "timeTakenSeconds");
+    let bytes = number(result, "bytesRead") + number(result, "bytesWritten");
+
+    let mut errors = ErrorCounts {
+        non_2xx_3xx: count("req1xx") + count("req4xx") + count("req5xx"),
+        ..Default::default()
+    };
+    for error in result
+        .get("errors")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let description = error.get("description").and_then(Value::as_str);
+        match description.zip(error.get("count").and_then(Value::as_u64)) {
+            Some((description, count)) => errors.add_message(description, count),
+            None => warnings.push(Diagnostic::new(format!("error `{error}` skipped"))),
+        }
+    }
+
+    Some(WrkMetrics {
+        endpoint: spec
+            .get("url")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        connections: spec
+            .get("numberOfConnections")
+            .and_then(Value::as_u64)
+            .unwrap_or(0),
+        latency: Latency {
+            avg: number(latency, "mean") / 1000.0,
+            stddev: number(latency, "stddev") / 1000.0,
+            max: number(latency, "max") / 1000.0,
+            ..Default::default()
+        },
+        req: RequestSec {
+            avg: number(rps, "mean"),
+            stddev: number(rps, "stddev"),
+            max: number(rps, "max"),
+            ..Default::default()
+        },
+        total_requests: ["req1xx", "req2xx", "req3xx", "req4xx", "req5xx", "others"]
+            .into_iter()
+            .map(count)
+            .sum(),
+        duration,
+        requests_per_sec: number(rps, "mean"),
+        transfer_per_sec: if duration > 0.0 && bytes > 0.0 {
+            units::format_bytes(bytes / duration)
+        } else {
+            String::new()
+        },
+        latency_distribution: latency
+            .get("percentiles")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .filter_map(|(percent, us)| {
+                let Some(us) = us.as_f64() else {
+                    warnings.push(Diagnostic::new(format!(
+                        "latency percentile `{percent}: {us}` skipped"
+                    )));
+                    return None;
+                };
+                Some((format!("{percent}%"), us / 1000.0))
+            })
+            .collect(),
+        errors,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn assert_float_eq(a: f64, b: f64) {
+        const EPSILON: f64 = 1e-6;
+        assert!(
+            (a - b).abs() < EPSILON,
+            "Expected {a} to be approximately equal to {b}"
+        );
+    }
+
+    const BOMBARDIER_TEXT: &str = include_str!("fixtures/bombardier_basic.txt");
+    const BOMBARDIER_JSON: &str = include_str!("fixtures/bombardier_result.json");
+
+    #[test]
+    fn parse_text_summary() {
+        let runs = parse_text(BOMBARDIER_TEXT, &mut Vec::new());
+        assert_eq!(runs.len(), 1);
+        let metrics = &runs[0];
+
+        assert_eq!(metrics.endpoint, "http://localhost:8080/api/users");
+        assert_eq!(metrics.connections, 125);
+        assert_float_eq(metrics.duration, 10.0);
+        assert_float_eq(metrics.req.avg, 20318.46);
+        assert_float_eq(metrics.req.stddev, 2139.08);
+        assert_float_eq(metrics.req.max, 26742.89);
+        assert_float_eq(metrics.latency.avg, 6.15);
+        assert_float_eq(metrics.latency.stddev, 1.56);
+        assert_float_eq(metrics.latency.max, 49.30);
+        assert_eq!(metrics.transfer_per_sec, "4.51MB");
+    }
+
+    #[test]
+    fn parse_text_codes_errors_and_distribution() {
+        let metrics = &parse_text(BOMBARDIER_TEXT, &mut Vec::new())[0];
+        assert_eq!(metrics.total_requests, 203_200);
+        assert_eq!(metrics.errors.non_2xx_3xx, 152);
+        assert_eq!(metrics.errors.connect, 27);
+
+        assert_eq!(metrics.latency_distribution.len(), 5);
+        assert_float_eq(metrics.latency_distribution["99%"], 11.73);
+    }
+
+    #[test]
+    fn latency_row_with_microseconds() {
+        let [avg, stddev, max] = latency_stats(&["615.23us", "92.10µs", "1.05s"]).unwrap();
+        assert_float_eq(avg, 0.615_23);
+        assert_float_eq(stddev, 0.0921);
+        assert_float_eq(max, 1050.0);
+        assert!(latency_stats(&["615.23us", "92.10µs"]).is_err());
+        assert!(Latency::try_from("Latency        6.15ms     1.56ms    49.30ms").is_err());
+    }
+
+    #[test]
+    fn parse_text_warnings() {
+        let mut warnings = Vec::new();
+        parse_text(BOMBARDIER_TEXT, &mut warnings);
+        assert!(warnings.is_empty());
+
+        let broken = BOMBARDIER_TEXT
+            .replace("49.30ms", "49.30xs")
+            .replace("8.64ms", "8.64")
+            .replace("11.73ms", "11.73qs");
+        let metrics = &parse_text(&broken, &mut warnings)[0];
+        let rendered: Vec<_> = warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "line 7: `Latency` row skipped: unknown time unit in `49.30xs`",
+                "line 13: latency distribution row skipped: unknown time unit in `11.73qs`",
+            ]
+        );
+        assert!(metrics.latency.is_empty());
+        // a bare number is read as milliseconds
+        assert_eq!(metrics.latency_distribution.len(), 4);
+    }
+
+    #[test]
+    fn parse_json_matches_text() {
+        let json = parse_json(BOMBARDIER_JSON, &mut Vec::new()).unwrap();
+        let text = &parse_text(BOMBARDIER_TEXT, &mut Vec::new())[0];
+
+        assert_eq!(json.endpoint, text.endpoint);
+        assert_eq!(json.connections, text.connections);
+        assert_eq!(json.total_requests, text.total_requests);
+        assert_eq!(json.errors, text.errors);
+        assert_eq!(json.transfer_per_sec, text.transfer_per_sec);
+        assert_float_eq(json.latency.avg, 6.1502);
+        assert_float_eq(json.latency.max, text.latency.max);
+        assert_float_eq(json.req.avg, text.req.avg);
+        assert_float_eq(
+            json.latency_distribution["95%"],
+            text.latency_distribution["95%"],
+        );
+    }
+
+    #[test]
+    fn confidence_prefers_bombardier() {
+        assert!((text_confidence(BOMBARDIER_TEXT) - 1.0).abs() < 1e-9);
+        assert!((json_confidence(BOMBARDIER_JSON) - 1.0).abs() < 1e-9);
+        assert!(text_confidence(include_str!("fixtures/wrk1_basic.txt")).abs() < 1e-9);
+        assert!(json_confidence(include_str!("fixtures/oha_basic.json")).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,228 @@
+//! Bootstrap confidence intervals for raw Criterion samples, resampled the way
+//! Criterion does. The generator is seeded with a constant so the same `sample.json`
+//! always gives the same intervals, and no OS randomness is needed under wasm.
+
+use super::criterion::{ConfidenceInterval, TimeUnit};
+
+/// Criterion defaults to 100 000 resamples; 10 000 keeps parsing fast in the browser
+/// while the 95% bounds move by well under a percent.
+pub const RESAMPLES: usize = 10_000;
+
+/// Evenly spaced quantiles kept from each bootstrap distribution so charts can draw it.
+pub const DISTRIBUTION_POINTS: usize = 25;
+
+const SEED: u64 = 0x5EED_C417_E410_0001;
+const CONFIDENCE_LEVEL: f64 = 0.95;
+/// Scales the median absolute deviation to estimate a normal standard deviation.
+const MAD_SCALE: f64 = 1.4826;
+const NS_TO_MS: f64 = 0.000_001;
+
+/// Bootstrapped estimates for a run, in milliseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Estimates {
+    pub mean: ConfidenceInterval,
+    pub median: ConfidenceInterval,
+    pub std_dev: ConfidenceInterval,
+    pub median_abs_dev: ConfidenceInterval,
+    /// Time per iteration from a least-squares fit through the origin, only for
+    /// samples with varying iteration counts
+    pub slope: Option<ConfidenceInterval>,
+}
+
+/// `SplitMix64`: tiny, fast and good enough for picking resample indices.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A uniform index below `len`, by Lemire's multiply-shift.
+    #[allow(clippy::cast_possible_truncation)]
+    fn index(&mut self, len: usize) -> usize {
+        ((u128::from(self.next_u64()) * len as u128) >> 64) as usize
+    }
+}
+
+/// Bootstraps `iters`/`times` (ns per sample) the way Criterion's analysis does:
+/// mean, median, standard deviation and MAD of the time per iteration, plus the slope
+/// of total time against iterations.
+pub fn estimate(iters: &[f64], times: &[f64]) -> Option<Estimates> {
+    let samples: Vec<(f64, f64)> = iters
+        .iter()
+        .zip(times)
+        .filter(|(n, t)| **n > 0.0 && n.is_finite() && t.is_finite())
+        .map(|(n, t)| (*n, *t))
+        .collect();
+    if samples.len() < 2 {
+        return None;
+    }
+    let per_iteration: Vec<f64> = samples.iter().map(|(n, t)| t / n).collect();
+
+    let mut rng = Rng(SEED);
+    let mut resample = vec![0.0; per_iteration.len()];
+    let mut distributions: [Vec<f64>; 4] = Default::default();
+    for _ in 0..RESAMPLES {
+        for value in &mut resample {
+            *value = per_iteration[rng.index(per_iteration.len())];
+        }
+        for (distribution, statistic) in distributions.iter_mut().zip(univariate(&mut resample)) {
+            distribution.push(statistic);
+        }
+    }
+    let [mean, median, std_dev, median_abs_dev] = distributions;
+    let [mean_estimate, median_estimate, std_dev_estimate, mad_estimate] =
+        univariate(&mut per_iteration.clone());
+
+    let slope = has_varying_iterations(&samples).then(|| {
+        let mut pairs = vec![(0.0, 0.0); samples.len()];
+        let distribution = (0..RESAMPLES)
+            .map(|_| {
+                for pair in &mut pairs {
+                    *pair = samples[rng.index(samples.len())];
+                }
+                slope(&pairs)
+            })
+            .collect();
+        interval(slope(&samples), distribution)
+    });
+
+    Some(Estimates {
+        mean: interval(mean_estimate, mean),
+        median: interval(median_estimate, median),
+        std_dev: interval(std_dev_estimate, std_dev),
+        median_abs_dev: interval(mad_estimate, median_abs_dev),
+        slope,
+    })
+}
+
+/// Mean, median, sample standard deviation and scaled MAD. Sorts `values` in place.
+#[allow(clippy::cast_precision_loss)]
+fn univariate(values: &mut [f64]) -> [f64; 4] {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let std_dev = (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+    values.sort_unstable_by(f64::total_cmp);
+    let median = percentile(values, 0.5);
+    let mut deviations: Vec<f64> = values.iter().map(|v| (v - median).abs()).collect();
+    deviations.sort_unstable_by(f64::total_cmp);
+    let median_abs_dev = percentile(&deviations, 0.5) * MAD_SCALE;
+    [mean, median, std_dev, median_abs_dev]
+}
+
+/// Least-squares slope through the origin, as Criterion fits its linear samples.
+fn slope(pairs: &[(f64, f64)]) -> f64 {
+    let xy: f64 = pairs.iter().map(|(x, y)| x * y).sum();
+    let x2: f64 = pairs.iter().map(|(x, _)| x * x).sum();
+    xy / x2
+}
+
+/// Flat sampling runs every sample with the same iteration count, so there is no slope.
+fn has_varying_iterations(samples: &[(f64, f64)]) -> bool {
+    samples
+        .windows(2)
+        .any(|w| (w[0].0 - w[1].0).abs() > f64::EPSILON)
+}
+
+/// Value at fraction `p` of `sorted`, interpolating between the nearest ranks like
+/// Criterion's `Percentiles::at`.
+pub fn percentile(sorted: &[f64], p: f64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let rank = p * (sorted.len() - 1) as f64;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (floor, ceil) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[floor] + (sorted[ceil] - sorted[floor]) * (rank - rank.floor())
+}
+
+/// A confidence interval (in ms) from a point estimate and its bootstrap distribution (in ns).
+#[allow(clippy::cast_precision_loss)]
+fn interval(estimate: f64, mut distribution: Vec<f64>) -> ConfidenceInterval {
+    distribution.sort_unstable_by(f64::total_cmp);
+    let n = distribution.len() as f64;
+    let mean = distribution.iter().sum::<f64>() / n;
+    let standard_error =
+        (distribution.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+    let tail = (1.0 - CONFIDENCE_LEVEL) / 2.0;
+    ConfidenceInterval {
+        lower_bound: percentile(&distribution, tail) * NS_TO_MS,
+        estimate: estimate * NS_TO_MS,
+        upper_bound: percentile(&distribution, 1.0 - tail) * NS_TO_MS,
+        unit: TimeUnit::Milliseconds,
+        standard_error: standard_error * NS_TO_MS,
+        distribution: (0..DISTRIBUTION_POINTS)
+            .map(|i| {
+                percentile(&distribution, (i as f64 + 0.5) / DISTRIBUTION_POINTS as f64) * NS_TO_MS
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn sample() -> (Vec<f64>, Vec<f64>) {
+        // 100 ns per iteration with a little deterministic noise and one slow sample
+        let iters: Vec<f64> = (1..=20).map(|i| f64::from(i) * 1_000.0).collect();
+        let times = (0..20u8)
+            .zip(&iters)
+            .map(|(i, n)| {
+                let noise = if i == 13 {
+                    1.5
+                } else {
+                    1.0 + 0.01 * f64::from(i % 5)
+                };
+                n * 100.0 * noise
+            })
+            .collect();
+        (iters, times)
+    }
+
+    #[test]
+    fn percentile_interpolates() {
+        let sorted = [1.0, 2.0, 3.0, 4.0];
+        assert!((percentile(&sorted, 0.5) - 2.5).abs() < 1e-12);
+        assert!((percentile(&sorted, 0.25) - 1.75).abs() < 1e-12);
+        assert!((percentile(&sorted, 1.0) - 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn estimates_bracket_the_point_estimate() {
+        let (iters, times) = sample();
+        let estimates = estimate(&iters, &times).unwrap();
+        let slope = estimates.slope.as_ref().unwrap();
+        for ci in [
+            &estimates.mean,
+            &estimates.median,
+            &estimates.std_dev,
+            &estimates.median_abs_dev,
+            slope,
+        ] {
+            assert!(ci.lower_bound <= ci.estimate && ci.estimate <= ci.upper_bound);
+            assert!(ci.standard_error > 0.0);
+            assert_eq!(ci.distribution.len(), DISTRIBUTION_POINTS);
+            assert!(ci.distribution.windows(2).all(|w| w[0] <= w[1]));
+        }
+        // the slow sample pulls the mean up but not the median
+        assert!(estimates.mean.estimate > estimates.median.estimate);
+        assert!((estimates.median.estimate - 0.000_102).abs() < 0.000_001);
+    }
+
+    #[test]
+    fn estimates_are_deterministic() {
+        let (iters, times) = sample();
+        assert_eq!(estimate(&iters, &times), estimate(&iters, &times));
+    }
+
+    #[test]
+    fn flat_sampling_has_no_slope() {
+        let estimates = estimate(&[10.0, 10.0, 10.0], &[1_000.0, 1_100.0, 900.0]).unwrap();
+        assert!(estimates.slope.is_none());
+        assert!(estimate(&[10.0], &[1_000.0]).is_none());
+    }
+}
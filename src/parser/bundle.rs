@@ -0,0 +1,159 @@
+//! Several input files joined into one text, separated by `==> path <==` headers the
+//! way `head` and `tail` print multiple files. The share modal builds one from a
+//! multi-file, folder or zip upload, and the same text can be pasted by hand.
+
+use std::io::Read;
+
+const LOCAL_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x0605_4b50;
+
+/// Joins `(path, content)` pairs into a bundle, ordered by path.
+pub fn join(files: &[(String, String)]) -> String {
+    let mut files = files.iter().collect::<Vec<_>>();
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    files
+        .iter()
+        .map(|(path, content)| format!("==> {path} <==\n{}\n", content.trim_end()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Splits a bundle back into `(path, content)` pairs. Text before the first header is dropped.
+pub fn split(output: &str) -> Vec<(&str, &str)> {
+    let mut files = Vec::new();
+    let mut current: Option<(&str, usize)> = None;
+    let mut offset = 0;
+    for line in output.split_inclusive('\n') {
+        if let Some(path) = header(line) {
+            if let Some((path, start)) = current.take() {
+                files.push((path, &output[start..offset]));
+            }
+            current = Some((path, offset + line.len()));
+        }
+        offset += line.len();
+    }
+    if let Some((path, start)) = current {
+        files.push((path, &output[start..]));
+    }
+    files
+}
+
+/// Whether `output` starts like a bundle.
+pub fn is_bundle(output: &str) -> bool {
+    output
+        .lines()
+        .find(|l| !l.trim().is_empty())
+        .and_then(header)
+        .is_some()
+}
+
+fn header(line: &str) -> Option<&str> {
+    line.trim_end()
+        .strip_prefix("==> ")?
+        .strip_suffix(" <==")
+        .filter(|path| !path.is_empty())
+}
+
+/// Reads the files of a zip archive as `(path, bytes)` pairs, skipping directories.
+/// Only stored and deflated entries without zip64 extensions are supported.
+pub fn unzip(archive: &[u8]) -> Option<Vec<(String, Vec<u8>)>> {
+    // the end of central directory record is 22 bytes plus a trailing comment
+    let eocd = (0..=archive.len().checked_sub(22)?)
+        .rev()
+        .find(|&i| read_u32(archive, i) == Some(END_OF_CENTRAL_DIRECTORY_SIGNATURE))?;
+    let entries = read_u16(archive, eocd + 10)?;
+    let mut pos = usize::try_from(read_u32(archive, eocd + 16)?).ok()?;
+
+    let mut files = Vec::new();
+    for _ in 0..entries {
+        if read_u32(archive, pos)? != CENTRAL_HEADER_SIGNATURE {
+            return None;
+        }
+        let method = read_u16(archive, pos + 10)?;
+        let compressed_size = usize::try_from(read_u32(archive, pos + 20)?).ok()?;
+        let name_len = usize::from(read_u16(archive, pos + 28)?);
+        let extra_len = usize::from(read_u16(archive, pos + 30)?);
+        let comment_len = usize::from(read_u16(archive, pos + 32)?);
+        let local = usize::try_from(read_u32(archive, pos + 42)?).ok()?;
+        let name = String::from_utf8_lossy(archive.get(pos + 46..pos + 46 + name_len)?);
+        pos += 46 + name_len + extra_len + comment_len;
+
+        if name.ends_with('/') {
+            continue;
+        }
+        if read_u32(archive, local)? != LOCAL_HEADER_SIGNATURE {
+            return None;
+        }
+        let start = local
+            + 30
+            + usize::from(read_u16(archive, local + 26)?)
+            + usize::from(read_u16(archive, local + 28)?);
+        let data = archive.get(start..start + compressed_size)?;
+        let content = match method {
+            0 => data.to_vec(),
+            8 => {
+                let mut content = Vec::new();
+                flate2::read::DeflateDecoder::new(data)
+                    .read_to_end(&mut content)
+                    .ok()?;
+                content
+            }
+            _ => return None,
+        };
+        files.push((name.into_owned(), content));
+    }
+    Some(files)
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn join_and_split_round_trip() {
+        let files = vec![
+            (
+                "b/new/sample.json".to_string(),
+                "{\"iters\":[1]}\n".to_string(),
+            ),
+            ("a/new/estimates.json".to_string(), "{}".to_string()),
+        ];
+        let bundle = join(&files);
+        assert!(bundle.starts_with("==> a/new/estimates.json <==\n{}\n\n==> b/"));
+        assert!(is_bundle(&bundle));
+
+        let split = split(&bundle);
+        assert_eq!(split.len(), 2);
+        assert_eq!(split[0].0, "a/new/estimates.json");
+        assert_eq!(split[0].1.trim(), "{}");
+        assert_eq!(split[1].1.trim(), "{\"iters\":[1]}");
+    }
+
+    #[test]
+    fn split_ignores_preamble() {
+        let split = split("$ head target/criterion/*/new/*.json\n==> x.json <==\n1\n");
+        assert_eq!(split, vec![("x.json", "1\n")]);
+        assert!(!is_bundle("fib/20  time: [1 ms 2 ms 3 ms]"));
+    }
+
+    #[test]
+    fn unzip_criterion_tree() {
+        let files = unzip(include_bytes!("fixtures/criterion_directory.zip")).unwrap();
+        // 22 json files and one html report; the directory entry is skipped
+        assert_eq!(files.len(), 23);
+        let (name, content) = &files[0];
+        assert_eq!(name, "target/criterion/fib/10/base/benchmark.json");
+        assert!(String::from_utf8_lossy(content).contains("\"full_id\": \"fib/10\""));
+        assert!(unzip(b"not a zip").is_none());
+    }
+}
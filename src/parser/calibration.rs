@@ -0,0 +1,136 @@
+use super::{is_empty, units};
+use serde::{Deserialize, Serialize};
+
+/// Per-thread calibration result printed by wrk2 before the test starts.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct ThreadCalibration {
+    /// Mean latency observed during calibration, in milliseconds
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub mean_latency: f64,
+    /// Interval wrk2 uses to sample the request rate, in milliseconds
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub sampling_interval: f64,
+}
+
+/// parses a thread calibration line from the WRK2 output.
+/// `Thread calibration: mean lat.: 1.473ms, rate sampling interval: 10ms`
+/// `Thread calibration: mean lat.: 9747 usec, rate sampling interval: 21 msec`
+impl TryFrom<&str> for ThreadCalibration {
+    type Error = String;
+    fn try_from(line: &str) -> Result<Self, Self::Error> {
+        let rest = line
+            .trim()
+            .strip_prefix("Thread calibration:")
+            .ok_or("Not a thread calibration line")?;
+        let (latency, interval) = rest
+            .split_once(',')
+            .ok_or("Invalid thread calibration format")?;
+        let value_of = |part: &str| {
+            part.rsplit_once(':')
+                .ok_or("Invalid thread calibration format".to_string())
+                .and_then(|(_, v)| units::parse_to_milliseconds(v))
+        };
+        Ok(Self {
+            mean_latency: value_of(latency)?,
+            sampling_interval: value_of(interval)?,
+        })
+    }
+}
+
+/// Returns true if the line is a `wrk`/`wrk2` invocation, optionally prefixed by a shell prompt.
+pub fn is_wrk_command(line: &str) -> bool {
+    is_command(line, &["wrk", "wrk2"])
+}
+
+/// Returns true if the line runs one of `programs`, optionally prefixed by a shell prompt
+/// or given as a path such as `./wrk2`.
+pub fn is_command(line: &str, programs: &[&str]) -> bool {
+    line.trim_start_matches(['$', '>', ' '])
+        .split_whitespace()
+        .next()
+        .and_then(|program| program.rsplit('/').next())
+        .is_some_and(|program| programs.contains(&program))
+}
+
+/// Extracts the target throughput (`-R`/`--rate`) from a wrk2 command line.
+/// Accepts `-R 2000`, `-R2000`, `--rate 2k` and `--rate=2k`.
+pub fn parse_target_rate(command: &str) -> Option<f64> {
+    let mut tokens = command.split_whitespace();
+    while let Some(token) = tokens.next() {
+        let value = match token {
+            "-R" | "--rate" => tokens.next(),
+            t if t.starts_with("--rate=") => t.strip_prefix("--rate="),
+            t if t.starts_with("-R") => t.strip_prefix("-R"),
+            _ => continue,
+        };
+        let rate = units::parse_count(value?).unwrap_or(0.0);
+        if rate > 0.0 {
+            return Some(rate);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn assert_float_eq(a: f64, b: f64) {
+        const EPSILON: f64 = 1e-6;
+        assert!(
+            (a - b).abs() < EPSILON,
+            "Expected {a} to be approximately equal to {b}"
+        );
+    }
+
+    #[test]
+    fn parse_millisecond_calibration() {
+        let calibration = ThreadCalibration::try_from(
+            "Thread calibration: mean lat.: 1.473ms, rate sampling interval: 10ms",
+        )
+        .unwrap();
+        assert_float_eq(calibration.mean_latency, 1.473);
+        assert_float_eq(calibration.sampling_interval, 10.0);
+    }
+
+    #[test]
+    fn parse_usec_calibration() {
+        let calibration = ThreadCalibration::try_from(
+            "  Thread calibration: mean lat.: 9747 usec, rate sampling interval: 21 msec",
+        )
+        .unwrap();
+        assert_float_eq(calibration.mean_latency, 9.747);
+        assert_float_eq(calibration.sampling_interval, 21.0);
+    }
+
+    #[test]
+    fn parse_invalid_calibration() {
+        assert!(ThreadCalibration::try_from("Latency 1.46ms 2.24ms 44.06ms 98.51%").is_err());
+        assert!(ThreadCalibration::try_from("Thread calibration: garbage").is_err());
+    }
+
+    #[test]
+    fn detects_wrk_commands() {
+        assert!(is_wrk_command(
+            "wrk -t2 -c100 -d30s -R2000 http://127.0.0.1/"
+        ));
+        assert!(is_wrk_command("$ ./wrk2 -t2 http://127.0.0.1/"));
+        assert!(is_wrk_command(
+            "/usr/local/bin/wrk --latency http://127.0.0.1/"
+        ));
+        assert!(!is_wrk_command("Running 30s test @ http://127.0.0.1/"));
+        assert!(!is_wrk_command("wrkflow -t2"));
+        assert!(is_command("$ hey -c 50 http://127.0.0.1/", &["hey"]));
+        assert!(!is_command("hey -c 50 http://127.0.0.1/", &["wrk", "wrk2"]));
+    }
+
+    #[test]
+    fn parse_target_rate_variants() {
+        assert_eq!(parse_target_rate("wrk -t2 -R2000 http://x"), Some(2000.0));
+        assert_eq!(parse_target_rate("wrk -t2 -R 2000 http://x"), Some(2000.0));
+        assert_eq!(parse_target_rate("wrk --rate 2k http://x"), Some(2000.0));
+        assert_eq!(parse_target_rate("wrk --rate=1.5k http://x"), Some(1500.0));
+        assert_eq!(parse_target_rate("wrk -t2 -c100 http://x"), None);
+    }
+}
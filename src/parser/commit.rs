@@ -0,0 +1,49 @@
+use super::is_empty;
+use serde::{Deserialize, Serialize};
+
+/// The source revision a benchmark run was made from, for tools that record it.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CommitInfo {
+    /// Full commit hash
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub branch: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub project: String,
+    /// Commit time, as the tool printed it
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub time: String,
+    /// Whether the working tree had uncommitted changes
+    #[serde(default, skip_serializing_if = "is_empty::check_bool")]
+    pub dirty: bool,
+}
+
+impl CommitInfo {
+    /// The commit hash shortened the way git shows it, marked when the tree was dirty.
+    pub fn short_id(&self) -> String {
+        let id = self.id.get(..12).unwrap_or(&self.id);
+        if self.dirty {
+            format!("{id} (dirty)")
+        } else {
+            id.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_id_truncates_and_marks_dirty() {
+        let mut commit = CommitInfo {
+            id: "6e1b680d2f4c9a7be3310c5d8e9f0a1b2c3d4e5f".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(commit.short_id(), "6e1b680d2f4c");
+        commit.dirty = true;
+        assert_eq!(commit.short_id(), "6e1b680d2f4c (dirty)");
+        assert_eq!(CommitInfo::default().short_id(), "");
+    }
+}
@@ -1,4 +1,6 @@
+use super::{bootstrap, bundle};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Unit of measurement for confidence interval values.
 /// Serializes as the string representation for backward compatibility
@@ -70,6 +72,19 @@ pub struct ConfidenceInterval {
     /// Standard error of the estimate (from estimates.json)
     #[serde(default, skip_serializing_if = "is_zero")]
     pub standard_error: f64,
+    /// Evenly spaced quantiles of the estimate's bootstrap distribution, when it was
+    /// resampled here rather than summarised by Criterion
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub distribution: Vec<f64>,
+}
+
+/// Confidence interval of a rate in operations per second. Kept apart from
+/// [`ConfidenceInterval`] so a rate never carries a time unit.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct RateInterval {
+    pub lower_bound: f64,
+    pub estimate: f64,
+    pub upper_bound: f64,
 }
 
 #[allow(clippy::trivially_copy_pass_by_ref)]
@@ -97,6 +112,28 @@ pub struct ChangeStats {
     pub result: ChangeResult,
     #[serde(default)]
     pub p_value: f64,
+    /// Significance level the p-value is tested against (e.g. 0.05)
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub significance_threshold: f64,
+    /// Mean changes within ± this many percent are reported as noise
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub noise_threshold: f64,
+}
+
+/// Criterion's default `noise_threshold`: mean changes within ±1% are not reported.
+/// Only used to classify changes whose input didn't record the threshold.
+const NOISE_THRESHOLD: f64 = 0.01;
+
+/// Classifies a mean change (in percent) the way Criterion does once the change is
+/// significant: only a confidence interval entirely past the noise threshold counts.
+fn classify_change(mean: &ConfidenceInterval, noise_threshold: f64) -> ChangeResult {
+    if mean.lower_bound > noise_threshold {
+        ChangeResult::Regressed
+    } else if mean.upper_bound < -noise_threshold {
+        ChangeResult::Improved
+    } else {
+        ChangeResult::NoChange
+    }
 }
 
 /// Throughput measurement for benchmarks that measure data processing.
@@ -107,6 +144,18 @@ pub struct Throughput {
     pub unit: String,
 }
 
+/// A measurement beside the main timing, such as Go's `B/op` and `allocs/op` or Google
+/// Benchmark's CPU time and user counters.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExtraMetric {
+    /// What was measured, when the unit alone doesn't say (e.g. "gc.alloc.rate")
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    pub value: f64,
+    /// Unit as the tool printed it, e.g. "B/op"; empty for plain counts
+    pub unit: String,
+}
+
 /// Outlier classification from Criterion's statistical analysis.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 pub struct Outliers {
@@ -120,6 +169,103 @@ pub struct Outliers {
     pub severe_low: u64,
     #[serde(default)]
     pub severe_high: u64,
+    /// Fences the samples were classified with, so charts can colour each sample
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fences: Option<TukeyFences>,
+}
+
+impl Outliers {
+    /// Classifies each sample's time per iteration (ns) against `fences`.
+    pub fn from_samples(fences: TukeyFences, per_iteration: &[f64]) -> Self {
+        let mut outliers = Self {
+            total_measurements: per_iteration.len() as u64,
+            fences: Some(fences),
+            ..Default::default()
+        };
+        for &time in per_iteration {
+            match fences.classify(time) {
+                OutlierClass::SevereLow => outliers.severe_low += 1,
+                OutlierClass::MildLow => outliers.mild_low += 1,
+                OutlierClass::MildHigh => outliers.mild_high += 1,
+                OutlierClass::SevereHigh => outliers.severe_high += 1,
+                OutlierClass::NotAnOutlier => continue,
+            }
+            outliers.outlier_count += 1;
+        }
+        outliers
+    }
+}
+
+/// Where a sample falls relative to the Tukey fences.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutlierClass {
+    SevereLow,
+    MildLow,
+    NotAnOutlier,
+    MildHigh,
+    SevereHigh,
+}
+
+/// Tukey's fences on time per iteration in ns, as in Criterion's `tukey.json`: the
+/// quartiles widened by 1.5 (mild) and 3 (severe) interquartile ranges.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct TukeyFences {
+    pub low_severe: f64,
+    pub low_mild: f64,
+    pub high_mild: f64,
+    pub high_severe: f64,
+}
+
+impl TukeyFences {
+    /// Computes the fences from samples of time per iteration, interpolating the
+    /// quartiles the way Criterion's `Percentiles::at` does.
+    pub fn from_samples(per_iteration: &[f64]) -> Option<Self> {
+        if per_iteration.len() < 2 {
+            return None;
+        }
+        let mut sorted = per_iteration.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let q1 = bootstrap::percentile(&sorted, 0.25);
+        let q3 = bootstrap::percentile(&sorted, 0.75);
+        let iqr = q3 - q1;
+        Some(Self {
+            low_severe: q1 - 3.0 * iqr,
+            low_mild: q1 - 1.5 * iqr,
+            high_mild: q3 + 1.5 * iqr,
+            high_severe: q3 + 3.0 * iqr,
+        })
+    }
+
+    pub fn classify(&self, time: f64) -> OutlierClass {
+        if time < self.low_severe {
+            OutlierClass::SevereLow
+        } else if time > self.high_severe {
+            OutlierClass::SevereHigh
+        } else if time < self.low_mild {
+            OutlierClass::MildLow
+        } else if time > self.high_mild {
+            OutlierClass::MildHigh
+        } else {
+            OutlierClass::NotAnOutlier
+        }
+    }
+}
+
+/// Time per iteration in ns for each sample with a positive iteration count.
+fn per_iteration_times(iteration_count: &[f64], measured_values: &[f64]) -> Vec<f64> {
+    iteration_count
+        .iter()
+        .zip(measured_values)
+        .filter(|(iters, _)| **iters > 0.0)
+        .map(|(iters, time)| time / iters)
+        .collect()
+}
+
+/// Outliers computed from raw samples, for inputs without Criterion's own summary.
+fn sample_outliers(iteration_count: &[f64], measured_values: &[f64]) -> Option<Outliers> {
+    let per_iteration = per_iteration_times(iteration_count, measured_values);
+    let fences = TukeyFences::from_samples(&per_iteration)?;
+    Some(Outliers::from_samples(fences, &per_iteration))
 }
 
 /// Metrics from a single Criterion.rs benchmark run.
@@ -127,8 +273,15 @@ pub struct Outliers {
 pub struct CriterionMetrics {
     /// Benchmark name (e.g. "fib/20", "sort/1000")
     pub name: String,
+    /// Benchmark group, when the input names it (`group-complete` messages, `benchmark.json`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
     /// Primary timing confidence interval: [lower estimate upper]
     pub time: ConfidenceInterval,
+    /// Operations per second, for benchmarks measured in throughput mode (JMH's `thrpt`).
+    /// These are shown as a rate, while `time` holds the matching time per operation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ops_per_sec: Option<RateInterval>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mean: Option<ConfidenceInterval>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -143,6 +296,8 @@ pub struct CriterionMetrics {
     pub change: Option<ChangeStats>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub throughput: Option<Throughput>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_metrics: Vec<ExtraMetric>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub outliers: Option<Outliers>,
     /// R² goodness of fit for linear regression (0.0 to 1.0)
@@ -156,6 +311,66 @@ pub struct CriterionMetrics {
     pub baseline: Option<Box<CriterionMetrics>>,
 }
 
+impl CriterionMetrics {
+    /// The group this benchmark belongs to. Inputs that don't name one fall back to
+    /// the first segment of the benchmark ID, which is the group for `group/function/input`.
+    pub fn group_name(&self) -> &str {
+        self.group.as_deref().unwrap_or_else(|| {
+            self.name
+                .split_once('/')
+                .map_or(self.name.as_str(), |(group, _)| group)
+        })
+    }
+}
+
+/// A Criterion.rs benchmark group and its benchmarks, in the order they were reported.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CriterionGroup {
+    pub name: String,
+    pub benchmarks: Vec<CriterionMetrics>,
+}
+
+impl CriterionGroup {
+    /// A lone `bench_function` outside any group, which Criterion reports as a group
+    /// of one named after the benchmark.
+    pub fn is_standalone(&self) -> bool {
+        matches!(self.benchmarks.as_slice(), [bench] if bench.name == self.name)
+    }
+
+    /// Whether the benchmarks end in a numeric input (e.g. "fib/20" or
+    /// "Sort/size=1000"), so they can be plotted against it.
+    pub fn has_numeric_inputs(&self) -> bool {
+        self.benchmarks
+            .iter()
+            .any(|b| split_numeric_input(&b.name).is_some())
+    }
+}
+
+/// Splits a benchmark ID into its function and trailing numeric input: "fib/20" and
+/// Go's "BenchmarkSort/size=1000" style both work.
+pub fn split_numeric_input(name: &str) -> Option<(&str, f64)> {
+    let (function, input) = name.rsplit_once('/')?;
+    let value = input.rsplit_once('=').map_or(input, |(_, value)| value);
+    Some((function, value.parse().ok()?))
+}
+
+/// Collects benchmarks into their groups, keeping the order each group first appears in.
+pub fn group_benchmarks(benchmarks: &[CriterionMetrics]) -> Vec<CriterionGroup> {
+    let mut groups: Vec<CriterionGroup> = Vec::new();
+    for bench in benchmarks {
+        let name = bench.group_name();
+        if let Some(group) = groups.iter_mut().find(|g| g.name == name) {
+            group.benchmarks.push(bench.clone());
+        } else {
+            groups.push(CriterionGroup {
+                name: name.to_string(),
+                benchmarks: vec![bench.clone()],
+            });
+        }
+    }
+    groups
+}
+
 // ---------------------------------------------------------------------------
 // CLI stdout parser (cargo bench output)
 // ---------------------------------------------------------------------------
@@ -361,13 +576,16 @@ fn parse_percentage_value(s: &str) -> Option<f64> {
 fn parse_change_line(line: &str, lines: &[&str], idx: &mut usize) -> Option<ChangeStats> {
     let ci = parse_confidence_interval(&line[7..])?;
 
-    // Extract p-value from "(p = 0.59 > 0.05)" or "(p = 0.01 < 0.05)"
-    let p_value = line
+    // Extract p-value and significance level from "(p = 0.59 > 0.05)" or "(p = 0.01 < 0.05)"
+    let mut test = line
         .find("(p = ")
-        .and_then(|start| {
-            let rest = &line[start + 5..];
-            rest.split_whitespace().next()?.parse::<f64>().ok()
-        })
+        .map(|start| line[start + 5..].split_whitespace())
+        .into_iter()
+        .flatten();
+    let p_value = test.next().and_then(|p| p.parse().ok()).unwrap_or(0.0);
+    let significance_threshold = test
+        .nth(1)
+        .and_then(|t| t.trim_end_matches(')').parse().ok())
         .unwrap_or(0.0);
 
     *idx += 1;
@@ -393,6 +611,8 @@ fn parse_change_line(line: &str, lines: &[&str], idx: &mut usize) -> Option<Chan
         median: ci,
         result,
         p_value,
+        significance_threshold,
+        ..Default::default()
     })
 }
 
@@ -441,28 +661,43 @@ fn parse_outliers(lines: &[&str], idx: &mut usize) -> Outliers {
 // ---------------------------------------------------------------------------
 
 /// Parse JSON output from `cargo-criterion --message-format=json`.
-/// Each line is a separate JSON object; we only care about "benchmark-complete" messages.
+/// Each line is a separate JSON object. `benchmark-complete` messages carry the
+/// measurements and the `group-complete` message after them names their group.
 pub fn parse_json_output(output: &str) -> Vec<CriterionMetrics> {
-    output
-        .lines()
-        .filter_map(|line| {
-            let line = line.trim();
-            if line.is_empty() {
-                return None;
-            }
-            parse_json_message(line)
-        })
-        .collect()
+    let mut benchmarks = Vec::new();
+    for line in output.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        match value.get("reason").and_then(serde_json::Value::as_str) {
+            Some("benchmark-complete") => benchmarks.extend(parse_json_message(&value)),
+            Some("group-complete") => assign_json_group(&value, &mut benchmarks),
+            _ => {}
+        }
+    }
+    benchmarks
 }
 
-fn parse_json_message(line: &str) -> Option<CriterionMetrics> {
-    let value: serde_json::Value = serde_json::from_str(line).ok()?;
-    let obj = value.as_object()?;
-
-    let reason = obj.get("reason")?.as_str()?;
-    if reason != "benchmark-complete" {
-        return None;
+/// Tags the benchmarks listed in a `group-complete` message with its `group_name`.
+fn assign_json_group(value: &serde_json::Value, benchmarks: &mut [CriterionMetrics]) {
+    let Some(group) = value.get("group_name").and_then(serde_json::Value::as_str) else {
+        return;
+    };
+    let ids: Vec<&str> = value
+        .get("benchmarks")
+        .and_then(serde_json::Value::as_array)
+        .map(|ids| ids.iter().filter_map(serde_json::Value::as_str).collect())
+        .unwrap_or_default();
+    for bench in benchmarks
+        .iter_mut()
+        .filter(|b| b.group.is_none() && ids.contains(&b.name.as_str()))
+    {
+        bench.group = Some(group.to_string());
     }
+}
+
+fn parse_json_message(value: &serde_json::Value) -> Option<CriterionMetrics> {
+    let obj = value.as_object()?;
 
     let name = obj.get("id")?.as_str()?.to_string();
     let unit = obj.get("unit").and_then(|u| u.as_str()).unwrap_or("ns");
@@ -485,13 +720,13 @@ fn parse_json_message(line: &str) -> Option<CriterionMetrics> {
         })
     });
 
-    let iteration_count = obj
+    let iteration_count: Vec<f64> = obj
         .get("iteration_count")
         .and_then(|v| v.as_array())
         .map(|arr| arr.iter().filter_map(serde_json::Value::as_f64).collect())
         .unwrap_or_default();
 
-    let measured_values = obj
+    let measured_values: Vec<f64> = obj
         .get("measured_values")
         .and_then(|v| v.as_array())
         .map(|arr| arr.iter().filter_map(serde_json::Value::as_f64).collect())
@@ -499,7 +734,9 @@ fn parse_json_message(line: &str) -> Option<CriterionMetrics> {
 
     Some(CriterionMetrics {
         name,
+        group: None,
         time: typical,
+        ops_per_sec: None,
         mean,
         median,
         median_abs_dev,
@@ -507,7 +744,8 @@ fn parse_json_message(line: &str) -> Option<CriterionMetrics> {
         slope,
         change,
         throughput,
-        outliers: None,
+        extra_metrics: Vec::new(),
+        outliers: sample_outliers(&iteration_count, &measured_values),
         r_squared: None,
         iteration_count,
         measured_values,
@@ -537,47 +775,46 @@ fn parse_json_ci(value: &serde_json::Value, unit: &str) -> Option<ConfidenceInte
     })
 }
 
+/// The `change` object of a `benchmark-complete` message. Its confidence intervals
+/// are fractions labelled `%`; `p_value` and the thresholds are read when present.
 fn parse_json_change(value: &serde_json::Value) -> Option<ChangeStats> {
     let obj = value.as_object()?;
-
-    let mean = obj
-        .get("mean")
-        .and_then(|m| {
-            let mobj = m.as_object()?;
-            Some(ConfidenceInterval {
-                estimate: mobj.get("estimate")?.as_f64()? * 100.0,
-                unit: TimeUnit::Percent,
-                ..Default::default()
-            })
-        })
-        .unwrap_or_default();
-
-    let median = obj
-        .get("median")
-        .and_then(|m| {
-            let mobj = m.as_object()?;
-            Some(ConfidenceInterval {
-                estimate: mobj.get("estimate")?.as_f64()? * 100.0,
-                unit: TimeUnit::Percent,
-                ..Default::default()
-            })
+    let number = |key: &str| obj.get(key).and_then(serde_json::Value::as_f64);
+    let percent = |key: &str| {
+        let ci = obj.get(key)?.as_object()?;
+        let bound = |key: &str| ci.get(key).and_then(serde_json::Value::as_f64);
+        let estimate = bound("estimate")?;
+        Some(ConfidenceInterval {
+            lower_bound: bound("lower_bound").unwrap_or(estimate) * 100.0,
+            estimate: estimate * 100.0,
+            upper_bound: bound("upper_bound").unwrap_or(estimate) * 100.0,
+            unit: TimeUnit::Percent,
+            ..Default::default()
         })
-        .unwrap_or_default();
+    };
 
-    let result = obj
-        .get("change")
-        .and_then(|c| c.as_str())
-        .map_or(ChangeResult::NoChange, |s| match s {
-            "Improved" => ChangeResult::Improved,
-            "Regressed" => ChangeResult::Regressed,
-            _ => ChangeResult::NoChange,
-        });
+    let mean = percent("mean").unwrap_or_default();
+    let p_value = number("p_value").unwrap_or(0.0);
+    let significance_threshold = number("significance_threshold").unwrap_or(0.0);
+    let noise_threshold = number("noise_threshold").map_or(0.0, |t| t * 100.0);
+    let result = match obj.get("change").and_then(serde_json::Value::as_str) {
+        Some("Improved") => ChangeResult::Improved,
+        Some("Regressed") => ChangeResult::Regressed,
+        Some(_) => ChangeResult::NoChange,
+        None if significance_threshold > 0.0 && p_value >= significance_threshold => {
+            ChangeResult::NoChange
+        }
+        None if noise_threshold > 0.0 => classify_change(&mean, noise_threshold),
+        None => classify_change(&mean, NOISE_THRESHOLD * 100.0),
+    };
 
     Some(ChangeStats {
+        median: percent("median").unwrap_or_default(),
         mean,
-        median,
         result,
-        p_value: 0.0,
+        p_value,
+        significance_threshold,
+        noise_threshold,
     })
 }
 
@@ -643,6 +880,7 @@ fn compute_linear_regression(
         upper_bound: slope_ms + 1.96 * std_error,
         unit: TimeUnit::Milliseconds,
         standard_error: std_error,
+        ..Default::default()
     };
 
     let r_squared_ci = ConfidenceInterval {
@@ -659,7 +897,8 @@ fn compute_linear_regression(
 // Raw sample.json parser (target/criterion/$NAME/new/sample.json)
 // ---------------------------------------------------------------------------
 
-#[allow(clippy::cast_precision_loss)]
+/// Parse a raw `sample.json`. Criterion doesn't save its estimates beside the samples,
+/// so they are bootstrapped here; the slope is the typical time when there is one.
 pub fn parse_sample_json(output: &str) -> Option<CriterionMetrics> {
     let value: serde_json::Value = serde_json::from_str(output).ok()?;
     let obj = value.as_object()?;
@@ -671,119 +910,278 @@ pub fn parse_sample_json(output: &str) -> Option<CriterionMetrics> {
         return None;
     }
 
-    let per_iter_ns: Vec<f64> = iters
-        .iter()
-        .zip(times.iter())
-        .filter_map(|(i, t)| {
-            let iter_count = i.as_f64()?;
-            let time_ns = t.as_f64()?;
-            if iter_count > 0.0 {
-                Some(time_ns / iter_count)
-            } else {
-                None
-            }
-        })
-        .collect();
+    let iteration_count: Vec<f64> = iters.iter().filter_map(serde_json::Value::as_f64).collect();
+    let measured_values: Vec<f64> = times.iter().filter_map(serde_json::Value::as_f64).collect();
+    let per_iteration = per_iteration_times(&iteration_count, &measured_values);
 
-    if per_iter_ns.is_empty() {
-        return None;
-    }
+    let estimates = bootstrap::estimate(&iteration_count, &measured_values)?;
+    let (_, r_squared) = compute_linear_regression(&iteration_count, &measured_values);
 
-    let mean_nanoseconds = per_iter_ns.iter().sum::<f64>() / per_iter_ns.len() as f64;
-    let median_nanoseconds = {
-        let mut sorted = per_iter_ns.clone();
-        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-        let mid = sorted.len() / 2;
-        if sorted.len().is_multiple_of(2) {
-            f64::midpoint(sorted[mid - 1], sorted[mid])
-        } else {
-            sorted[mid]
-        }
-    };
-    let std_dev_nanoseconds = {
-        let variance = per_iter_ns
-            .iter()
-            .map(|x| (x - mean_nanoseconds).powi(2))
-            .sum::<f64>()
-            / per_iter_ns.len() as f64;
-        variance.sqrt()
-    };
+    Some(CriterionMetrics {
+        name: "benchmark".to_string(),
+        group: None,
+        time: estimates
+            .slope
+            .clone()
+            .unwrap_or_else(|| estimates.mean.clone()),
+        ops_per_sec: None,
+        mean: Some(estimates.mean),
+        median: Some(estimates.median),
+        std_dev: Some(estimates.std_dev),
+        median_abs_dev: Some(estimates.median_abs_dev),
+        slope: estimates.slope,
+        r_squared,
+        change: None,
+        throughput: None,
+        extra_metrics: Vec::new(),
+        outliers: Some(Outliers::from_samples(
+            TukeyFences::from_samples(&per_iteration)?,
+            &per_iteration,
+        )),
+        iteration_count,
+        measured_values,
+        baseline: None,
+    })
+}
 
-    let ns_to_ms = 0.000_001;
-    let mean_milliseconds = mean_nanoseconds * ns_to_ms;
-    let median_milliseconds = median_nanoseconds * ns_to_ms;
-    let std_dev_milliseconds = std_dev_nanoseconds * ns_to_ms;
+// ---------------------------------------------------------------------------
+// target/criterion directory parser (<bench>/{base,new,change}/*.json)
+// ---------------------------------------------------------------------------
 
-    let iteration_count: Vec<f64> = iters.iter().filter_map(serde_json::Value::as_f64).collect();
-    let measured_values: Vec<f64> = times.iter().filter_map(serde_json::Value::as_f64).collect();
+/// Parse a `target/criterion` tree given as a [`super::bundle`] of its files. Each
+/// benchmark directory's `new` run becomes the metrics, with `base` as the baseline
+/// and `change/estimates.json` as the change between them.
+pub fn parse_directory(output: &str) -> Vec<CriterionMetrics> {
+    // benchmark directory -> (base|new|change, file name) -> content
+    let mut benches: BTreeMap<&str, BTreeMap<(&str, &str), &str>> = BTreeMap::new();
+    for (path, content) in bundle::split(output) {
+        let Some((dir, file)) = path.rsplit_once('/') else {
+            continue;
+        };
+        let (bench, kind) = dir.rsplit_once('/').unwrap_or(("", dir));
+        if matches!(kind, "base" | "new" | "change") {
+            benches
+                .entry(bench)
+                .or_default()
+                .insert((kind, file), content);
+        }
+    }
 
-    let (slope, r_squared) = compute_linear_regression(&iteration_count, &measured_values);
+    benches
+        .iter()
+        .filter_map(|(dir, files)| {
+            let baseline = parse_directory_run(dir, files, "base");
+            // a benchmark may only have a saved baseline so far
+            let Some(mut metrics) = parse_directory_run(dir, files, "new") else {
+                return baseline;
+            };
+            metrics.change = files
+                .get(&("change", "estimates.json"))
+                .and_then(|c| parse_change_estimates(c));
+            metrics.baseline = baseline.map(Box::new);
+            Some(metrics)
+        })
+        .collect()
+}
 
-    let time = ConfidenceInterval {
-        lower_bound: mean_milliseconds - std_dev_milliseconds,
-        estimate: mean_milliseconds,
-        upper_bound: mean_milliseconds + std_dev_milliseconds,
-        unit: TimeUnit::Milliseconds,
-        ..Default::default()
+/// One `base` or `new` run: `estimates.json` plus the optional `sample.json`,
+/// `tukey.json` and `benchmark.json` beside it.
+fn parse_directory_run(
+    dir: &str,
+    files: &BTreeMap<(&str, &str), &str>,
+    kind: &str,
+) -> Option<CriterionMetrics> {
+    let file = |name: &str| files.get(&(kind, name)).copied();
+    let estimates: serde_json::Value = serde_json::from_str(file("estimates.json")?).ok()?;
+    let benchmark = file("benchmark.json")
+        .and_then(|b| serde_json::from_str::<serde_json::Value>(b).ok())
+        .unwrap_or_default();
+    let estimate = |key: &str| {
+        estimates
+            .get(key)
+            .and_then(|e| parse_estimate(e, 0.000_001, TimeUnit::Milliseconds))
     };
 
+    let (iteration_count, measured_values) = file("sample.json")
+        .and_then(|s| serde_json::from_str::<serde_json::Value>(s).ok())
+        .map(|sample| {
+            let values = |key| -> Vec<f64> {
+                sample
+                    .get(key)
+                    .and_then(serde_json::Value::as_array)
+                    .map(|a| a.iter().filter_map(serde_json::Value::as_f64).collect())
+                    .unwrap_or_default()
+            };
+            (values("iters"), values("times"))
+        })
+        .unwrap_or_default();
+    let (_, r_squared) = compute_linear_regression(&iteration_count, &measured_values);
+
+    let outliers = file("tukey.json")
+        .and_then(|t| serde_json::from_str::<Vec<f64>>(t).ok())
+        .and_then(|fences| match fences[..] {
+            [low_severe, low_mild, high_mild, high_severe] => Some(TukeyFences {
+                low_severe,
+                low_mild,
+                high_mild,
+                high_severe,
+            }),
+            _ => None,
+        })
+        .map(|fences| {
+            Outliers::from_samples(
+                fences,
+                &per_iteration_times(&iteration_count, &measured_values),
+            )
+        })
+        .or_else(|| sample_outliers(&iteration_count, &measured_values));
+
+    let throughput = benchmark
+        .get("throughput")
+        .and_then(serde_json::Value::as_object)
+        .and_then(|t| t.iter().next())
+        .and_then(|(kind, amount)| {
+            Some(Throughput {
+                per_iteration: amount.as_f64()?,
+                unit: match kind.as_str() {
+                    "Elements" => "elements",
+                    _ => "bytes",
+                }
+                .to_string(),
+            })
+        });
+
+    let mean = estimate("mean");
+    let slope = estimate("slope");
     Some(CriterionMetrics {
-        name: "benchmark".to_string(),
-        time,
-        mean: Some(ConfidenceInterval {
-            lower_bound: mean_milliseconds - std_dev_milliseconds,
-            estimate: mean_milliseconds,
-            upper_bound: mean_milliseconds + std_dev_milliseconds,
-            unit: TimeUnit::Milliseconds,
-            ..Default::default()
-        }),
-        median: Some(ConfidenceInterval {
-            lower_bound: median_milliseconds - std_dev_milliseconds,
-            estimate: median_milliseconds,
-            upper_bound: median_milliseconds + std_dev_milliseconds,
-            unit: TimeUnit::Milliseconds,
-            ..Default::default()
-        }),
-        std_dev: Some(ConfidenceInterval {
-            estimate: std_dev_milliseconds,
-            unit: TimeUnit::Milliseconds,
-            ..Default::default()
-        }),
+        name: benchmark
+            .get("full_id")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_else(|| dir.rsplit_once("criterion/").map_or(dir, |(_, name)| name))
+            .to_string(),
+        group: benchmark
+            .get("group_id")
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string),
+        // Criterion reports the slope as the typical time when it has one
+        time: slope.clone().or_else(|| mean.clone())?,
+        ops_per_sec: None,
+        mean,
+        median: estimate("median"),
+        median_abs_dev: estimate("median_abs_dev"),
+        std_dev: estimate("std_dev"),
         slope,
-        r_squared,
-        median_abs_dev: None,
         change: None,
-        throughput: None,
-        outliers: None,
+        throughput,
+        extra_metrics: Vec::new(),
+        outliers,
+        r_squared,
         iteration_count,
         measured_values,
         baseline: None,
     })
 }
 
+/// An `estimates.json` entry, scaled from its unit (ns, or a fraction for changes).
+fn parse_estimate(
+    value: &serde_json::Value,
+    scale: f64,
+    unit: TimeUnit,
+) -> Option<ConfidenceInterval> {
+    let interval = value.get("confidence_interval")?;
+    Some(ConfidenceInterval {
+        lower_bound: interval.get("lower_bound")?.as_f64()? * scale,
+        estimate: value.get("point_estimate")?.as_f64()? * scale,
+        upper_bound: interval.get("upper_bound")?.as_f64()? * scale,
+        unit,
+        standard_error: value
+            .get("standard_error")
+            .and_then(serde_json::Value::as_f64)
+            .unwrap_or(0.0)
+            * scale,
+        ..Default::default()
+    })
+}
+
+/// `change/estimates.json`. The t-test p-value isn't saved, so the result only
+/// compares the mean's confidence interval with the noise threshold.
+fn parse_change_estimates(content: &str) -> Option<ChangeStats> {
+    let estimates: serde_json::Value = serde_json::from_str(content).ok()?;
+    let estimate = |key: &str| parse_estimate(estimates.get(key)?, 100.0, TimeUnit::Percent);
+    let mean = estimate("mean")?;
+    Some(ChangeStats {
+        median: estimate("median").unwrap_or_default(),
+        result: classify_change(&mean, NOISE_THRESHOLD * 100.0),
+        mean,
+        p_value: 0.0,
+        significance_threshold: 0.0,
+        noise_threshold: 0.0,
+    })
+}
+
 // ---------------------------------------------------------------------------
 // Auto-detection
 // ---------------------------------------------------------------------------
 
-pub fn try_parse(output: &str) -> Option<Vec<CriterionMetrics>> {
-    if is_criterion_message_json(output) {
-        let results = parse_json_output(output);
-        if results.is_empty() {
-            None
-        } else {
-            Some(results)
-        }
-    } else if is_criterion_sample_json(output) {
-        parse_sample_json(output).map(|m| vec![m])
-    } else if is_criterion_cli(output) {
-        let results = parse_cli_output(output);
-        if results.is_empty() {
-            None
-        } else {
-            Some(results)
-        }
+/// Confidence (0.0–1.0) that `output` is a bundle of `target/criterion` files.
+pub fn directory_confidence(output: &str) -> f64 {
+    if !bundle::is_bundle(output) {
+        return 0.0;
+    }
+    let files = bundle::split(output);
+    let has = |suffix: &str| files.iter().any(|(path, _)| path.ends_with(suffix));
+    if has("/new/estimates.json") || has("/base/estimates.json") {
+        1.0
+    } else if has("estimates.json") || has("sample.json") {
+        0.5
     } else {
-        None
+        0.0
+    }
+}
+
+/// Confidence (0.0–1.0) that `output` is `cargo-criterion --message-format=json` output.
+pub fn json_confidence(output: &str) -> f64 {
+    if !is_criterion_message_json(output) {
+        0.0
+    } else if output.contains("\"benchmark-complete\"") {
+        1.0
+    } else {
+        0.6
+    }
+}
+
+/// Confidence (0.0–1.0) that `output` is a Criterion `sample.json` file.
+pub fn sample_confidence(output: &str) -> f64 {
+    let trimmed = output.trim();
+    if is_criterion_sample_json(output) {
+        1.0
+    } else if trimmed.starts_with('{')
+        && trimmed.contains("\"iters\"")
+        && trimmed.contains("\"times\"")
+    {
+        0.5
+    } else {
+        0.0
+    }
+}
+
+/// Confidence (0.0–1.0) that `output` is Criterion's terminal output. A run cut off
+/// before its first `time:` line still scores low so it can be reported as such.
+pub fn cli_confidence(output: &str) -> f64 {
+    if is_criterion_cli(output) {
+        let text = output.to_lowercase();
+        let extras = ["benchmarking", "change:", "outliers", "thrpt:"]
+            .iter()
+            .filter(|marker| text.contains(*marker))
+            .count();
+        (0.6 + 0.1 * f64::from(u8::try_from(extras).unwrap_or(0))).min(1.0)
+    } else if output
+        .lines()
+        .any(|l| l.trim_start().starts_with("Benchmarking "))
+    {
+        0.3
+    } else {
+        0.0
     }
 }
 
@@ -849,6 +1247,7 @@ mod tests {
         assert_float_eq(change.mean.estimate, 0.2437);
         assert_float_eq(change.mean.upper_bound, 1.1291);
         assert_float_eq(change.p_value, 0.59);
+        assert_float_eq(change.significance_threshold, 0.05);
         assert_eq!(change.result, ChangeResult::NoChange);
 
         let outliers = m.outliers.as_ref().unwrap();
@@ -928,18 +1327,93 @@ mod tests {
         assert_eq!(m.measured_values, vec![124_200.0, 248_400.0, 372_600.0]);
     }
 
+    #[test]
+    fn test_parse_json_change_bounds() {
+        let results = parse_json_output(include_str!("fixtures/criterion_json_change.json"));
+        let change = results[0].change.as_ref().unwrap();
+        assert_float_eq(change.mean.estimate, 1.4);
+        assert_float_eq(change.mean.lower_bound, -0.21);
+        assert_float_eq(change.mean.upper_bound, 2.97);
+        assert_eq!(change.mean.unit, TimeUnit::Percent);
+        assert_float_eq(change.median.upper_bound, 2.68);
+        // cargo-criterion doesn't write the p-value or thresholds
+        assert_float_eq(change.p_value, 0.0);
+        assert_float_eq(change.noise_threshold, 0.0);
+        assert_eq!(change.result, ChangeResult::NoChange);
+    }
+
+    #[test]
+    fn test_parse_json_change_without_verdict() {
+        let change = |extra: &str| {
+            parse_json_change(
+                &serde_json::from_str(&format!(
+                    r#"{{"mean":{{"estimate":0.05,"lower_bound":0.03,"upper_bound":0.07,"unit":"%"}}{extra}}}"#
+                ))
+                .unwrap(),
+            )
+            .unwrap()
+        };
+        assert_eq!(change("").result, ChangeResult::Regressed);
+        assert_eq!(
+            change(r#","noise_threshold":0.04"#).result,
+            ChangeResult::NoChange
+        );
+        let tested =
+            change(r#","p_value":0.2,"significance_threshold":0.05,"noise_threshold":0.01"#);
+        assert_eq!(tested.result, ChangeResult::NoChange);
+        assert_float_eq(tested.p_value, 0.2);
+        assert_float_eq(tested.significance_threshold, 0.05);
+        assert_float_eq(tested.noise_threshold, 1.0);
+        // median is absent, so it stays empty rather than copying the mean
+        assert_float_eq(change("").median.estimate, 0.0);
+    }
+
+    #[test]
+    fn test_parse_json_group_complete() {
+        let results = parse_json_output(include_str!("fixtures/criterion_json_groups.json"));
+        assert_eq!(results.len(), 5);
+        assert!(results[..4]
+            .iter()
+            .all(|b| b.group.as_deref() == Some("fib")));
+        assert_eq!(results[4].group.as_deref(), Some("checksum"));
+
+        let groups = group_benchmarks(&results);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].name, "fib");
+        assert_eq!(groups[0].benchmarks.len(), 4);
+        assert!(groups[0].has_numeric_inputs());
+        assert!(!groups[0].is_standalone());
+        assert!(groups[1].is_standalone());
+    }
+
+    #[test]
+    fn test_group_benchmarks_without_group_names() {
+        let results = parse_cli_output(CLI_OUTPUT_MULTIPLE);
+        let groups = group_benchmarks(&results);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].name, "fib");
+        assert_eq!(groups[0].benchmarks.len(), 2);
+
+        let grouped = parse_cli_output(include_str!("fixtures/criterion_cli_grouped_bench.txt"));
+        let groups = group_benchmarks(&grouped);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].name, "compute_cache_key_hash");
+        assert!(!groups[0].has_numeric_inputs());
+    }
+
     #[test]
     fn test_auto_detect_cli() {
         assert!(is_criterion_cli(CLI_OUTPUT_SIMPLE));
         assert!(!is_criterion_message_json(CLI_OUTPUT_SIMPLE));
-        assert!(try_parse(CLI_OUTPUT_SIMPLE).is_some());
+        assert!(cli_confidence(CLI_OUTPUT_SIMPLE) > json_confidence(CLI_OUTPUT_SIMPLE));
     }
 
     #[test]
     fn test_auto_detect_json() {
         assert!(is_criterion_message_json(JSON_OUTPUT));
         assert!(!is_criterion_cli(JSON_OUTPUT));
-        assert!(try_parse(JSON_OUTPUT).is_some());
+        assert!((json_confidence(JSON_OUTPUT) - 1.0).abs() < f64::EPSILON);
+        assert!(cli_confidence(JSON_OUTPUT).abs() < f64::EPSILON);
     }
 
     #[test]
@@ -951,14 +1425,16 @@ Running 10s test @ http://localhost:8080
     Latency   125.12ms   25.31ms 450.00ms   90.12%
 ";
         assert!(!is_criterion_message_json(wrk_output));
-        assert!(try_parse(wrk_output).is_none() || !is_criterion_cli(wrk_output));
+        assert!(!is_criterion_cli(wrk_output));
+        assert!(cli_confidence(wrk_output).abs() < f64::EPSILON);
     }
 
     #[test]
     fn test_empty_input() {
         assert!(parse_cli_output("").is_empty());
         assert!(parse_json_output("").is_empty());
-        assert!(try_parse("").is_none());
+        assert!(parse_sample_json("").is_none());
+        assert!(cli_confidence("").abs() < f64::EPSILON);
     }
 
     #[test]
@@ -990,9 +1466,9 @@ Running 10s test @ http://localhost:8080
     fn test_auto_detect_sample_json() {
         let input = include_str!("fixtures/criterion_sample.json");
         assert!(is_criterion_sample_json(input));
-        let results = try_parse(input).expect("should auto-detect sample.json");
-        assert_eq!(results.len(), 1);
-        assert_eq!(results[0].name, "benchmark");
+        assert!((sample_confidence(input) - 1.0).abs() < f64::EPSILON);
+        let result = parse_sample_json(input).expect("should parse sample.json");
+        assert_eq!(result.name, "benchmark");
     }
 
     const CLI_OUTPUT_GROUPED_BENCH: &str = include_str!("fixtures/criterion_cli_grouped_bench.txt");
@@ -1015,6 +1491,7 @@ Running 10s test @ http://localhost:8080
         assert_float_eq(change.mean.estimate, 56.816);
         assert_float_eq(change.mean.upper_bound, 57.716);
         assert_float_eq(change.p_value, 0.0);
+        assert_float_eq(change.noise_threshold, 0.0);
         assert_eq!(change.result, ChangeResult::Regressed);
 
         let outliers = simple.outliers.as_ref().unwrap();
@@ -1052,8 +1529,7 @@ Running 10s test @ http://localhost:8080
     #[test]
     fn test_sample_json_roundtrip_serialize() {
         let input = include_str!("fixtures/criterion_sample.json");
-        let results = try_parse(input).unwrap();
-        let bench = &results[0];
+        let bench = &parse_sample_json(input).unwrap();
 
         let json = serde_json::to_string(bench).expect("should serialize");
         let deserialized: CriterionMetrics =
@@ -1064,4 +1540,117 @@ Running 10s test @ http://localhost:8080
             deserialized.iteration_count.len()
         );
     }
+
+    #[test]
+    fn test_parse_directory() {
+        let benches = parse_directory(include_str!("fixtures/criterion_directory.txt"));
+        let names: Vec<_> = benches.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, vec!["fib/10", "fib/20", "parse/json"]);
+
+        let fib20 = &benches[1];
+        assert_eq!(fib20.group.as_deref(), Some("fib"));
+        assert_float_eq(fib20.time.estimate, 0.031_225_228_1);
+        let mean = fib20.mean.as_ref().unwrap();
+        assert_float_eq(mean.standard_error, 0.000_740_068_7);
+        let baseline = fib20.baseline.as_ref().unwrap();
+        assert_float_eq(baseline.time.estimate, 0.029_569_344_2);
+        assert!(baseline.baseline.is_none());
+        let change = fib20.change.as_ref().unwrap();
+        assert_eq!(change.result, ChangeResult::Regressed);
+        assert_float_eq(change.mean.estimate, 3.55);
+        assert_eq!(fib20.iteration_count.len(), 20);
+        assert!(fib20.r_squared.is_some());
+
+        let fib10 = &benches[0];
+        assert_eq!(
+            fib10.change.as_ref().unwrap().result,
+            ChangeResult::Improved
+        );
+        let outliers = fib10.outliers.as_ref().unwrap();
+        assert_eq!(outliers.total_measurements, 20);
+        assert_eq!(outliers.severe_low, 1);
+        assert_eq!(outliers.severe_high, 1);
+
+        let json = &benches[2];
+        assert!(json.baseline.is_none());
+        assert!(json.change.is_none());
+        let throughput = json.throughput.as_ref().unwrap();
+        assert_eq!(throughput.unit, "bytes");
+        assert_float_eq(throughput.per_iteration, 65536.0);
+    }
+
+    #[test]
+    fn test_parse_directory_from_zip() {
+        let files: Vec<_> = bundle::unzip(include_bytes!("fixtures/criterion_directory.zip"))
+            .unwrap()
+            .into_iter()
+            .filter(|(path, _)| !path.ends_with("index.html"))
+            .map(|(path, content)| (path, String::from_utf8(content).unwrap()))
+            .collect();
+        let from_zip = parse_directory(&bundle::join(&files));
+        let from_text = parse_directory(include_str!("fixtures/criterion_directory.txt"));
+        assert_eq!(from_zip, from_text);
+    }
+
+    #[test]
+    fn test_tukey_fences_match_criterion() {
+        // fib/10's new sample.json and the tukey.json Criterion wrote beside it
+        let files = bundle::split(include_str!("fixtures/criterion_directory.txt"));
+        let sample = files
+            .iter()
+            .find(|(path, _)| *path == "target/criterion/fib/10/new/sample.json")
+            .unwrap()
+            .1;
+        let sample: serde_json::Value = serde_json::from_str(sample).unwrap();
+        let values = |key: &str| -> Vec<f64> {
+            sample[key]
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter_map(serde_json::Value::as_f64)
+                .collect()
+        };
+        let per_iteration = per_iteration_times(&values("iters"), &values("times"));
+        let fences = TukeyFences::from_samples(&per_iteration).unwrap();
+        assert_float_eq(fences.low_severe, 227.5298);
+        assert_float_eq(fences.low_mild, 231.2154);
+        assert_float_eq(fences.high_mild, 241.0438);
+        assert_float_eq(fences.high_severe, 244.7294);
+
+        assert_eq!(fences.classify(236.0), OutlierClass::NotAnOutlier);
+        assert_eq!(fences.classify(243.0), OutlierClass::MildHigh);
+        assert_eq!(fences.classify(200.0), OutlierClass::SevereLow);
+        assert!(TukeyFences::from_samples(&[1.0]).is_none());
+    }
+
+    #[test]
+    fn test_outliers_from_json_and_sample_json() {
+        let json = &parse_json_output(JSON_OUTPUT)[0];
+        let outliers = json.outliers.as_ref().unwrap();
+        assert_eq!(outliers.total_measurements, 3);
+        assert_eq!(outliers.outlier_count, 0);
+        assert!(outliers.fences.is_some());
+
+        let sample = parse_sample_json(include_str!("fixtures/criterion_sample.json")).unwrap();
+        let outliers = sample.outliers.as_ref().unwrap();
+        assert_eq!(
+            outliers.total_measurements,
+            sample.iteration_count.len() as u64
+        );
+        assert_eq!(
+            outliers.outlier_count,
+            outliers.mild_low + outliers.mild_high + outliers.severe_low + outliers.severe_high
+        );
+    }
+
+    #[test]
+    fn test_directory_confidence() {
+        let input = include_str!("fixtures/criterion_directory.txt");
+        assert_float_eq(directory_confidence(input), 1.0);
+        assert_float_eq(directory_confidence("==> notes.txt <==\nhello\n"), 0.0);
+        assert_float_eq(
+            directory_confidence(include_str!("fixtures/criterion_sample.json")),
+            0.0,
+        );
+    }
 }
@@ -0,0 +1,79 @@
+use super::{BenchmarkResult, Candidate, CommitInfo, InputFormat, MachineInfo};
+use std::fmt;
+use thiserror::Error;
+
+/// Input that could not be turned into any results.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ParseError {
+    #[error("Input is empty")]
+    Empty,
+    #[error("Input doesn't look like output from any supported tool")]
+    UnrecognizedFormat,
+    #[error("Input looks like {format} output but no results could be parsed from it")]
+    NoResults { format: InputFormat },
+}
+
+/// A recoverable problem found while parsing; the affected value falls back to its default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// 1-based line number in the original input, when the problem is tied to a line
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn at_line(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: Some(line),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+/// Parsed results together with the detected format and any warnings raised along the way.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseReport {
+    /// Formats the results were parsed as; more than one when the input mixed tools
+    pub formats: Vec<InputFormat>,
+    /// Every plausible format for the input, most likely first
+    pub candidates: Vec<Candidate>,
+    pub results: Vec<BenchmarkResult>,
+    /// The machine the benchmarks ran on, for tools that record it
+    pub machine: Option<MachineInfo>,
+    /// The source revision the benchmarks were built from, for tools that record it
+    pub commit: Option<CommitInfo>,
+    pub warnings: Vec<Diagnostic>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_with_line() {
+        let diagnostic = Diagnostic::at_line(4, "unknown time unit `m`");
+        assert_eq!(diagnostic.to_string(), "line 4: unknown time unit `m`");
+    }
+
+    #[test]
+    fn display_without_line() {
+        let diagnostic = Diagnostic::new("percentile spectrum is truncated");
+        assert_eq!(diagnostic.to_string(), "percentile spectrum is truncated");
+    }
+}
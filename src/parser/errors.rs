@@ -0,0 +1,206 @@
+use super::{is_empty, Diagnostic};
+use serde::{Deserialize, Serialize};
+
+/// Error counts reported at the end of a wrk run.
+/// Each category is skipped when zero so clean runs add nothing to the encoded URL.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct ErrorCounts {
+    #[serde(default, skip_serializing_if = "is_empty::check_u64")]
+    pub connect: u64,
+    #[serde(default, skip_serializing_if = "is_empty::check_u64")]
+    pub read: u64,
+    #[serde(default, skip_serializing_if = "is_empty::check_u64")]
+    pub write: u64,
+    #[serde(default, skip_serializing_if = "is_empty::check_u64")]
+    pub timeout: u64,
+    #[serde(default, skip_serializing_if = "is_empty::check_u64")]
+    pub non_2xx_3xx: u64,
+}
+
+impl ErrorCounts {
+    pub fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+
+    /// Socket errors across all categories (connect, read, write, timeout).
+    pub fn socket_errors(&self) -> u64 {
+        self.connect + self.read + self.write + self.timeout
+    }
+
+    /// All failed requests: socket errors plus non-2xx/3xx responses.
+    pub fn total(&self) -> u64 {
+        self.socket_errors() + self.non_2xx_3xx
+    }
+
+    /// Counts `count` socket errors under the category `message` describes, for tools
+    /// that report error messages rather than wrk's categories (hey, oha).
+    pub fn add_message(&mut self, message: &str, count: u64) {
+        let message = message.to_lowercase();
+        if message.contains("timeout")
+            || message.contains("timed out")
+            || message.contains("deadline")
+        {
+            self.timeout += count;
+        } else if message.contains("closed") || message.contains("reset") {
+            self.read += count;
+        } else if message.contains("dial") || message.contains("connect") {
+            self.connect += count;
+        } else if message.contains("write") {
+            self.write += count;
+        } else {
+            self.read += count;
+        }
+    }
+
+    /// Percentage of `total_requests` that failed, or `0.0` when nothing was sent.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn error_rate(&self, total_requests: u64) -> f64 {
+        if total_requests == 0 {
+            0.0
+        } else {
+            self.total() as f64 / total_requests as f64 * 100.0
+        }
+    }
+}
+
+impl ErrorCounts {
+    /// Parses the error summary lines from the wrk output. Counts that can't be read
+    /// are left at 0 and reported in `warnings`.
+    /// `Socket errors: connect 0, read 152, write 3, timeout 87`
+    /// `Non-2xx or 3xx responses: 37962`
+    pub fn parse(lines: &[(usize, &str)], warnings: &mut Vec<Diagnostic>) -> Self {
+        let mut errors = ErrorCounts::default();
+
+        if let Some((number, line)) = lines.iter().find(|(_, l)| l.starts_with("Socket errors:")) {
+            for part in line.trim_start_matches("Socket errors:").split(',') {
+                let mut tokens = part.split_whitespace();
+                let (Some(kind), Some(count)) = (tokens.next(), tokens.next()) else {
+                    continue;
+                };
+                let Ok(count) = count.parse() else {
+                    warnings.push(Diagnostic::at_line(
+                        *number,
+                        format!("can't read `{kind}` socket error count `{count}`"),
+                    ));
+                    continue;
+                };
+                match kind {
+                    "connect" => errors.connect = count,
+                    "read" => errors.read = count,
+                    "write" => errors.write = count,
+                    "timeout" => errors.timeout = count,
+                    _ => {}
+                }
+            }
+        }
+
+        if let Some((number, line)) = lines
+            .iter()
+            .find(|(_, l)| l.starts_with("Non-2xx or 3xx responses:"))
+        {
+            let count = line.split(':').nth(1).unwrap_or_default().trim();
+            match count.parse() {
+                Ok(count) => errors.non_2xx_3xx = count,
+                Err(_) => warnings.push(Diagnostic::at_line(
+                    *number,
+                    format!("can't read `Non-2xx or 3xx responses:` count `{count}`"),
+                )),
+            }
+        }
+
+        errors
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn parse(lines: &[&str]) -> ErrorCounts {
+        let numbered = lines.iter().copied().enumerate().collect::<Vec<_>>();
+        ErrorCounts::parse(&numbered, &mut Vec::new())
+    }
+
+    #[test]
+    fn parse_socket_errors_line() {
+        let lines = ["Socket errors: connect 1, read 152, write 3, timeout 87"];
+        let errors = parse(&lines);
+        assert_eq!(errors.connect, 1);
+        assert_eq!(errors.read, 152);
+        assert_eq!(errors.write, 3);
+        assert_eq!(errors.timeout, 87);
+        assert_eq!(errors.non_2xx_3xx, 0);
+        assert_eq!(errors.socket_errors(), 243);
+    }
+
+    #[test]
+    fn parse_non_2xx_line() {
+        let lines = ["Non-2xx or 3xx responses: 37962"];
+        let errors = parse(&lines);
+        assert_eq!(errors.non_2xx_3xx, 37962);
+        assert_eq!(errors.socket_errors(), 0);
+        assert_eq!(errors.total(), 37962);
+    }
+
+    #[test]
+    fn parse_no_error_lines() {
+        let lines = ["Requests/sec:   2000.15", "Transfer/sec:    676.14KB"];
+        let errors = parse(&lines);
+        assert!(errors.is_empty());
+        assert_eq!(errors, ErrorCounts::default());
+    }
+
+    #[test]
+    fn parse_warns_on_unreadable_counts() {
+        let lines = [
+            (7, "Socket errors: connect 1, read ?, write 3, timeout 87"),
+            (8, "Non-2xx or 3xx responses: lots"),
+        ];
+        let mut warnings = Vec::new();
+        let errors = ErrorCounts::parse(&lines, &mut warnings);
+        assert_eq!(errors.read, 0);
+        assert_eq!(errors.timeout, 87);
+        assert_eq!(errors.non_2xx_3xx, 0);
+        let rendered: Vec<_> = warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "line 7: can't read `read` socket error count `?`",
+                "line 8: can't read `Non-2xx or 3xx responses:` count `lots`",
+            ]
+        );
+    }
+
+    #[test]
+    fn add_error_messages() {
+        let mut errors = ErrorCounts::default();
+        errors.add_message("dial tcp 127.0.0.1:8080: connect: connection refused", 3);
+        errors.add_message("connection closed before message completed", 2);
+        errors.add_message("context deadline exceeded", 1);
+        errors.add_message("write: broken pipe", 4);
+        assert_eq!(errors.connect, 3);
+        assert_eq!(errors.read, 2);
+        assert_eq!(errors.timeout, 1);
+        assert_eq!(errors.write, 4);
+    }
+
+    #[test]
+    fn error_rate_percentage() {
+        let errors = ErrorCounts {
+            timeout: 50,
+            non_2xx_3xx: 250,
+            ..Default::default()
+        };
+        assert!((errors.error_rate(1000) - 30.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn error_rate_no_requests() {
+        let errors = ErrorCounts {
+            connect: 10,
+            ..Default::default()
+        };
+        assert!(errors.error_rate(0).abs() < f64::EPSILON);
+    }
+}
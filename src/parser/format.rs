@@ -0,0 +1,472 @@
+use super::{
+    ab, asv, bombardier, criterion, go_bench, google_benchmark, hdr, hey, hyperfine, jmh, k6,
+    libtest, metrics, oha, pytest_benchmark, vegeta, BenchmarkResult, CommitInfo, Diagnostic,
+    MachineInfo,
+};
+use std::{fmt, str::FromStr};
+
+/// Input formats the parser understands. Auto-detection scores each one; the share
+/// modal can also pick one explicitly when detection gets it wrong.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InputFormat {
+    /// `cargo-criterion --message-format=json`
+    CriterionJson,
+    /// A Criterion `sample.json` file
+    CriterionSample,
+    /// Files from a `target/criterion` directory, joined with `==> path <==` headers
+    CriterionDirectory,
+    /// Criterion's terminal output from `cargo bench`
+    CriterionCli,
+    /// libtest `#[bench]` output from `cargo bench` on nightly
+    Libtest,
+    /// Google Benchmark `--benchmark_format=json` output
+    GoogleBenchmark,
+    /// hyperfine `--export-json` output
+    Hyperfine,
+    /// `go test -bench` output, with or without `-benchmem`
+    GoBench,
+    /// JMH `-rf json` results
+    Jmh,
+    /// pytest-benchmark `--benchmark-json` output
+    PytestBenchmark,
+    /// airspeed velocity (asv) result files
+    Asv,
+    Wrk2,
+    Wrk,
+    Hey,
+    /// `ab`, the Apache HTTP server benchmarking tool
+    Ab,
+    /// `oha --json`
+    Oha,
+    Bombardier,
+    /// `bombardier --print r --format json`
+    BombardierJson,
+    /// `vegeta report`
+    VegetaText,
+    /// `vegeta report -type=json`
+    VegetaJson,
+    /// k6's end-of-test summary
+    K6Text,
+    /// `k6 run --summary-export` JSON
+    K6Json,
+    /// `HdrHistogram` percentile distribution (`.hgrm`)
+    Hgrm,
+    /// `HdrHistogram` interval log (`.hlog`)
+    Hlog,
+}
+
+impl InputFormat {
+    /// Every format, in the order detection breaks ties.
+    pub const ALL: [Self; 24] = [
+        Self::CriterionJson,
+        Self::CriterionSample,
+        Self::CriterionDirectory,
+        Self::CriterionCli,
+        Self::Libtest,
+        Self::GoogleBenchmark,
+        Self::Hyperfine,
+        Self::GoBench,
+        Self::Jmh,
+        Self::PytestBenchmark,
+        Self::Asv,
+        Self::Wrk2,
+        Self::Wrk,
+        Self::Hey,
+        Self::Ab,
+        Self::Oha,
+        Self::Bombardier,
+        Self::BombardierJson,
+        Self::VegetaText,
+        Self::VegetaJson,
+        Self::K6Text,
+        Self::K6Json,
+        Self::Hgrm,
+        Self::Hlog,
+    ];
+
+    /// Stable identifier, used as the value of the share modal's format picker.
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::CriterionJson => "criterion-json",
+            Self::CriterionSample => "criterion-sample",
+            Self::CriterionDirectory => "criterion-dir",
+            Self::CriterionCli => "criterion-cli",
+            Self::Libtest => "libtest",
+            Self::GoogleBenchmark => "google-benchmark",
+            Self::Hyperfine => "hyperfine",
+            Self::GoBench => "go-bench",
+            Self::Jmh => "jmh",
+            Self::PytestBenchmark => "pytest-benchmark",
+            Self::Asv => "asv",
+            Self::Wrk2 => "wrk2",
+            Self::Wrk => "wrk",
+            Self::Hey => "hey",
+            Self::Ab => "ab",
+            Self::Oha => "oha",
+            Self::Bombardier => "bombardier",
+            Self::BombardierJson => "bombardier-json",
+            Self::VegetaText => "vegeta-text",
+            Self::VegetaJson => "vegeta-json",
+            Self::K6Text => "k6",
+            Self::K6Json => "k6-json",
+            Self::Hgrm => "hgrm",
+            Self::Hlog => "hlog",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::CriterionJson => "Criterion.rs JSON",
+            Self::CriterionSample => "Criterion.rs sample.json",
+            Self::CriterionDirectory => "Criterion.rs target/criterion",
+            Self::CriterionCli => "Criterion.rs",
+            Self::Libtest => "libtest bench",
+            Self::GoogleBenchmark => "Google Benchmark JSON",
+            Self::Hyperfine => "hyperfine JSON",
+            Self::GoBench => "go test -bench",
+            Self::Jmh => "JMH JSON",
+            Self::PytestBenchmark => "pytest-benchmark JSON",
+            Self::Asv => "asv results JSON",
+            Self::Wrk2 => "wrk2",
+            Self::Wrk => "wrk",
+            Self::Hey => "hey",
+            Self::Ab => "ApacheBench",
+            Self::Oha => "oha",
+            Self::Bombardier => "bombardier",
+            Self::BombardierJson => "bombardier JSON",
+            Self::VegetaText => "Vegeta",
+            Self::VegetaJson => "Vegeta JSON",
+            Self::K6Text => "k6",
+            Self::K6Json => "k6 JSON",
+            Self::Hgrm => "HdrHistogram .hgrm",
+            Self::Hlog => "HdrHistogram interval log",
+        }
+    }
+
+    /// Confidence (0.0–1.0) that `output` is in this format.
+    pub fn confidence(self, output: &str) -> f64 {
+        match self {
+            Self::CriterionJson => criterion::json_confidence(output),
+            Self::CriterionSample => criterion::sample_confidence(output),
+            Self::CriterionDirectory => criterion::directory_confidence(output),
+            Self::CriterionCli => criterion::cli_confidence(output),
+            Self::Libtest => libtest::confidence(output),
+            Self::GoogleBenchmark => google_benchmark::confidence(output),
+            Self::Hyperfine => hyperfine::confidence(output),
+            Self::GoBench => go_bench::confidence(output),
+            Self::Jmh => jmh::confidence(output),
+            Self::PytestBenchmark => pytest_benchmark::confidence(output),
+            Self::Asv => asv::confidence(output),
+            // wrk and wrk2 share a parser; wrk2-only sections decide between them
+            Self::Wrk2 if metrics::is_wrk2(output) => metrics::confidence(output),
+            Self::Wrk2 => metrics::confidence(output) * 0.5,
+            Self::Wrk if metrics::is_wrk2(output) => metrics::confidence(output) * 0.6,
+            Self::Wrk => metrics::confidence(output),
+            Self::Hey => hey::confidence(output),
+            Self::Ab => ab::confidence(output),
+            Self::Oha => oha::confidence(output),
+            Self::Bombardier => bombardier::text_confidence(output),
+            Self::BombardierJson => bombardier::json_confidence(output),
+            Self::VegetaText => vegeta::text_confidence(output),
+            Self::VegetaJson => vegeta::json_confidence(output),
+            Self::K6Text => k6::text_confidence(output),
+            Self::K6Json => k6::json_confidence(output),
+            Self::Hgrm => hdr::hgrm_confidence(output),
+            Self::Hlog => hdr::hlog_confidence(output),
+        }
+    }
+
+    /// Parses `output` as this format, along with the machine it ran on and the commit it
+    /// was built from when the tool records them. Values that fell back to a default
+    /// while parsing are reported in `warnings`.
+    pub fn parse(
+        self,
+        output: &str,
+        warnings: &mut Vec<Diagnostic>,
+    ) -> (
+        Option<MachineInfo>,
+        Option<CommitInfo>,
+        Vec<BenchmarkResult>,
+    ) {
+        let criterion = |results: Vec<criterion::CriterionMetrics>| {
+            results
+                .into_iter()
+                .map(|m| BenchmarkResult::Criterion(Box::new(m)))
+                .collect()
+        };
+        let wrk = |results: Vec<metrics::WrkMetrics>| {
+            results
+                .into_iter()
+                .map(|m| BenchmarkResult::Wrk(Box::new(m)))
+                .collect()
+        };
+        let vegeta = |results: Vec<vegeta::VegetaMetrics>| {
+            results
+                .into_iter()
+                .map(|m| BenchmarkResult::Vegeta(Box::new(m)))
+                .collect()
+        };
+        let with_machine = |(machine, benchmarks): (Option<MachineInfo>, Vec<_>)| {
+            (machine, None, criterion(benchmarks))
+        };
+        let with_run_info =
+            |(machine, commit, benchmarks): (Option<MachineInfo>, Option<CommitInfo>, Vec<_>)| {
+                (machine, commit, criterion(benchmarks))
+            };
+        let results = match self {
+            Self::CriterionJson => criterion(criterion::parse_json_output(output)),
+            Self::CriterionSample => {
+                criterion(criterion::parse_sample_json(output).into_iter().collect())
+            }
+            Self::CriterionDirectory => criterion(criterion::parse_directory(output)),
+            Self::CriterionCli => criterion(criterion::parse_cli_output(output)),
+            Self::Libtest => criterion(libtest::parse(output)),
+            Self::GoogleBenchmark => return with_machine(google_benchmark::parse(output)),
+            Self::Hyperfine => criterion(hyperfine::parse(output)),
+            Self::GoBench => return with_machine(go_bench::parse(output)),
+            Self::Jmh => criterion(jmh::parse(output)),
+            Self::PytestBenchmark => return with_run_info(pytest_benchmark::parse(output)),
+            Self::Asv => return with_run_info(asv::parse(output, warnings)),
+            Self::Wrk2 | Self::Wrk => wrk(metrics::parse_tests(output, warnings)),
+            Self::Hey => wrk(hey::parse(output, warnings)),
+            Self::Ab => wrk(ab::parse(output, warnings)),
+            Self::Oha => wrk(oha::parse(output, warnings).into_iter().collect()),
+            Self::Bombardier => wrk(bombardier::parse_text(output, warnings)),
+            Self::BombardierJson => wrk(bombardier::parse_json(output, warnings)
+                .into_iter()
+                .collect()),
+            Self::VegetaText => vegeta(vegeta::parse_text(output, warnings)),
+            Self::VegetaJson => vegeta(vegeta::parse_json(output, warnings)),
+            Self::K6Text => k6::parse_text(output, warnings)
+                .map(|m| BenchmarkResult::K6(Box::new(m)))
+                .into_iter()
+                .collect(),
+            Self::K6Json => k6::parse_json(output, warnings)
+                .map(|m| BenchmarkResult::K6(Box::new(m)))
+                .into_iter()
+                .collect(),
+            Self::Hgrm => wrk(hdr::parse_hgrm(output, warnings).into_iter().collect()),
+            Self::Hlog => wrk(hdr::parse_hlog(output, warnings).into_iter().collect()),
+        };
+        (None, None, results)
+    }
+
+    /// Warnings about `results` that the parse itself can't see, such as failed runs or
+    /// benchmarks without a timing estimate.
+    pub fn diagnose(self, output: &str, results: &[BenchmarkResult]) -> Vec<Diagnostic> {
+        match self {
+            Self::CriterionJson
+            | Self::CriterionSample
+            | Self::CriterionDirectory
+            | Self::CriterionCli
+            | Self::Libtest
+            | Self::GoBench
+            | Self::Jmh
+            | Self::PytestBenchmark
+            | Self::Asv => missing_estimates(results),
+            Self::GoogleBenchmark => {
+                let mut warnings = google_benchmark::diagnose(output);
+                warnings.extend(missing_estimates(results));
+                warnings
+            }
+            Self::Hyperfine => hyperfine::diagnose(output),
+            Self::Wrk2
+            | Self::Wrk
+            | Self::Hey
+            | Self::Oha
+            | Self::Bombardier
+            | Self::BombardierJson
+            | Self::VegetaText
+            | Self::VegetaJson
+            | Self::K6Text
+            | Self::K6Json
+            | Self::Hgrm
+            | Self::Hlog => Vec::new(),
+            Self::Ab => ab::diagnose(output),
+        }
+    }
+}
+
+/// Benchmarks that were parsed but carry no timing estimate to chart.
+fn missing_estimates(results: &[BenchmarkResult]) -> Vec<Diagnostic> {
+    results
+        .iter()
+        .filter_map(|r| match r {
+            BenchmarkResult::Criterion(m) if m.time.estimate == 0.0 => Some(Diagnostic::new(
+                format!("benchmark `{}` has no timing estimate", m.name),
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+impl fmt::Display for InputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for InputFormat {
+    type Err = ();
+
+    fn from_str(id: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter().find(|f| f.id() == id).ok_or(())
+    }
+}
+
+/// A possible format for some input and how likely it is to be right.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Candidate {
+    pub format: InputFormat,
+    pub confidence: f64,
+}
+
+/// Scores `output` against every format and returns the plausible ones, most likely first.
+/// Ties keep the order of [`InputFormat::ALL`].
+pub fn detect(output: &str) -> Vec<Candidate> {
+    let mut candidates = InputFormat::ALL
+        .into_iter()
+        .map(|format| Candidate {
+            format,
+            confidence: format.confidence(output),
+        })
+        .filter(|c| c.confidence > 0.0)
+        .collect::<Vec<_>>();
+    candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    candidates
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn top(output: &str) -> InputFormat {
+        detect(output).first().expect("a candidate").format
+    }
+
+    #[test]
+    fn detect_ranks_formats() {
+        assert_eq!(
+            top(include_str!("fixtures/wrk1_basic.txt")),
+            InputFormat::Wrk
+        );
+        assert_eq!(
+            top(include_str!("fixtures/wrk2_full.txt")),
+            InputFormat::Wrk2
+        );
+        assert_eq!(
+            top(include_str!("fixtures/criterion_cli_simple.txt")),
+            InputFormat::CriterionCli
+        );
+        assert_eq!(
+            top(include_str!("fixtures/criterion_json_output.json")),
+            InputFormat::CriterionJson
+        );
+        assert_eq!(
+            top(include_str!("fixtures/criterion_sample.json")),
+            InputFormat::CriterionSample
+        );
+        assert_eq!(
+            top(include_str!("fixtures/criterion_directory.txt")),
+            InputFormat::CriterionDirectory
+        );
+        assert_eq!(
+            top(include_str!("fixtures/libtest_bench.txt")),
+            InputFormat::Libtest
+        );
+        assert_eq!(
+            top(include_str!("fixtures/google_benchmark.json")),
+            InputFormat::GoogleBenchmark
+        );
+        assert_eq!(
+            top(include_str!("fixtures/hyperfine.json")),
+            InputFormat::Hyperfine
+        );
+        assert_eq!(
+            top(include_str!("fixtures/go_bench.txt")),
+            InputFormat::GoBench
+        );
+        assert_eq!(top(include_str!("fixtures/jmh.json")), InputFormat::Jmh);
+        assert_eq!(
+            top(include_str!("fixtures/pytest_benchmark.json")),
+            InputFormat::PytestBenchmark
+        );
+        assert_eq!(
+            top(include_str!("fixtures/asv_results.json")),
+            InputFormat::Asv
+        );
+        assert_eq!(
+            top(include_str!("fixtures/hey_basic.txt")),
+            InputFormat::Hey
+        );
+        assert_eq!(
+            top(include_str!("fixtures/oha_basic.json")),
+            InputFormat::Oha
+        );
+        assert_eq!(
+            top(include_str!("fixtures/bombardier_basic.txt")),
+            InputFormat::Bombardier
+        );
+        assert_eq!(
+            top(include_str!("fixtures/bombardier_result.json")),
+            InputFormat::BombardierJson
+        );
+        assert_eq!(
+            top(include_str!("fixtures/vegeta_report.txt")),
+            InputFormat::VegetaText
+        );
+        assert_eq!(
+            top(include_str!("fixtures/vegeta_report.json")),
+            InputFormat::VegetaJson
+        );
+        assert_eq!(
+            top(include_str!("fixtures/k6_summary.txt")),
+            InputFormat::K6Text
+        );
+        assert_eq!(
+            top(include_str!("fixtures/k6_summary_export.json")),
+            InputFormat::K6Json
+        );
+        assert_eq!(
+            top(include_str!("fixtures/hdr_latency.hgrm")),
+            InputFormat::Hgrm
+        );
+        assert_eq!(
+            top(include_str!("fixtures/hdr_latency.hlog")),
+            InputFormat::Hlog
+        );
+    }
+
+    #[test]
+    fn detect_keeps_runner_up() {
+        let candidates = detect(include_str!("fixtures/wrk2_full.txt"));
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[1].format, InputFormat::Wrk);
+        assert!(candidates[0].confidence > candidates[1].confidence);
+    }
+
+    #[test]
+    fn detect_nothing_in_garbage() {
+        assert!(detect("this is not benchmark output at all").is_empty());
+        assert!(detect("").is_empty());
+    }
+
+    #[test]
+    fn id_round_trips() {
+        for format in InputFormat::ALL {
+            assert_eq!(format.id().parse::<InputFormat>(), Ok(format));
+        }
+        assert!("jmeter".parse::<InputFormat>().is_err());
+    }
+
+    #[test]
+    fn parse_as_wrong_format_is_empty() {
+        let wrk = include_str!("fixtures/wrk1_basic.txt");
+        assert!(InputFormat::CriterionCli
+            .parse(wrk, &mut Vec::new())
+            .2
+            .is_empty());
+        assert_eq!(InputFormat::Wrk.parse(wrk, &mut Vec::new()).2.len(), 1);
+    }
+}
@@ -0,0 +1,289 @@
+//! Go's `go test -bench` output, with or without `-benchmem`:
+//!
+//! ```text
+//! goos: linux
+//! goarch: amd64
+//! cpu: AMD Ryzen 9 5950X 16-Core Processor
+//! BenchmarkSort/size=1000-32     21306     56103 ns/op     8192 B/op     1 allocs/op
+//! ```
+//!
+//! Results become [`CriterionMetrics`] so they share the Criterion dashboard. Repeated
+//! lines from `-count=N` are the samples of one benchmark.
+
+use super::bootstrap;
+use super::criterion::{
+    ConfidenceInterval, CriterionMetrics, ExtraMetric, Outliers, Throughput, TimeUnit, TukeyFences,
+};
+use super::machine::MachineInfo;
+
+const NS_TO_MS: f64 = 0.000_001;
+
+/// One benchmark line: the run's iteration count and every `<value> <unit>` pair.
+struct Line<'a> {
+    name: &'a str,
+    iterations: f64,
+    ns_per_op: f64,
+    metrics: Vec<(f64, &'a str)>,
+}
+
+/// Confidence (0.0–1.0) that `output` is `go test -bench` output.
+pub fn confidence(output: &str) -> f64 {
+    if !output.lines().any(|l| parse_line(l).is_some()) {
+        return 0.0;
+    }
+    let signals = [
+        (0.4, true),
+        (0.2, output.lines().any(|l| l.starts_with("goos: "))),
+        (0.2, output.contains(" allocs/op")),
+        (
+            0.2,
+            output
+                .lines()
+                .any(|l| l.starts_with("ok  \t") || l.trim() == "PASS"),
+        ),
+    ];
+    signals
+        .iter()
+        .filter(|(_, present)| *present)
+        .map(|(weight, _)| weight)
+        .sum()
+}
+
+/// Parses every benchmark line, merging repetitions of the same benchmark, and the
+/// `goos`/`goarch`/`cpu` headers.
+pub fn parse(output: &str) -> (Option<MachineInfo>, Vec<CriterionMetrics>) {
+    let mut runs: Vec<(&str, Vec<Line>)> = Vec::new();
+    for line in output.lines().filter_map(parse_line) {
+        match runs.iter_mut().find(|(name, _)| *name == line.name) {
+            Some((_, lines)) => lines.push(line),
+            None => runs.push((line.name, vec![line])),
+        }
+    }
+    let benchmarks = runs
+        .into_iter()
+        .map(|(name, lines)| merge_runs(name, &lines))
+        .collect();
+    (parse_headers(output), benchmarks)
+}
+
+/// "BenchmarkName-8   1000000   1234 ns/op   512 B/op   4 allocs/op"
+fn parse_line(line: &str) -> Option<Line<'_>> {
+    let mut fields = line.split_whitespace();
+    let name = fields.next()?;
+    if !name.starts_with("Benchmark") {
+        return None;
+    }
+    let iterations = fields.next()?.parse().ok()?;
+    let rest: Vec<&str> = fields.collect();
+    let metrics: Vec<(f64, &str)> = rest
+        .chunks_exact(2)
+        .map(|pair| Some((pair[0].parse().ok()?, pair[1])))
+        .collect::<Option<_>>()?;
+    let ns_per_op = metrics
+        .iter()
+        .find(|(_, unit)| *unit == "ns/op")
+        .map(|(value, _)| *value)?;
+    Some(Line {
+        name: strip_procs(name),
+        iterations,
+        ns_per_op,
+        metrics,
+    })
+}
+
+/// Drops the `-8` GOMAXPROCS suffix Go appends to every name.
+fn strip_procs(name: &str) -> &str {
+    match name.rsplit_once('-') {
+        Some((base, procs)) if procs.chars().all(|c| c.is_ascii_digit()) => base,
+        _ => name,
+    }
+}
+
+/// One benchmark from all its `-count` repetitions. Like benchstat, the headline time
+/// is the median across repetitions; a single run is a point estimate.
+#[allow(clippy::cast_precision_loss)]
+fn merge_runs(name: &str, lines: &[Line]) -> CriterionMetrics {
+    let iteration_count: Vec<f64> = lines.iter().map(|l| l.iterations).collect();
+    let measured_values: Vec<f64> = lines.iter().map(|l| l.ns_per_op * l.iterations).collect();
+    let ns_per_op: Vec<f64> = lines.iter().map(|l| l.ns_per_op).collect();
+    let mean_of = |unit: &str| {
+        let values: Vec<f64> = lines
+            .iter()
+            .flat_map(|l| &l.metrics)
+            .filter(|(_, u)| *u == unit)
+            .map(|(value, _)| *value)
+            .collect();
+        (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+    };
+
+    let mut metrics = match bootstrap::estimate(&iteration_count, &measured_values) {
+        Some(estimates) => CriterionMetrics {
+            time: estimates.median.clone(),
+            mean: Some(estimates.mean),
+            median: Some(estimates.median),
+            std_dev: Some(estimates.std_dev),
+            median_abs_dev: Some(estimates.median_abs_dev),
+            outliers: TukeyFences::from_samples(&ns_per_op)
+                .map(|fences| Outliers::from_samples(fences, &ns_per_op)),
+            iteration_count,
+            measured_values,
+            ..Default::default()
+        },
+        None => CriterionMetrics {
+            time: ConfidenceInterval {
+                lower_bound: ns_per_op[0] * NS_TO_MS,
+                estimate: ns_per_op[0] * NS_TO_MS,
+                upper_bound: ns_per_op[0] * NS_TO_MS,
+                unit: TimeUnit::Milliseconds,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    };
+    metrics.name = name.to_string();
+
+    // "MB/s" from b.SetBytes is bytes per op over the time per op, in 10^6 bytes/s
+    metrics.throughput = mean_of("MB/s").map(|mb_per_sec| Throughput {
+        per_iteration: (mb_per_sec * metrics.time.estimate * 1_000.0).round(),
+        unit: "bytes".to_string(),
+    });
+    let mut units: Vec<&str> = Vec::new();
+    for (_, unit) in lines.iter().flat_map(|l| &l.metrics) {
+        if !matches!(*unit, "ns/op" | "MB/s") && !units.contains(unit) {
+            units.push(unit);
+        }
+    }
+    metrics.extra_metrics = units
+        .into_iter()
+        .filter_map(|unit| {
+            Some(ExtraMetric {
+                name: String::new(),
+                value: mean_of(unit)?,
+                unit: unit.to_string(),
+            })
+        })
+        .collect();
+    metrics
+}
+
+/// `goos`, `goarch` and `cpu` from the header `go test` prints before each package.
+fn parse_headers(output: &str) -> Option<MachineInfo> {
+    let header = |key: &str| {
+        output
+            .lines()
+            .find_map(|l| l.strip_prefix(key))
+            .map(|value| value.trim().to_string())
+    };
+    let (goos, goarch, cpu) = (header("goos:"), header("goarch:"), header("cpu:"));
+    if goos.is_none() && goarch.is_none() && cpu.is_none() {
+        return None;
+    }
+    Some(MachineInfo {
+        os: [goos, goarch]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join("/"),
+        cpu: cpu.unwrap_or_default(),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = include_str!("fixtures/go_bench.txt");
+
+    fn assert_float_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "Expected {a} to be approximately {b}");
+    }
+
+    #[test]
+    fn parse_repetitions_as_samples() {
+        let (_, results) = parse(OUTPUT);
+        let names: Vec<_> = results.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "BenchmarkSort/size=10",
+                "BenchmarkSort/size=1000",
+                "BenchmarkTokenize",
+                "BenchmarkLookup"
+            ]
+        );
+
+        let sort = &results[1];
+        assert_eq!(sort.group_name(), "BenchmarkSort");
+        assert_eq!(sort.iteration_count, vec![21_306.0, 21_488.0, 20_977.0]);
+        assert_float_eq(sort.measured_values[0], 56_103.0 * 21_306.0);
+        // the median of 56103, 55871 and 57240 ns/op
+        assert_float_eq(sort.time.estimate, 0.056_103);
+        assert!(sort.time.lower_bound <= sort.time.estimate);
+        assert!(sort.std_dev.is_some());
+        assert!(sort.slope.is_none());
+    }
+
+    #[test]
+    fn parse_benchmem_metrics() {
+        let (_, results) = parse(OUTPUT);
+        let sort = &results[1];
+        assert_eq!(
+            sort.extra_metrics,
+            vec![
+                ExtraMetric {
+                    name: String::new(),
+                    value: 8192.0,
+                    unit: "B/op".to_string()
+                },
+                ExtraMetric {
+                    name: String::new(),
+                    value: 1.0,
+                    unit: "allocs/op".to_string()
+                }
+            ]
+        );
+
+        // 438.41 MB/s at 9342 ns/op is about 4096 bytes per op
+        let tokenize = &results[2];
+        let throughput = tokenize.throughput.as_ref().unwrap();
+        assert_eq!(throughput.unit, "bytes");
+        assert_float_eq(throughput.per_iteration, 4096.0);
+        assert_eq!(tokenize.extra_metrics.len(), 2);
+    }
+
+    #[test]
+    fn parse_single_run_with_custom_metric() {
+        let (_, results) = parse(OUTPUT);
+        let lookup = &results[3];
+        assert_float_eq(lookup.time.estimate, 0.001_043);
+        assert_float_eq(lookup.time.lower_bound, lookup.time.upper_bound);
+        assert!(lookup.iteration_count.is_empty());
+        assert_eq!(lookup.extra_metrics[0].unit, "probes/op");
+        assert_float_eq(lookup.extra_metrics[0].value, 3.0);
+    }
+
+    #[test]
+    fn parse_machine_headers() {
+        let (machine, _) = parse(OUTPUT);
+        let machine = machine.unwrap();
+        assert_eq!(machine.os, "linux/amd64");
+        assert_eq!(machine.cpu, "AMD Ryzen 9 5950X 16-Core Processor");
+        assert!(parse("BenchmarkFoo 100 10 ns/op").0.is_none());
+    }
+
+    #[test]
+    fn strip_gomaxprocs_suffix() {
+        assert_eq!(strip_procs("BenchmarkFoo-8"), "BenchmarkFoo");
+        assert_eq!(strip_procs("BenchmarkFoo"), "BenchmarkFoo");
+        assert_eq!(strip_procs("BenchmarkFoo/a-b"), "BenchmarkFoo/a-b");
+    }
+
+    #[test]
+    fn test_confidence() {
+        assert!((confidence(OUTPUT) - 1.0).abs() < 1e-9);
+        assert!(confidence(include_str!("fixtures/libtest_bench.txt")).abs() < 1e-9);
+        assert!(confidence("Benchmarking fib/20: Warming up").abs() < 1e-9);
+    }
+}
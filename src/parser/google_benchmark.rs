@@ -0,0 +1,485 @@
+//! Google Benchmark (C++) results written with `--benchmark_format=json` or
+//! `--benchmark_out=<file>`. Each run becomes a [`CriterionMetrics`] so it shares the
+//! Criterion dashboard, and the `context` block describes the machine it ran on.
+
+use super::criterion::{ConfidenceInterval, CriterionMetrics, ExtraMetric, Throughput, TimeUnit};
+use super::machine::MachineInfo;
+use super::Diagnostic;
+use serde_json::Value;
+
+/// z-score for a 95% confidence interval of the mean across repetitions
+const Z_95: f64 = 1.96;
+
+/// Row fields that aren't user counters.
+const RESERVED_FIELDS: [&str; 18] = [
+    "name",
+    "family_index",
+    "per_family_instance_index",
+    "run_name",
+    "run_type",
+    "repetitions",
+    "repetition_index",
+    "threads",
+    "iterations",
+    "real_time",
+    "cpu_time",
+    "time_unit",
+    "aggregate_name",
+    "aggregate_unit",
+    "error_occurred",
+    "error_message",
+    "label",
+    "big_o",
+];
+
+/// Confidence (0.0–1.0) that `output` is Google Benchmark JSON.
+pub fn confidence(output: &str) -> f64 {
+    let trimmed = output.trim();
+    if !trimmed.starts_with('{') {
+        return 0.0;
+    }
+    let signals = [
+        (0.4, trimmed.contains("\"benchmarks\"")),
+        (0.2, trimmed.contains("\"context\"")),
+        (0.2, trimmed.contains("\"real_time\"")),
+        (0.2, trimmed.contains("\"time_unit\"")),
+    ];
+    signals
+        .iter()
+        .filter(|(_, present)| *present)
+        .map(|(weight, _)| weight)
+        .sum()
+}
+
+/// Parses the `benchmarks` array, one result per run name, and the `context` block.
+/// Repetitions and their `mean`/`median`/`stddev`/`cv` aggregates collapse into a single
+/// result; runs that reported an error are skipped and reported by [`diagnose`].
+pub fn parse(output: &str) -> (Option<MachineInfo>, Vec<CriterionMetrics>) {
+    let Ok(value) = serde_json::from_str::<Value>(output.trim()) else {
+        return (None, Vec::new());
+    };
+
+    let mut runs: Vec<(&str, Vec<&Value>)> = Vec::new();
+    for row in value
+        .get("benchmarks")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let Some(name) = row
+            .get("run_name")
+            .or_else(|| row.get("name"))
+            .and_then(Value::as_str)
+        else {
+            continue;
+        };
+        match runs.iter_mut().find(|(run, _)| *run == name) {
+            Some((_, rows)) => rows.push(row),
+            None => runs.push((name, vec![row])),
+        }
+    }
+
+    let benchmarks = runs
+        .into_iter()
+        .filter_map(|(name, rows)| parse_run(name, &rows))
+        .collect();
+    let machine = value.get("context").map(parse_context);
+    (machine, benchmarks)
+}
+
+/// One run: its repetitions plus whichever aggregates were reported for them.
+#[allow(clippy::cast_precision_loss)]
+fn parse_run(name: &str, rows: &[&Value]) -> Option<CriterionMetrics> {
+    let rows: Vec<&Value> = rows
+        .iter()
+        .copied()
+        .filter(|row| {
+            !row.get("error_occurred")
+                .and_then(Value::as_bool)
+                .unwrap_or(false)
+        })
+        .collect();
+    let repetitions: Vec<&Value> = rows
+        .iter()
+        .copied()
+        .filter(|row| row.get("run_type").and_then(Value::as_str) != Some("aggregate"))
+        .collect();
+    let aggregate = |kind: &str| {
+        rows.iter().copied().find(|row| {
+            row.get("run_type").and_then(Value::as_str) == Some("aggregate")
+                && row.get("aggregate_name").and_then(Value::as_str) == Some(kind)
+        })
+    };
+    let times: Vec<f64> = repetitions
+        .iter()
+        .filter_map(|row| real_time_ms(row))
+        .collect();
+
+    let mean = aggregate("mean")
+        .and_then(real_time_ms)
+        .or_else(|| (!times.is_empty()).then(|| times.iter().sum::<f64>() / times.len() as f64))?;
+    let std_dev = aggregate("stddev")
+        .and_then(real_time_ms)
+        .or_else(|| {
+            // `cv` is a plain ratio and never carries a time unit
+            aggregate("cv")
+                .and_then(|row| row.get("real_time"))
+                .and_then(Value::as_f64)
+                .map(|cv| cv * mean)
+        })
+        .or_else(|| {
+            (times.len() > 1).then(|| {
+                let n = times.len() as f64;
+                (times.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+            })
+        });
+    let median = aggregate("median").and_then(real_time_ms).or_else(|| {
+        (times.len() > 1).then(|| {
+            let mut sorted = times.clone();
+            sorted.sort_unstable_by(f64::total_cmp);
+            super::bootstrap::percentile(&sorted, 0.5)
+        })
+    });
+    // aggregates count repetitions in `iterations`
+    let count = aggregate("mean")
+        .and_then(|row| row.get("iterations"))
+        .and_then(Value::as_f64)
+        .unwrap_or(times.len() as f64);
+
+    let mean_ci = match std_dev {
+        Some(sd) if count > 1.0 => {
+            let standard_error = sd / count.sqrt();
+            ConfidenceInterval {
+                lower_bound: (mean - Z_95 * standard_error).max(0.0),
+                estimate: mean,
+                upper_bound: mean + Z_95 * standard_error,
+                unit: TimeUnit::Milliseconds,
+                standard_error,
+                ..Default::default()
+            }
+        }
+        _ => point(mean),
+    };
+
+    let (iteration_count, measured_values) = if repetitions.len() > 1 {
+        repetitions
+            .iter()
+            .filter_map(|row| {
+                let iterations = row.get("iterations").and_then(Value::as_f64)?;
+                Some((iterations, real_time_ms(row)? * 1_000_000.0 * iterations))
+            })
+            .unzip()
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    let representative = aggregate("mean").or_else(|| repetitions.first().copied())?;
+    let mut extra_metrics: Vec<ExtraMetric> =
+        cpu_time(aggregate("mean"), &repetitions, time_unit(representative))
+            .into_iter()
+            .collect();
+    extra_metrics.extend(counters(representative));
+
+    Some(CriterionMetrics {
+        name: name.to_string(),
+        time: mean_ci.clone(),
+        mean: Some(mean_ci),
+        median: median.map(point),
+        std_dev: std_dev.map(point),
+        throughput: throughput(representative, mean),
+        extra_metrics,
+        iteration_count,
+        measured_values,
+        ..Default::default()
+    })
+}
+
+/// Mean `cpu_time` per iteration, in the run's own time unit.
+#[allow(clippy::cast_precision_loss)]
+fn cpu_time(mean: Option<&Value>, repetitions: &[&Value], unit: &str) -> Option<ExtraMetric> {
+    let cpu_time = |row: &Value| row.get("cpu_time").and_then(Value::as_f64);
+    let value = mean.and_then(cpu_time).or_else(|| {
+        let times: Vec<f64> = repetitions.iter().filter_map(|row| cpu_time(row)).collect();
+        (!times.is_empty()).then(|| times.iter().sum::<f64>() / times.len() as f64)
+    })?;
+    Some(ExtraMetric {
+        name: "CPU time".to_string(),
+        value,
+        unit: unit.to_string(),
+    })
+}
+
+/// `real_time` per iteration, converted from the row's `time_unit` to milliseconds.
+fn real_time_ms(row: &Value) -> Option<f64> {
+    let time = row.get("real_time").and_then(Value::as_f64)?;
+    let scale = match time_unit(row) {
+        "ns" => 0.000_001,
+        "us" => 0.001,
+        "ms" => 1.0,
+        "s" => 1_000.0,
+        _ => return None,
+    };
+    Some(time * scale)
+}
+
+fn time_unit(row: &Value) -> &str {
+    row.get("time_unit").and_then(Value::as_str).unwrap_or("ns")
+}
+
+fn point(estimate: f64) -> ConfidenceInterval {
+    ConfidenceInterval {
+        lower_bound: estimate,
+        estimate,
+        upper_bound: estimate,
+        unit: TimeUnit::Milliseconds,
+        ..Default::default()
+    }
+}
+
+/// `SetBytesProcessed`/`SetItemsProcessed` rates turned back into a per-iteration amount.
+fn throughput(row: &Value, time_ms: f64) -> Option<Throughput> {
+    let rate = |key: &str| row.get(key).and_then(Value::as_f64).filter(|r| *r > 0.0);
+    if let Some(bytes) = rate("bytes_per_second") {
+        return Some(Throughput {
+            per_iteration: (bytes * time_ms / 1_000.0).round(),
+            unit: "bytes".to_string(),
+        });
+    }
+    rate("items_per_second").map(|items| Throughput {
+        per_iteration: (items * time_ms / 1_000.0).round(),
+        unit: "elements".to_string(),
+    })
+}
+
+/// User counters, named after the counter. The JSON doesn't say which flags a counter
+/// was set with, so the value is kept as reported: a `kIsRate` counter is already per
+/// second and a plain one is a total or average.
+fn counters(row: &Value) -> Vec<ExtraMetric> {
+    row.as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(key, _)| {
+            !RESERVED_FIELDS.contains(&key.as_str())
+                && !["bytes_per_second", "items_per_second"].contains(&key.as_str())
+        })
+        .filter_map(|(key, value)| {
+            Some(ExtraMetric {
+                name: key.clone(),
+                value: value.as_f64()?,
+                unit: String::new(),
+            })
+        })
+        .collect()
+}
+
+/// The `context` block: host, CPUs and caches, library build type and start time.
+fn parse_context(context: &Value) -> MachineInfo {
+    let text = |key: &str| {
+        context
+            .get(key)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    let num_cpus = context.get("num_cpus").and_then(Value::as_u64).unwrap_or(0);
+    MachineInfo {
+        host: text("host_name"),
+        num_cpus,
+        mhz_per_cpu: context
+            .get("mhz_per_cpu")
+            .and_then(Value::as_f64)
+            .unwrap_or(0.0),
+        caches: context
+            .get("caches")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|cache| format_cache(cache, num_cpus))
+            .collect(),
+        build_type: text("library_build_type"),
+        date: text("date"),
+        executable: text("executable"),
+        load_avg: context
+            .get("load_avg")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_f64)
+            .collect(),
+        ..Default::default()
+    }
+}
+
+/// A cache the way Google Benchmark prints it, e.g. "L1 Data 48 KiB (x8)".
+fn format_cache(cache: &Value, num_cpus: u64) -> Option<String> {
+    let level = cache.get("level").and_then(Value::as_u64)?;
+    let kind = cache
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or("Unified");
+    let size = cache.get("size").and_then(Value::as_u64)?;
+    let sharing = cache
+        .get("num_sharing")
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    let text = format!("L{level} {kind} {} KiB", size / 1024);
+    if sharing > 0 && num_cpus > 0 {
+        Some(format!("{text} (x{})", num_cpus / sharing))
+    } else {
+        Some(text)
+    }
+}
+
+/// Runs that reported an error (`SkipWithError`), with the message they gave.
+pub fn diagnose(output: &str) -> Vec<Diagnostic> {
+    let Ok(value) = serde_json::from_str::<Value>(output.trim()) else {
+        return Vec::new();
+    };
+    value
+        .get("benchmarks")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|row| {
+            row.get("error_occurred")
+                .and_then(Value::as_bool)
+                .unwrap_or(false)
+        })
+        .filter_map(|row| {
+            let name = row.get("name").and_then(Value::as_str)?;
+            Some(Diagnostic::new(
+                match row.get("error_message").and_then(Value::as_str) {
+                    Some(message) => format!("benchmark `{name}` failed: {message}"),
+                    None => format!("benchmark `{name}` failed"),
+                },
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = include_str!("fixtures/google_benchmark.json");
+
+    fn assert_float_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-6, "Expected {a} to be approximately {b}");
+    }
+
+    #[test]
+    fn parse_runs_with_aggregates() {
+        let (_, benches) = parse(OUTPUT);
+        let names: Vec<_> = benches.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["BM_Encode/64", "BM_Encode/4096", "BM_Decode/real_time"]
+        );
+
+        let encode = &benches[0];
+        assert_eq!(encode.group_name(), "BM_Encode");
+        let mean = encode.mean.as_ref().unwrap();
+        assert_float_eq(mean.estimate, 0.000_413_029_6);
+        // mean ± 1.96 × stddev / √5
+        assert_float_eq(mean.lower_bound, 0.000_411_176_1);
+        assert_float_eq(mean.upper_bound, 0.000_414_883_1);
+        assert_eq!(encode.time, *mean);
+        assert_float_eq(encode.median.as_ref().unwrap().estimate, 0.000_412_782);
+        assert_float_eq(encode.std_dev.as_ref().unwrap().estimate, 0.000_002_114_5);
+
+        assert_eq!(encode.iteration_count.len(), 5);
+        assert_float_eq(encode.iteration_count[0], 1_696_245.0);
+        assert_float_eq(encode.measured_values[0], 411.545 * 1_696_245.0);
+
+        let throughput = encode.throughput.as_ref().unwrap();
+        assert_eq!(throughput.unit, "bytes");
+        assert_float_eq(throughput.per_iteration, 64.0);
+
+        assert_eq!(encode.extra_metrics.len(), 1);
+        let cpu_time = &encode.extra_metrics[0];
+        assert_eq!(cpu_time.name, "CPU time");
+        assert_eq!(cpu_time.unit, "ns");
+        assert_float_eq(cpu_time.value, 411.7905);
+    }
+
+    #[test]
+    fn parse_single_run_with_counters() {
+        let (_, benches) = parse(OUTPUT);
+        let decode = &benches[2];
+        // 708.214 us
+        assert_float_eq(decode.time.estimate, 0.708_214);
+        assert_float_eq(decode.time.lower_bound, decode.time.upper_bound);
+        assert!(decode.median.is_none());
+        assert!(decode.iteration_count.is_empty());
+        assert!(decode.throughput.is_none());
+        let metrics: Vec<_> = decode
+            .extra_metrics
+            .iter()
+            .map(|m| (m.name.as_str(), m.value, m.unit.as_str()))
+            .collect();
+        assert_eq!(
+            metrics,
+            vec![
+                ("CPU time", 702.9, "us"),
+                ("frames", 240.0, ""),
+                ("frames_per_second", 338_886.8, ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_repetitions_without_aggregates() {
+        let output = r#"{"benchmarks": [
+            {"name": "BM_Sort", "run_name": "BM_Sort", "run_type": "iteration", "iterations": 10, "real_time": 1.0, "time_unit": "ms"},
+            {"name": "BM_Sort", "run_name": "BM_Sort", "run_type": "iteration", "iterations": 10, "real_time": 3.0, "time_unit": "ms"},
+            {"name": "BM_Sort", "run_name": "BM_Sort", "run_type": "iteration", "iterations": 10, "real_time": 2.0, "time_unit": "ms"}
+        ]}"#;
+        let (machine, benches) = parse(output);
+        assert!(machine.is_none());
+        let sort = &benches[0];
+        assert_float_eq(sort.time.estimate, 2.0);
+        assert_float_eq(sort.median.as_ref().unwrap().estimate, 2.0);
+        assert_float_eq(sort.std_dev.as_ref().unwrap().estimate, 1.0);
+        assert_float_eq(sort.time.standard_error, 1.0 / 3f64.sqrt());
+    }
+
+    #[test]
+    fn diagnose_errored_runs() {
+        let warnings = diagnose(OUTPUT);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].message,
+            "benchmark `BM_Broken` failed: input file missing"
+        );
+        assert!(diagnose("{\"benchmarks\": []}").is_empty());
+    }
+
+    #[test]
+    fn parse_context_block() {
+        let (machine, _) = parse(OUTPUT);
+        let machine = machine.unwrap();
+        assert_eq!(machine.host, "ci-runner-12");
+        assert_eq!(machine.num_cpus, 16);
+        assert_float_eq(machine.mhz_per_cpu, 3600.0);
+        assert_eq!(machine.build_type, "release");
+        assert_eq!(machine.date, "2026-09-14T09:12:44+00:00");
+        assert_eq!(
+            machine.caches,
+            vec![
+                "L1 Data 48 KiB (x8)",
+                "L1 Instruction 32 KiB (x8)",
+                "L2 Unified 1280 KiB (x8)",
+                "L3 Unified 24576 KiB (x1)"
+            ]
+        );
+        assert_eq!(machine.load_avg, vec![0.42, 0.58, 0.61]);
+    }
+
+    #[test]
+    fn test_confidence() {
+        assert!((confidence(OUTPUT) - 1.0).abs() < 1e-9);
+        assert!(confidence(include_str!("fixtures/k6_summary_export.json")) < 0.5);
+        assert!(confidence("test bench_fib ... bench: 10 ns/iter (+/- 1)").abs() < 1e-9);
+    }
+}
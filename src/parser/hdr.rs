@@ -0,0 +1,636 @@
+use super::{
+    is_empty,
+    latency::Latency,
+    metrics::WrkMetrics,
+    percentile::{HistogramSummary, PercentileBucket},
+    Diagnostic,
+};
+use base64::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, io::Read};
+
+/// Cookies identifying `HdrHistogram`'s V2 encoding; the low nibble's `0x10`-`0xf0` bits
+/// carry the word size and are masked off before comparing.
+const V2_COOKIE_BASE: u32 = 0x1c84_9303;
+const V2_COMPRESSED_COOKIE_BASE: u32 = 0x1c84_9304;
+
+/// Percentiles stored in `latency_distribution`, matching wrk's Latency Distribution.
+const DISTRIBUTION: [(&str, f64); 4] = [("50%", 0.5), ("75%", 0.75), ("90%", 0.9), ("99%", 0.99)];
+
+/// Latency percentiles of one interval of an `HdrHistogram` interval log, in ms.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct LatencyInterval {
+    /// Seconds since the first interval of the log started
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub start: f64,
+    /// Interval length in seconds
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub length: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_u64")]
+    pub count: u64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub p50: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub p90: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub p99: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub max: f64,
+}
+
+/// Confidence (0.0–1.0) that `output` is a standalone `.hgrm` percentile distribution.
+pub fn hgrm_confidence(output: &str) -> f64 {
+    // the same spectrum embedded in wrk2's output belongs to the wrk2 parser
+    if output.contains("Detailed Percentile spectrum") {
+        return 0.0;
+    }
+    let signals = [
+        (
+            0.4,
+            output.lines().any(|l| {
+                l.contains("Value") && l.contains("Percentile") && l.contains("TotalCount")
+            }),
+        ),
+        (0.2, output.contains("#[Mean")),
+        (0.2, output.contains("#[Max")),
+        (0.2, output.contains("#[Buckets")),
+    ];
+    signals
+        .iter()
+        .filter(|(_, present)| *present)
+        .map(|(weight, _)| weight)
+        .sum()
+}
+
+/// Confidence (0.0–1.0) that `output` is an `HdrHistogram` interval log (`.hlog`).
+pub fn hlog_confidence(output: &str) -> f64 {
+    let signals = [
+        (0.4, output.contains("\"Interval_Compressed_Histogram\"")),
+        (0.2, output.contains("#[Histogram log format version")),
+        (0.2, output.contains("#[StartTime:")),
+        (
+            0.2,
+            output
+                .lines()
+                .any(|l| l.split(',').any(|field| field.starts_with("HISTF"))),
+        ),
+    ];
+    signals
+        .iter()
+        .filter(|(_, present)| *present)
+        .map(|(weight, _)| weight)
+        .sum()
+}
+
+/// Parses an `.hgrm` percentile distribution, as printed by `outputPercentileDistribution`
+/// in either its plain or `-csv` form. Values are expected in milliseconds. Data rows
+/// that can't be read are skipped and reported in `warnings`.
+pub fn parse_hgrm(output: &str, warnings: &mut Vec<Diagnostic>) -> Option<WrkMetrics> {
+    let lines = output.lines().map(str::trim).collect::<Vec<_>>();
+    let mut percentiles = lines
+        .iter()
+        .enumerate()
+        // skip the footer and the column header
+        .filter(|(_, l)| l.starts_with(|c: char| c.is_ascii_digit()))
+        .filter_map(|(number, l)| {
+            PercentileBucket::try_from(l.replace(',', " ").as_str())
+                .map_err(|err| {
+                    warnings.push(Diagnostic::at_line(
+                        number + 1,
+                        format!("percentile row skipped: {err}"),
+                    ));
+                })
+                .ok()
+        })
+        .collect::<Vec<_>>();
+    // the plain form leaves the 1/(1-Percentile) column off the final 100% row
+    for bucket in &mut percentiles {
+        if bucket.percentile >= 1.0 && bucket.inverse_percentile == 0.0 {
+            bucket.inverse_percentile = f64::INFINITY;
+        }
+    }
+    let last = percentiles.last()?.clone();
+    let numbered = lines
+        .iter()
+        .enumerate()
+        .map(|(number, l)| (number + 1, *l))
+        .collect::<Vec<_>>();
+    let histogram = HistogramSummary::parse(&numbered, warnings);
+
+    Some(WrkMetrics {
+        latency: Latency {
+            avg: histogram.mean,
+            stddev: histogram.std_deviation,
+            max: if histogram.max > 0.0 {
+                histogram.max
+            } else {
+                last.value
+            },
+            ..Default::default()
+        },
+        total_requests: histogram.total_count.max(last.total_count),
+        latency_distribution: distribution(|p| {
+            percentiles
+                .iter()
+                .find(|b| b.percentile >= p - 1e-9)
+                .map_or(0.0, |b| b.value)
+        }),
+        percentiles,
+        histogram,
+        ..Default::default()
+    })
+}
+
+/// One data line of an interval log, with its histogram still in recorded units.
+struct LogRow<'a> {
+    tag: Option<&'a str>,
+    start: f64,
+    length: f64,
+    /// `Interval_Max` column, scaled by the writer's unit ratio
+    max: f64,
+    /// (highest equivalent value, count) pairs in ascending order
+    counts: Vec<(u64, u64)>,
+}
+
+/// Parses an `HdrHistogram` interval log into per-interval latency and the percentile
+/// spectrum of every interval combined. Only intervals with the first row's tag are read.
+/// Rows that can't be read are skipped and reported in `warnings`.
+#[allow(clippy::cast_precision_loss)]
+pub fn parse_hlog(output: &str, warnings: &mut Vec<Diagnostic>) -> Option<WrkMetrics> {
+    let rows = output
+        .lines()
+        .map(str::trim)
+        .enumerate()
+        .filter(|(_, l)| !l.is_empty() && !l.starts_with('#') && !l.starts_with('"'))
+        .filter_map(|(number, l)| {
+            parse_log_row(l)
+                .map_err(|err| {
+                    warnings.push(Diagnostic::at_line(
+                        number + 1,
+                        format!("interval skipped: {err}"),
+                    ));
+                })
+                .ok()
+        })
+        .collect::<Vec<_>>();
+    let first = rows.first()?;
+    let rows = rows
+        .iter()
+        .filter(|r| r.tag == first.tag)
+        .collect::<Vec<_>>();
+    let ratio = unit_ratio(&rows).unwrap_or_else(|| {
+        warnings.push(Diagnostic::new(
+            "no interval has an `Interval_Max`; values are read as milliseconds",
+        ));
+        1.0
+    });
+
+    let mut samples = Vec::new();
+    let intervals = rows
+        .iter()
+        .map(|row| {
+            let interval = row
+                .counts
+                .iter()
+                .map(|&(value, count)| (value as f64 / ratio, count))
+                .collect::<Vec<_>>();
+            let count = interval.iter().map(|(_, c)| c).sum();
+            let at = |p| value_at(&interval, count, p).0;
+            let latency = LatencyInterval {
+                start: row.start - first.start,
+                length: row.length,
+                count,
+                p50: at(0.5),
+                p90: at(0.9),
+                p99: at(0.99),
+                max: interval.last().map_or(0.0, |(v, _)| *v),
+            };
+            samples.extend_from_slice(&row.counts);
+            latency
+        })
+        .collect::<Vec<_>>();
+    samples.sort_unstable();
+    // intervals share buckets; fold them so counts at a value are cumulated together
+    samples.dedup_by(|next, kept| {
+        let same = next.0 == kept.0;
+        if same {
+            kept.1 += next.1;
+        }
+        same
+    });
+    let samples = samples
+        .into_iter()
+        .map(|(value, count)| (value as f64 / ratio, count))
+        .collect::<Vec<_>>();
+
+    let total = samples.iter().map(|(_, c)| c).sum::<u64>();
+    let mean = samples.iter().map(|(v, c)| v * *c as f64).sum::<f64>() / total.max(1) as f64;
+    let variance = samples
+        .iter()
+        .map(|(v, c)| (v - mean).powi(2) * *c as f64)
+        .sum::<f64>()
+        / total.max(1) as f64;
+    let max = samples.last().map_or(0.0, |(v, _)| *v);
+    let duration = intervals
+        .last()
+        .map_or(0.0, |last| last.start + last.length);
+
+    Some(WrkMetrics {
+        latency: Latency {
+            avg: mean,
+            stddev: variance.sqrt(),
+            max,
+            ..Default::default()
+        },
+        total_requests: total,
+        duration,
+        requests_per_sec: if duration > 0.0 {
+            total as f64 / duration
+        } else {
+            0.0
+        },
+        latency_distribution: distribution(|p| value_at(&samples, total, p).0),
+        percentiles: spectrum(&samples, total),
+        histogram: HistogramSummary {
+            mean,
+            std_deviation: variance.sqrt(),
+            max,
+            total_count: total,
+            ..Default::default()
+        },
+        intervals,
+        ..Default::default()
+    })
+}
+
+/// `[Tag=<tag>,]<start>,<length>,<max>,<base64 histogram>`
+fn parse_log_row(line: &str) -> Result<LogRow<'_>, String> {
+    let (tag, rest) = match line.strip_prefix("Tag=") {
+        Some(tagged) => {
+            let (tag, rest) = tagged.split_once(',').ok_or("tag has no interval")?;
+            (Some(tag), rest)
+        }
+        None => (None, line),
+    };
+    let mut fields = rest.splitn(4, ',');
+    let mut number = |name: &str| {
+        fields
+            .next()
+            .and_then(|f| f.trim().parse().ok())
+            .ok_or(format!("can't read the interval's {name}"))
+    };
+    Ok(LogRow {
+        tag,
+        start: number("start")?,
+        length: number("length")?,
+        max: number("max")?,
+        counts: fields
+            .next()
+            .and_then(decode_histogram)
+            .ok_or("histogram isn't a compressed V2 histogram")?,
+    })
+}
+
+/// Divisor that turns recorded values into milliseconds. Log writers divide
+/// `Interval_Max` by a unit ratio so that it reads in milliseconds, so comparing it
+/// with the decoded max tells us whether values were recorded in ms, µs, ns or ps.
+#[allow(clippy::cast_precision_loss)]
+fn unit_ratio(rows: &[&LogRow]) -> Option<f64> {
+    rows.iter()
+        .filter(|r| r.max > 0.0)
+        .find_map(|r| Some(r.counts.last()?.0 as f64 / r.max))
+        .and_then(|ratio| {
+            [1.0, 1e3, 1e6, 1e9]
+                .into_iter()
+                .min_by(|a, b| (ratio / a).ln().abs().total_cmp(&(ratio / b).ln().abs()))
+        })
+}
+
+/// Decodes a base64, zlib-compressed V2 histogram into (value, count) pairs.
+fn decode_histogram(encoded: &str) -> Option<Vec<(u64, u64)>> {
+    let bytes = BASE64_STANDARD.decode(encoded.trim()).ok()?;
+    let (cookie, rest) = read_u32(&bytes)?;
+    if cookie & !0xf0 != V2_COMPRESSED_COOKIE_BASE {
+        return None;
+    }
+    let (length, rest) = read_u32(rest)?;
+    let mut raw = Vec::new();
+    flate2::read::ZlibDecoder::new(rest.get(..usize::try_from(length).ok()?)?)
+        .read_to_end(&mut raw)
+        .ok()?;
+
+    let (cookie, rest) = read_u32(&raw)?;
+    if cookie & !0xf0 != V2_COOKIE_BASE {
+        return None;
+    }
+    let (payload_length, rest) = read_u32(rest)?;
+    // the normalizing index offset is only non-zero for shifted in-memory histograms
+    let (_, rest) = read_u32(rest)?;
+    let (digits, rest) = read_u32(rest)?;
+    let lowest = u64::from_be_bytes(rest.get(..8)?.try_into().ok()?);
+    // skip the highest trackable value and the integer-to-double conversion ratio
+    let payload = rest.get(24..24 + usize::try_from(payload_length).ok()?)?;
+    let layout = Layout::new(digits, lowest)?;
+
+    let mut counts = Vec::new();
+    let mut index = 0usize;
+    let mut pos = 0;
+    while pos < payload.len() {
+        let count = read_zig_zag(payload, &mut pos)?;
+        if count < 0 {
+            // a negative count is a run of empty buckets
+            index = index.checked_add(usize::try_from(count.unsigned_abs()).ok()?)?;
+        } else {
+            if count > 0 {
+                counts.push((
+                    layout.highest_equivalent_value(index)?,
+                    count.unsigned_abs(),
+                ));
+            }
+            index += 1;
+        }
+    }
+    Some(counts)
+}
+
+fn read_u32(bytes: &[u8]) -> Option<(u32, &[u8])> {
+    let (head, rest) = bytes.split_first_chunk::<4>()?;
+    Some((u32::from_be_bytes(*head), rest))
+}
+
+/// Reads one `ZigZag` LEB128 count: seven bits per byte, with a ninth byte that
+/// carries a full eight bits.
+#[allow(clippy::cast_possible_wrap)]
+fn read_zig_zag(bytes: &[u8], pos: &mut usize) -> Option<i64> {
+    let mut value = 0u64;
+    for i in 0..9 {
+        let byte = *bytes.get(*pos)?;
+        *pos += 1;
+        if i == 8 {
+            value |= u64::from(byte) << 56;
+            break;
+        }
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+    Some((value >> 1) as i64 ^ -((value & 1) as i64))
+}
+
+/// Bucket layout of an `HdrHistogram`, which maps a counts index back to its value.
+struct Layout {
+    unit_magnitude: u32,
+    sub_bucket_half_count_magnitude: u32,
+}
+
+impl Layout {
+    fn new(significant_digits: u32, lowest_discernible_value: u64) -> Option<Self> {
+        if significant_digits > 5 {
+            return None;
+        }
+        let largest_single_unit = 2 * 10u64.pow(significant_digits);
+        let sub_bucket_count_magnitude = largest_single_unit.next_power_of_two().trailing_zeros();
+        Some(Self {
+            unit_magnitude: lowest_discernible_value.max(1).ilog2(),
+            sub_bucket_half_count_magnitude: sub_bucket_count_magnitude.max(1) - 1,
+        })
+    }
+
+    /// Largest value counted at `index`, which is what `HdrHistogram` reports for it.
+    fn highest_equivalent_value(&self, index: usize) -> Option<u64> {
+        let half_count = 1 << self.sub_bucket_half_count_magnitude;
+        // the first bucket spans the whole lower half of the sub-buckets
+        let (bucket, sub_bucket) = match index >> self.sub_bucket_half_count_magnitude {
+            0 => (0, index),
+            n => (n - 1, (index & (half_count - 1)) + half_count),
+        };
+        let shift = u32::try_from(bucket).ok()? + self.unit_magnitude;
+        let width = 1u64.checked_shl(shift)?;
+        let value = u64::try_from(sub_bucket).ok()?.checked_mul(width)?;
+        value.checked_add(width - 1)
+    }
+}
+
+/// Value at `percentile` of ascending (value, count) pairs, and how many samples
+/// fall at or below it.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn value_at(samples: &[(f64, u64)], total: u64, percentile: f64) -> (f64, u64) {
+    let target = ((percentile * total as f64).round() as u64).max(1);
+    let mut cumulative = 0;
+    for &(value, count) in samples {
+        cumulative += count;
+        if cumulative >= target {
+            return (value, cumulative);
+        }
+    }
+    (samples.last().map_or(0.0, |(v, _)| *v), cumulative)
+}
+
+/// Percentile rows stepped like `HdrHistogram`'s own output: five ticks per halving
+/// of the distance to 100%, closed by a final 100% row at the max.
+fn spectrum(samples: &[(f64, u64)], total: u64) -> Vec<PercentileBucket> {
+    let Some(&(max, _)) = samples.last() else {
+        return Vec::new();
+    };
+    let mut buckets = Vec::new();
+    let mut percentile = 0.0_f64;
+    loop {
+        let (value, total_count) = value_at(samples, total, percentile);
+        if total_count >= total {
+            break;
+        }
+        let inverse_percentile = 1.0 / (1.0 - percentile);
+        buckets.push(PercentileBucket {
+            value,
+            percentile,
+            total_count,
+            inverse_percentile,
+        });
+        let half_distances = inverse_percentile.log2().floor() + 1.0;
+        percentile += 1.0 / (5.0 * 2f64.powf(half_distances));
+    }
+    buckets.push(PercentileBucket {
+        value: max,
+        percentile: 1.0,
+        total_count: total,
+        inverse_percentile: f64::INFINITY,
+    });
+    buckets
+}
+
+fn distribution(value_at: impl Fn(f64) -> f64) -> HashMap<String, f64> {
+    DISTRIBUTION
+        .iter()
+        .map(|(key, p)| ((*key).to_string(), value_at(*p)))
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn assert_float_eq(a: f64, b: f64) {
+        const EPSILON: f64 = 1e-6;
+        assert!(
+            (a - b).abs() < EPSILON,
+            "Expected {a} to be approximately equal to {b}"
+        );
+    }
+
+    const HGRM: &str = include_str!("fixtures/hdr_latency.hgrm");
+    const HLOG: &str = include_str!("fixtures/hdr_latency.hlog");
+
+    #[test]
+    fn parse_hgrm_spectrum() {
+        let metrics = parse_hgrm(HGRM, &mut Vec::new()).unwrap();
+        assert_eq!(metrics.percentiles.len(), 75);
+        assert_float_eq(metrics.percentiles[5].value, 2.831);
+        assert_float_eq(metrics.percentiles[5].percentile, 0.5);
+        assert_eq!(metrics.percentiles[5].total_count, 6002);
+
+        let last = metrics.percentiles.last().unwrap();
+        assert_float_eq(last.value, 67.391);
+        assert!(last.inverse_percentile.is_infinite());
+
+        assert_eq!(metrics.total_requests, 12000);
+        assert_float_eq(metrics.latency.avg, 4.266);
+        assert_float_eq(metrics.latency.max, 67.391);
+        assert_eq!(metrics.histogram.sub_buckets, 2048);
+        assert_float_eq(metrics.latency_distribution["50%"], 2.831);
+    }
+
+    #[test]
+    fn parse_hgrm_csv() {
+        let csv = "Value,Percentile,TotalCount,1/(1-Percentile)\n\
+                   0.386,0.000000000000,1,1.00\n\
+                   2.831,0.500000000000,6002,2.00\n\
+                   67.391,1.000000000000,12000,Infinity\n";
+        let metrics = parse_hgrm(csv, &mut Vec::new()).unwrap();
+        assert_eq!(metrics.percentiles.len(), 3);
+        assert!(metrics.percentiles[2].inverse_percentile.is_infinite());
+        assert_eq!(metrics.total_requests, 12000);
+        assert_float_eq(metrics.latency.max, 67.391);
+    }
+
+    #[test]
+    fn parse_hlog_intervals() {
+        let metrics = parse_hlog(HLOG, &mut Vec::new()).unwrap();
+        assert_eq!(metrics.intervals.len(), 6);
+
+        let first = &metrics.intervals[0];
+        assert_float_eq(first.start, 0.0);
+        assert_float_eq(first.length, 1.0);
+        assert_eq!(first.count, 2000);
+        assert_float_eq(first.p50, 2.507);
+        assert_float_eq(first.p90, 4.699);
+        assert_float_eq(first.p99, 7.807);
+        assert_float_eq(first.max, 13.983);
+
+        // the slow fourth second stands out
+        let spike = &metrics.intervals[3];
+        assert_float_eq(spike.start, 3.0);
+        assert_float_eq(spike.p99, 33.983);
+        assert_float_eq(spike.max, 67.391);
+    }
+
+    #[test]
+    fn parse_hlog_combined_spectrum_matches_hgrm() {
+        let log = parse_hlog(HLOG, &mut Vec::new()).unwrap();
+        let hgrm = parse_hgrm(HGRM, &mut Vec::new()).unwrap();
+
+        assert_eq!(log.total_requests, 12000);
+        assert_float_eq(log.duration, 6.0);
+        assert_float_eq(log.requests_per_sec, 2000.0);
+        assert_eq!(log.percentiles.len(), hgrm.percentiles.len());
+        for (a, b) in log.percentiles.iter().zip(&hgrm.percentiles) {
+            assert_float_eq(a.value, b.value);
+            assert_float_eq(a.percentile, b.percentile);
+            assert_eq!(a.total_count, b.total_count);
+        }
+        assert!((log.latency.avg - hgrm.latency.avg).abs() < 1e-3);
+        assert_float_eq(log.latency_distribution["99%"], 22.639);
+    }
+
+    #[test]
+    fn parse_warns_on_skipped_rows() {
+        let mut warnings = Vec::new();
+        parse_hgrm(HGRM, &mut warnings).unwrap();
+        parse_hlog(HLOG, &mut warnings).unwrap();
+        assert!(warnings.is_empty());
+
+        let hgrm = HGRM.replacen("1.377 0.100000000000", "1.377 0.1x", 1);
+        assert_eq!(
+            parse_hgrm(&hgrm, &mut warnings).unwrap().percentiles.len(),
+            74
+        );
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].line, Some(4));
+
+        let data = HLOG
+            .lines()
+            .position(|l| l.starts_with(|c: char| c.is_ascii_digit()))
+            .unwrap();
+        let hlog = HLOG
+            .lines()
+            .enumerate()
+            .map(|(i, l)| {
+                if i == data {
+                    "0.0,1.0,13.983,HISTFAAAnotbase64"
+                } else {
+                    l
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut warnings = Vec::new();
+        assert_eq!(parse_hlog(&hlog, &mut warnings).unwrap().intervals.len(), 5);
+        assert_eq!(
+            warnings[0].to_string(),
+            format!(
+                "line {}: interval skipped: histogram isn't a compressed V2 histogram",
+                data + 1
+            )
+        );
+    }
+
+    #[test]
+    fn decode_rejects_other_encodings() {
+        assert!(decode_histogram("not base64!").is_none());
+        // V1 compressed cookie
+        let v1 = BASE64_STANDARD.encode(0x1c84_9302u32.to_be_bytes());
+        assert!(decode_histogram(&v1).is_none());
+    }
+
+    #[test]
+    fn layout_values() {
+        let layout = Layout::new(3, 1).unwrap();
+        assert_eq!(layout.highest_equivalent_value(0), Some(0));
+        assert_eq!(layout.highest_equivalent_value(2047), Some(2047));
+        // second bucket doubles the width
+        assert_eq!(layout.highest_equivalent_value(2048), Some(2049));
+        assert!(Layout::new(6, 1).is_none());
+    }
+
+    #[test]
+    fn zig_zag_runs() {
+        let mut pos = 0;
+        assert_eq!(read_zig_zag(&[0x03], &mut pos), Some(-2));
+        let mut pos = 0;
+        assert_eq!(read_zig_zag(&[0xac, 0x02], &mut pos), Some(150));
+        assert_eq!(pos, 2);
+    }
+
+    #[test]
+    fn confidence_prefers_hdr() {
+        assert!((hgrm_confidence(HGRM) - 1.0).abs() < 1e-9);
+        assert!((hlog_confidence(HLOG) - 1.0).abs() < 1e-9);
+        assert!(hgrm_confidence(include_str!("fixtures/wrk2_full.txt")).abs() < 1e-9);
+        assert!(hlog_confidence(HGRM).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,337 @@
+use super::{
+    calibration,
+    errors::ErrorCounts,
+    latency::Latency,
+    metrics::{PhaseTiming, WrkMetrics},
+    units, Diagnostic,
+};
+use std::collections::{BTreeMap, HashMap};
+
+/// parses a line of the Details section from the hey output.
+/// `DNS+dialup:    0.0001 secs, 0.0012 secs, 0.2051 secs`
+impl TryFrom<&str> for PhaseTiming {
+    type Error = String;
+    fn try_from(line: &str) -> Result<Self, Self::Error> {
+        let (name, values) = line.trim().split_once(':').ok_or("Not a phase line")?;
+        let values = values
+            .split(',')
+            .map(|v| parse_secs(v).ok_or("Invalid phase timing"))
+            .collect::<Result<Vec<_>, _>>()?;
+        let [avg, min, max] = values[..] else {
+            return Err("Expected average, fastest and slowest".to_string());
+        };
+        Ok(Self {
+            name: name.trim().to_string(),
+            avg,
+            min,
+            max,
+        })
+    }
+}
+
+/// Confidence (0.0–1.0) that `output` came from hey.
+pub fn confidence(output: &str) -> f64 {
+    let signals = [
+        (
+            0.4,
+            output.contains("Summary:") && output.contains("Requests/sec:"),
+        ),
+        (
+            0.2,
+            output.contains("Latency distribution:") && output.contains("% in "),
+        ),
+        (0.2, output.contains("Details (average, fastest, slowest):")),
+        (0.2, output.contains("Status code distribution:")),
+    ];
+    signals
+        .iter()
+        .filter(|(_, present)| *present)
+        .map(|(weight, _)| weight)
+        .sum()
+}
+
+/// Parses one or more hey runs. Each run starts at its `Summary:` line; a pasted
+/// `hey` command line before it supplies the endpoint and concurrency. Values that
+/// can't be read are reported in `warnings`.
+pub fn parse(output: &str, warnings: &mut Vec<Diagnostic>) -> Vec<WrkMetrics> {
+    let mut runs: Vec<Vec<(usize, &str)>> = Vec::new();
+    let mut command = None;
+
+    for (number, line) in output.lines().map(str::trim).enumerate() {
+        let line = (number + 1, line);
+        if is_hey_command(line.1) {
+            command = Some(line);
+        } else if line.1 == "Summary:" {
+            runs.push(command.take().into_iter().collect());
+        }
+        if let Some(run) = runs.last_mut() {
+            run.push(line);
+        }
+    }
+
+    runs.iter().map(|run| parse_run(run, warnings)).collect()
+}
+
+fn parse_run(lines: &[(usize, &str)], warnings: &mut Vec<Diagnostic>) -> WrkMetrics {
+    let mut secs_of = |key: &str| summary_value(lines, key, parse_secs, warnings);
+    let duration = secs_of("Total:") / 1000.0;
+    let average = secs_of("Average:");
+    let slowest = secs_of("Slowest:");
+    let requests_per_sec = summary_value(lines, "Requests/sec:", |v| v.parse().ok(), warnings);
+    let total_bytes = lines
+        .iter()
+        .find_map(|(_, l)| l.strip_prefix("Total data:"))
+        .and_then(|v| v.trim_end_matches("bytes").trim().parse::<f64>().ok())
+        .unwrap_or(0.0);
+
+    let status_codes = section(lines, "Status code distribution:")
+        .filter_map(|(_, l)| {
+            let (code, count) = l.strip_prefix('[')?.split_once(']')?;
+            let count = count.split_whitespace().next()?.parse().ok()?;
+            Some((code.parse().ok()?, count))
+        })
+        .collect::<BTreeMap<u16, u64>>();
+
+    let mut errors = ErrorCounts {
+        non_2xx_3xx: status_codes
+            .iter()
+            .filter(|(code, _)| !(200..400).contains(*code))
+            .map(|(_, count)| count)
+            .sum(),
+        ..Default::default()
+    };
+    for (number, line) in section(lines, "Error distribution:") {
+        let Some((count, message)) = line.strip_prefix('[').and_then(|l| l.split_once(']')) else {
+            continue;
+        };
+        match count.parse() {
+            Ok(count) => errors.add_message(message, count),
+            Err(_) => warnings.push(Diagnostic::at_line(
+                number,
+                format!(
+                    "error `{}` skipped: `{count}` is not a count",
+                    message.trim()
+                ),
+            )),
+        }
+    }
+
+    let latency_distribution = section(lines, "Latency distribution:")
+        .filter_map(|(number, l)| {
+            let (percent, value) = l.split_once(" in ")?;
+            let value = parse_secs(value).or_else(|| {
+                warnings.push(Diagnostic::at_line(
+                    number,
+                    format!(
+                        "latency distribution row skipped: `{}` is not in secs",
+                        value.trim()
+                    ),
+                ));
+                None
+            })?;
+            Some((percent.trim().to_string(), value))
+        })
+        .collect::<HashMap<_, _>>();
+
+    let phases = section(lines, "Details (average, fastest, slowest):")
+        .filter_map(|(number, l)| {
+            PhaseTiming::try_from(l)
+                .map_err(|err| {
+                    warnings.push(Diagnostic::at_line(
+                        number,
+                        format!("request phase skipped: {err}"),
+                    ));
+                })
+                .ok()
+        })
+        .collect();
+
+    let command = lines.first().map(|(_, l)| *l).filter(|l| is_hey_command(l));
+
+    WrkMetrics {
+        endpoint: command
+            .and_then(|c| c.split_whitespace().rfind(|t| t.contains("://")))
+            .unwrap_or_default()
+            .to_string(),
+        connections: command
+            .and_then(|c| command_option(c, "-c"))
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0),
+        latency: Latency {
+            avg: average,
+            max: slowest,
+            ..Default::default()
+        },
+        total_requests: status_codes.values().sum::<u64>() + errors.socket_errors(),
+        duration,
+        requests_per_sec,
+        transfer_per_sec: if duration > 0.0 {
+            units::format_bytes(total_bytes / duration)
+        } else {
+            String::new()
+        },
+        latency_distribution,
+        errors,
+        status_codes,
+        phases,
+        ..Default::default()
+    }
+}
+
+/// The value after `key` in the summary, or 0 with a warning when it is missing or
+/// can't be read. A missing value is reported against the run's `Summary:` line.
+fn summary_value(
+    lines: &[(usize, &str)],
+    key: &str,
+    parse: impl Fn(&str) -> Option<f64>,
+    warnings: &mut Vec<Diagnostic>,
+) -> f64 {
+    let Some((number, value)) = lines
+        .iter()
+        .find_map(|(n, l)| Some((*n, l.strip_prefix(key)?.trim())))
+    else {
+        let summary = lines
+            .iter()
+            .find(|(_, l)| *l == "Summary:")
+            .map(|(n, _)| *n);
+        let message = format!("run has no `{key}` line");
+        warnings.push(match summary {
+            Some(number) => Diagnostic::at_line(number, message),
+            None => Diagnostic::new(message),
+        });
+        return 0.0;
+    };
+    parse(value).unwrap_or_else(|| {
+        warnings.push(Diagnostic::at_line(
+            number,
+            format!("can't read `{key}` value `{value}`"),
+        ));
+        0.0
+    })
+}
+
+/// Lines following `header` up to the next blank line.
+fn section<'a>(
+    lines: &'a [(usize, &'a str)],
+    header: &'a str,
+) -> impl Iterator<Item = (usize, &'a str)> {
+    lines
+        .iter()
+        .skip_while(move |(_, l)| *l != header)
+        .skip(1)
+        .take_while(|(_, l)| !l.is_empty())
+        .copied()
+}
+
+/// hey prints every duration as `0.0244 secs`; returns milliseconds.
+fn parse_secs(value: &str) -> Option<f64> {
+    let secs = value
+        .trim()
+        .trim_end_matches("secs")
+        .trim()
+        .parse::<f64>()
+        .ok()?;
+    Some(secs * 1000.0)
+}
+
+fn is_hey_command(line: &str) -> bool {
+    calibration::is_command(line, &["hey"])
+}
+
+fn command_option<'a>(command: &'a str, flag: &str) -> Option<&'a str> {
+    let mut tokens = command.split_whitespace();
+    tokens.find(|t| *t == flag)?;
+    tokens.next()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn assert_float_eq(a: f64, b: f64) {
+        const EPSILON: f64 = 1e-6;
+        assert!(
+            (a - b).abs() < EPSILON,
+            "Expected {a} to be approximately equal to {b}"
+        );
+    }
+
+    const HEY_OUTPUT: &str = include_str!("fixtures/hey_basic.txt");
+
+    #[test]
+    fn parse_phase_line() {
+        let phase =
+            PhaseTiming::try_from("DNS+dialup:\t0.0001 secs, 0.0012 secs, 0.2051 secs").unwrap();
+        assert_eq!(phase.name, "DNS+dialup");
+        assert_float_eq(phase.avg, 0.1);
+        assert_float_eq(phase.min, 1.2);
+        assert_float_eq(phase.max, 205.1);
+        assert!(PhaseTiming::try_from("resp wait:\t0.0242 secs").is_err());
+    }
+
+    #[test]
+    fn parse_hey_summary() {
+        let runs = parse(HEY_OUTPUT, &mut Vec::new());
+        assert_eq!(runs.len(), 1);
+        let metrics = &runs[0];
+
+        assert_eq!(metrics.endpoint, "http://localhost:8080/api/users");
+        assert_eq!(metrics.connections, 50);
+        assert_float_eq(metrics.duration, 10.0234);
+        assert_float_eq(metrics.requests_per_sec, 2038.4529);
+        assert_float_eq(metrics.latency.avg, 24.4);
+        assert_float_eq(metrics.latency.max, 205.1);
+        assert_eq!(metrics.transfer_per_sec, "218.24KB");
+        assert_eq!(metrics.total_requests, 20443);
+    }
+
+    #[test]
+    fn parse_hey_distribution_and_details() {
+        let metrics = &parse(HEY_OUTPUT, &mut Vec::new())[0];
+        assert_eq!(metrics.latency_distribution.len(), 7);
+        assert_float_eq(metrics.latency_distribution["99%"], 71.3);
+
+        assert_eq!(metrics.phases.len(), 5);
+        assert_eq!(metrics.phases[3].name, "resp wait");
+        assert_float_eq(metrics.phases[3].avg, 24.2);
+    }
+
+    #[test]
+    fn parse_hey_status_codes_and_errors() {
+        let metrics = &parse(HEY_OUTPUT, &mut Vec::new())[0];
+        assert_eq!(metrics.status_codes.get(&200), Some(&20000));
+        assert_eq!(metrics.status_codes.get(&503), Some(&431));
+        assert_eq!(metrics.errors.non_2xx_3xx, 431);
+        assert_eq!(metrics.errors.connect, 12);
+        assert_eq!(metrics.errors.timeout, 0);
+    }
+
+    #[test]
+    fn parse_hey_warnings() {
+        let mut warnings = Vec::new();
+        parse(HEY_OUTPUT, &mut warnings);
+        assert!(warnings.is_empty());
+
+        let broken = HEY_OUTPUT
+            .replace("Slowest:", "Slowest :")
+            .replacen("secs", "ms", 1);
+        let metrics = &parse(&broken, &mut warnings)[0];
+        let rendered: Vec<_> = warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "line 4: can't read `Total:` value `10.0234 ms`",
+                "line 3: run has no `Slowest:` line",
+            ]
+        );
+        assert_float_eq(metrics.duration, 0.0);
+        assert_float_eq(metrics.latency.max, 0.0);
+    }
+
+    #[test]
+    fn confidence_prefers_hey() {
+        assert!((confidence(HEY_OUTPUT) - 1.0).abs() < 1e-9);
+        assert!(confidence(include_str!("fixtures/wrk1_basic.txt")).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,232 @@
+//! hyperfine results written with `--export-json`. Each command becomes a
+//! [`CriterionMetrics`] with one sample per run, so it shares the Criterion dashboard.
+
+use super::criterion::{
+    ConfidenceInterval, CriterionMetrics, ExtraMetric, Outliers, TimeUnit, TukeyFences,
+};
+use super::Diagnostic;
+use serde_json::Value;
+
+/// Group shared by every command, so they are compared in one section
+pub const GROUP: &str = "hyperfine";
+
+/// z-score for a 95% confidence interval of the mean
+const Z_95: f64 = 1.96;
+const S_TO_MS: f64 = 1_000.0;
+const S_TO_NS: f64 = 1_000_000_000.0;
+
+/// Confidence (0.0–1.0) that `output` is hyperfine JSON.
+pub fn confidence(output: &str) -> f64 {
+    let trimmed = output.trim();
+    if !trimmed.starts_with('{') {
+        return 0.0;
+    }
+    let signals = [
+        (0.4, trimmed.contains("\"results\"")),
+        (0.2, trimmed.contains("\"command\"")),
+        (0.2, trimmed.contains("\"exit_codes\"")),
+        (0.2, trimmed.contains("\"times\"")),
+    ];
+    signals
+        .iter()
+        .filter(|(_, present)| *present)
+        .map(|(weight, _)| weight)
+        .sum()
+}
+
+/// Parses every entry of `results`, in the order the commands were benchmarked.
+pub fn parse(output: &str) -> Vec<CriterionMetrics> {
+    let Ok(value) = serde_json::from_str::<Value>(output.trim()) else {
+        return Vec::new();
+    };
+    value
+        .get("results")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(parse_result)
+        .collect()
+}
+
+/// One command. Times are in seconds; each run is a sample of a single iteration.
+#[allow(clippy::cast_precision_loss)]
+fn parse_result(result: &Value) -> Option<CriterionMetrics> {
+    let command = result.get("command").and_then(Value::as_str)?;
+    let seconds = |key: &str| result.get(key).and_then(Value::as_f64);
+    let times: Vec<f64> = result
+        .get("times")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_f64)
+        .collect();
+
+    let mean = seconds("mean")?;
+    let std_dev = seconds("stddev");
+    let time = match std_dev {
+        Some(sd) if times.len() > 1 => {
+            let standard_error = sd / (times.len() as f64).sqrt();
+            ConfidenceInterval {
+                lower_bound: (mean - Z_95 * standard_error).max(0.0) * S_TO_MS,
+                estimate: mean * S_TO_MS,
+                upper_bound: (mean + Z_95 * standard_error) * S_TO_MS,
+                unit: TimeUnit::Milliseconds,
+                standard_error: standard_error * S_TO_MS,
+                ..Default::default()
+            }
+        }
+        _ => point(mean),
+    };
+
+    let measured_values: Vec<f64> = times.iter().map(|t| t * S_TO_NS).collect();
+    let outliers = TukeyFences::from_samples(&measured_values)
+        .map(|fences| Outliers::from_samples(fences, &measured_values));
+    // CPU time split and the fastest/slowest run, in milliseconds like the estimates
+    let extra_metrics = [
+        ("user", "User time"),
+        ("system", "System time"),
+        ("min", "Min"),
+        ("max", "Max"),
+    ]
+    .into_iter()
+    .filter_map(|(key, name)| {
+        Some(ExtraMetric {
+            name: name.to_string(),
+            value: seconds(key)? * S_TO_MS,
+            unit: "ms".to_string(),
+        })
+    })
+    .collect();
+    Some(CriterionMetrics {
+        name: command.to_string(),
+        group: Some(GROUP.to_string()),
+        time: time.clone(),
+        mean: Some(time),
+        median: seconds("median").map(point),
+        std_dev: std_dev.map(point),
+        extra_metrics,
+        outliers,
+        iteration_count: vec![1.0; measured_values.len()],
+        measured_values,
+        ..Default::default()
+    })
+}
+
+/// A point estimate in seconds, as an interval in milliseconds.
+fn point(seconds: f64) -> ConfidenceInterval {
+    ConfidenceInterval {
+        lower_bound: seconds * S_TO_MS,
+        estimate: seconds * S_TO_MS,
+        upper_bound: seconds * S_TO_MS,
+        unit: TimeUnit::Milliseconds,
+        ..Default::default()
+    }
+}
+
+/// Commands that exited with a non-zero status in some runs, which hyperfine only
+/// allows with `--ignore-failure`; their timings may not measure the intended work.
+pub fn diagnose(output: &str) -> Vec<Diagnostic> {
+    let Ok(value) = serde_json::from_str::<Value>(output.trim()) else {
+        return Vec::new();
+    };
+    value
+        .get("results")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|result| {
+            let command = result.get("command").and_then(Value::as_str)?;
+            let codes = result.get("exit_codes").and_then(Value::as_array)?;
+            let failed = codes
+                .iter()
+                .filter(|code| code.as_i64().is_some_and(|c| c != 0))
+                .count();
+            (failed > 0).then(|| {
+                Diagnostic::new(format!(
+                    "command `{command}` exited with a non-zero status in {failed} of {} runs",
+                    codes.len()
+                ))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = include_str!("fixtures/hyperfine.json");
+
+    fn assert_float_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-6, "Expected {a} to be approximately {b}");
+    }
+
+    #[test]
+    fn parse_commands() {
+        let results = parse(OUTPUT);
+        let names: Vec<_> = results.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "./target/release/grep-rs pattern corpus.txt",
+                "grep pattern corpus.txt",
+                "rg pattern corpus.txt"
+            ]
+        );
+        assert!(results.iter().all(|b| b.group_name() == GROUP));
+
+        let grep_rs = &results[0];
+        assert_float_eq(grep_rs.time.estimate, 103.5);
+        // mean ± 1.96 × stddev / √10
+        assert_float_eq(grep_rs.time.lower_bound, 101.987_424);
+        assert_float_eq(grep_rs.median.as_ref().unwrap().estimate, 102.75);
+        assert_float_eq(grep_rs.std_dev.as_ref().unwrap().estimate, 2.440_401);
+
+        let extra: Vec<_> = grep_rs
+            .extra_metrics
+            .iter()
+            .map(|m| (m.name.as_str(), m.unit.as_str()))
+            .collect();
+        assert_eq!(
+            extra,
+            vec![
+                ("User time", "ms"),
+                ("System time", "ms"),
+                ("Min", "ms"),
+                ("Max", "ms")
+            ]
+        );
+        assert_float_eq(grep_rs.extra_metrics[0].value, 82.8);
+        assert_float_eq(grep_rs.extra_metrics[1].value, 15.525);
+        assert_float_eq(grep_rs.extra_metrics[3].value, 110.2);
+    }
+
+    #[test]
+    fn parse_times_as_samples() {
+        let grep_rs = &parse(OUTPUT)[0];
+        assert_eq!(grep_rs.iteration_count, vec![1.0; 10]);
+        assert_float_eq(grep_rs.measured_values[0], 102_100_000.0);
+        // the 110.2 ms run is well above the others
+        let outliers = grep_rs.outliers.as_ref().unwrap();
+        assert_eq!(outliers.total_measurements, 10);
+        assert_eq!(outliers.outlier_count, 1);
+    }
+
+    #[test]
+    fn diagnose_failed_runs() {
+        let warnings = diagnose(OUTPUT);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].message,
+            "command `rg pattern corpus.txt` exited with a non-zero status in 1 of 10 runs"
+        );
+    }
+
+    #[test]
+    fn test_confidence() {
+        assert!((confidence(OUTPUT) - 1.0).abs() < 1e-9);
+        assert!(confidence(include_str!("fixtures/google_benchmark.json")) < 0.5);
+        assert!(confidence("Benchmark 1: sleep 0.1").abs() < 1e-9);
+    }
+}
@@ -10,6 +10,12 @@ pub fn check_f64(value: &f64) -> bool {
     *value == 0.0
 }
 
+// serde's `skip_serializing_if` requires `fn(&T) -> bool` signature
+#[allow(clippy::trivially_copy_pass_by_ref)]
+pub fn check_bool(value: &bool) -> bool {
+    !*value
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -36,4 +42,10 @@ mod tests {
         assert!(!check_f64(&-1.0));
         assert!(!check_f64(&f64::MIN_POSITIVE));
     }
+
+    #[test]
+    fn check_bool_false_is_empty() {
+        assert!(check_bool(&false));
+        assert!(!check_bool(&true));
+    }
 }
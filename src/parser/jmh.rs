@@ -0,0 +1,297 @@
+//! JMH results written with `-rf json`. Each entry becomes a [`CriterionMetrics`] named
+//! `method/param=value` within a group per benchmark class, so parameterized benchmarks
+//! are plotted against their numeric params like Criterion inputs.
+
+use super::bootstrap;
+use super::criterion::{
+    ConfidenceInterval, CriterionMetrics, ExtraMetric, Outliers, RateInterval, TimeUnit,
+    TukeyFences,
+};
+use serde_json::{Map, Value};
+
+/// Confidence (0.0–1.0) that `output` is JMH JSON.
+pub fn confidence(output: &str) -> f64 {
+    let trimmed = output.trim();
+    if !trimmed.starts_with('[') {
+        return 0.0;
+    }
+    let signals = [
+        (0.4, trimmed.contains("\"primaryMetric\"")),
+        (0.2, trimmed.contains("\"jmhVersion\"")),
+        (0.2, trimmed.contains("\"scoreUnit\"")),
+        (0.2, trimmed.contains("\"mode\"")),
+    ];
+    signals
+        .iter()
+        .filter(|(_, present)| *present)
+        .map(|(weight, _)| weight)
+        .sum()
+}
+
+/// Parses every entry, in the order JMH ran them.
+pub fn parse(output: &str) -> Vec<CriterionMetrics> {
+    let Ok(Value::Array(entries)) = serde_json::from_str::<Value>(output.trim()) else {
+        return Vec::new();
+    };
+    let entries: Vec<(&str, &str, &Value)> = entries
+        .iter()
+        .filter_map(|entry| {
+            let benchmark = entry.get("benchmark").and_then(Value::as_str)?;
+            let mode = entry.get("mode").and_then(Value::as_str).unwrap_or("avgt");
+            Some((benchmark, mode, entry))
+        })
+        .collect();
+
+    entries
+        .iter()
+        .filter_map(|&(benchmark, mode, entry)| {
+            let (class, method) = split_benchmark(benchmark);
+            // a class benchmarked in several modes gets a group per mode, since
+            // throughput and time scores can't be compared
+            let mixed_modes = entries.iter().any(|&(other, other_mode, _)| {
+                split_benchmark(other).0 == class && other_mode != mode
+            });
+            let group = if mixed_modes {
+                format!("{class} ({mode})")
+            } else {
+                class.to_string()
+            };
+            parse_entry(entry, &group, method, mode)
+        })
+        .collect()
+}
+
+/// "com.example.SortBench.sort" into its simple class name and method.
+fn split_benchmark(benchmark: &str) -> (&str, &str) {
+    let (class, method) = benchmark.rsplit_once('.').unwrap_or(("", benchmark));
+    let class = class.rsplit_once('.').map_or(class, |(_, simple)| simple);
+    (class, method)
+}
+
+/// `method/param=value/...`, with numeric params last so the final one can be plotted.
+fn benchmark_name(method: &str, params: Option<&Map<String, Value>>) -> String {
+    let mut params: Vec<(&String, &str)> = params
+        .into_iter()
+        .flatten()
+        .filter_map(|(key, value)| Some((key, value.as_str()?)))
+        .collect();
+    params.sort_by_key(|(_, value)| value.parse::<f64>().is_ok());
+    std::iter::once(method.to_string())
+        .chain(params.iter().map(|(key, value)| format!("{key}={value}")))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn parse_entry(entry: &Value, group: &str, method: &str, mode: &str) -> Option<CriterionMetrics> {
+    let primary = entry.get("primaryMetric")?;
+    let score_unit = primary.get("scoreUnit").and_then(Value::as_str)?;
+    let score = primary.get("score").and_then(Value::as_f64)?;
+    // JMH writes NaN bounds, as strings, when there are too few iterations for an error
+    let (lower, upper) = primary
+        .get("scoreConfidence")
+        .and_then(Value::as_array)
+        .and_then(|bounds| Some((bounds.first()?.as_f64()?, bounds.get(1)?.as_f64()?)))
+        .unwrap_or((score, score));
+    let raw: Vec<f64> = primary
+        .get("rawData")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_array)
+        .flatten()
+        .filter_map(Value::as_f64)
+        .collect();
+
+    // every score becomes a time per operation in ms; throughput modes also keep the rate
+    let throughput_mode = mode == "thrpt";
+    let unit = if throughput_mode {
+        score_unit.strip_prefix("ops/")
+    } else {
+        score_unit.strip_suffix("/op")
+    };
+    let ms = unit_ms(unit?)?;
+    let to_ms_per_op = |value: f64| {
+        if throughput_mode {
+            ms / value
+        } else {
+            value * ms
+        }
+    };
+    let ops_per_sec = throughput_mode.then(|| RateInterval {
+        lower_bound: lower * 1_000.0 / ms,
+        estimate: score * 1_000.0 / ms,
+        upper_bound: upper * 1_000.0 / ms,
+    });
+    // a higher rate is a shorter time, so throughput bounds swap over
+    let (fastest, slowest) = if throughput_mode {
+        (upper, lower)
+    } else {
+        (lower, upper)
+    };
+    let time = ConfidenceInterval {
+        lower_bound: to_ms_per_op(fastest),
+        estimate: to_ms_per_op(score),
+        upper_bound: to_ms_per_op(slowest),
+        unit: TimeUnit::Milliseconds,
+        ..Default::default()
+    };
+
+    let measured_values: Vec<f64> = raw.iter().map(|v| to_ms_per_op(*v) * 1_000_000.0).collect();
+    let iteration_count = vec![1.0; measured_values.len()];
+    let estimates = bootstrap::estimate(&iteration_count, &measured_values);
+    let median = estimates.as_ref().map(|e| e.median.clone()).or_else(|| {
+        let p50 = primary
+            .get("scorePercentiles")
+            .and_then(|p| p.get("50.0"))
+            .and_then(Value::as_f64)?;
+        let ms = to_ms_per_op(p50);
+        Some(ConfidenceInterval {
+            lower_bound: ms,
+            estimate: ms,
+            upper_bound: ms,
+            unit: TimeUnit::Milliseconds,
+            ..Default::default()
+        })
+    });
+
+    Some(CriterionMetrics {
+        name: benchmark_name(method, entry.get("params").and_then(Value::as_object)),
+        group: Some(group.to_string()),
+        time,
+        ops_per_sec,
+        mean: estimates.as_ref().map(|e| e.mean.clone()),
+        median,
+        std_dev: estimates.as_ref().map(|e| e.std_dev.clone()),
+        median_abs_dev: estimates.map(|e| e.median_abs_dev),
+        extra_metrics: secondary_metrics(entry),
+        outliers: TukeyFences::from_samples(&measured_values)
+            .map(|fences| Outliers::from_samples(fences, &measured_values)),
+        iteration_count,
+        measured_values,
+        ..Default::default()
+    })
+}
+
+/// A JMH time unit in milliseconds.
+fn unit_ms(unit: &str) -> Option<f64> {
+    match unit {
+        "ns" => Some(0.000_001),
+        "us" => Some(0.001),
+        "ms" => Some(1.0),
+        "s" => Some(1_000.0),
+        "min" => Some(60_000.0),
+        _ => None,
+    }
+}
+
+/// Profiler results such as `·gc.alloc.rate.norm`, with their own units.
+fn secondary_metrics(entry: &Value) -> Vec<ExtraMetric> {
+    entry
+        .get("secondaryMetrics")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .filter_map(|(name, metric)| {
+            Some(ExtraMetric {
+                name: name.trim_start_matches('\u{b7}').to_string(),
+                value: metric.get("score").and_then(Value::as_f64)?,
+                unit: metric.get("scoreUnit").and_then(Value::as_str)?.to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = include_str!("fixtures/jmh.json");
+
+    fn assert_float_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "Expected {a} to be approximately {b}");
+    }
+
+    #[test]
+    fn parse_average_time_entries() {
+        let results = parse(OUTPUT);
+        let names: Vec<_> = results.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "sort/size=10",
+                "sort/size=1000",
+                "shuffle/size=1000",
+                "encode",
+                "decode"
+            ]
+        );
+
+        let sort = &results[1];
+        assert_eq!(sort.group_name(), "SortBench");
+        assert!(sort.ops_per_sec.is_none());
+        // 56.416667 us/op, within JMH's 99.9% interval
+        assert_float_eq(sort.time.estimate, 0.056_416_667);
+        assert_float_eq(sort.time.lower_bound, 0.054_660_132);
+        assert_float_eq(sort.time.upper_bound, 0.058_173_202);
+        assert_eq!(sort.iteration_count.len(), 6);
+        assert_float_eq(sort.measured_values[0], 56_100.0);
+        assert!(sort.mean.is_some() && sort.std_dev.is_some());
+    }
+
+    #[test]
+    fn parse_throughput_entries() {
+        let results = parse(OUTPUT);
+        let encode = &results[3];
+        assert_eq!(encode.group_name(), "CodecBench");
+        let ops = encode.ops_per_sec.as_ref().unwrap();
+        // 1523.433333 ops/ms
+        assert_float_eq(ops.estimate, 1_523_433.333);
+        assert_float_eq(ops.lower_bound, 1_513_621.775);
+        // the time per operation is the inverse, with the bounds swapped
+        assert_float_eq(encode.time.estimate, 1.0 / 1_523.433_333);
+        assert_float_eq(encode.time.lower_bound, 1.0 / 1_533.244_891);
+        assert_float_eq(encode.time.upper_bound, 1.0 / 1_513.621_775);
+    }
+
+    #[test]
+    fn parse_secondary_metrics() {
+        let sort = &parse(OUTPUT)[0];
+        let names: Vec<_> = sort.extra_metrics.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["gc.alloc.rate", "gc.alloc.rate.norm", "gc.count"]
+        );
+        assert_eq!(sort.extra_metrics[1].unit, "B/op");
+        assert_float_eq(sort.extra_metrics[1].value, 80.0);
+    }
+
+    #[test]
+    fn params_order_numeric_last() {
+        let params = serde_json::json!({"size": "1000", "kind": "int"});
+        assert_eq!(
+            benchmark_name("sort", params.as_object()),
+            "sort/kind=int/size=1000"
+        );
+        assert_eq!(benchmark_name("sort", None), "sort");
+    }
+
+    #[test]
+    fn mixed_modes_split_groups() {
+        let output = r#"[
+            {"benchmark": "a.B.run", "mode": "thrpt", "primaryMetric": {"score": 10.0, "scoreUnit": "ops/s"}},
+            {"benchmark": "a.B.run", "mode": "avgt", "primaryMetric": {"score": 0.1, "scoreUnit": "s/op"}}
+        ]"#;
+        let results = parse(output);
+        assert_eq!(results[0].group_name(), "B (thrpt)");
+        assert_eq!(results[1].group_name(), "B (avgt)");
+        assert_float_eq(results[0].time.estimate, 100.0);
+        assert_float_eq(results[1].time.estimate, 100.0);
+    }
+
+    #[test]
+    fn test_confidence() {
+        assert!((confidence(OUTPUT) - 1.0).abs() < 1e-9);
+        assert!(confidence(include_str!("fixtures/hyperfine.json")).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,731 @@
+use super::{is_empty, units, Diagnostic};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+/// What a metric's values measure, from k6's `contains` field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum K6Contains {
+    /// Durations, in milliseconds
+    #[default]
+    Time,
+    /// Sizes, in bytes
+    Data,
+    /// Plain numbers, such as a custom `Trend` created without `isTime`
+    #[serde(rename = "default")]
+    Number,
+}
+
+impl K6Contains {
+    // serde's `skip_serializing_if` requires `fn(&T) -> bool` signature
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn is_time(&self) -> bool {
+        *self == Self::Time
+    }
+}
+
+/// A k6 trend metric such as `http_req_duration`. Time trends are in milliseconds.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct K6Trend {
+    #[serde(default, skip_serializing_if = "K6Contains::is_time")]
+    pub contains: K6Contains,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub avg: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub min: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub med: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub max: f64,
+    /// Percentiles keyed as k6 prints them, e.g. `p(95)`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub percentiles: BTreeMap<String, f64>,
+}
+
+impl K6Trend {
+    /// Median and percentiles keyed like wrk's latency distribution (`"95%"`), for the
+    /// shared latency panel.
+    pub fn distribution(&self) -> HashMap<String, f64> {
+        let median = (self.med > 0.0).then(|| ("50%".to_string(), self.med));
+        self.percentiles
+            .iter()
+            .filter_map(|(key, value)| {
+                let percent = key.strip_prefix("p(")?.strip_suffix(')')?;
+                Some((format!("{percent}%"), *value))
+            })
+            .chain(median)
+            .collect()
+    }
+}
+
+/// A k6 counter such as `http_reqs`: the total and its per-second rate.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct K6Counter {
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub count: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub rate: f64,
+}
+
+/// A k6 rate such as `checks`: the fraction (0.0–1.0) of non-zero values.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct K6Rate {
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub rate: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_u64")]
+    pub passes: u64,
+    #[serde(default, skip_serializing_if = "is_empty::check_u64")]
+    pub fails: u64,
+}
+
+/// A k6 gauge such as `vus`: the last value and its range.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct K6Gauge {
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub value: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub min: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub max: f64,
+}
+
+/// A threshold and whether it held. Older text summaries only mark the metric,
+/// so `expression` may be empty.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct K6Threshold {
+    pub metric: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub expression: String,
+    pub ok: bool,
+}
+
+/// A named `check()`. Text summaries only count passes and fails for checks that failed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct K6Check {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "is_empty::check_u64")]
+    pub passes: u64,
+    #[serde(default, skip_serializing_if = "is_empty::check_u64")]
+    pub fails: u64,
+}
+
+/// A k6 end-of-test summary. Every metric is kept by name, including custom ones.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct K6Metrics {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub script: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub trends: BTreeMap<String, K6Trend>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub counters: BTreeMap<String, K6Counter>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub rates: BTreeMap<String, K6Rate>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub gauges: BTreeMap<String, K6Gauge>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub thresholds: Vec<K6Threshold>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<K6Check>,
+}
+
+impl K6Metrics {
+    /// Fraction of passed checks, from the `checks` rate (`checks_succeeded` since k6 1.0).
+    pub fn check_pass_rate(&self) -> Option<f64> {
+        self.rates
+            .get("checks")
+            .or_else(|| self.rates.get("checks_succeeded"))
+            .map(|r| r.rate)
+    }
+
+    /// Received bytes per second, formatted like wrk's `Transfer/sec`.
+    pub fn transfer_per_sec(&self) -> String {
+        match self.counters.get("data_received") {
+            Some(received) if received.rate > 0.0 => units::format_bytes(received.rate),
+            _ => String::new(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.trends.is_empty()
+            && self.counters.is_empty()
+            && self.rates.is_empty()
+            && self.gauges.is_empty()
+    }
+}
+
+/// Confidence (0.0–1.0) that `output` is k6's end-of-test text summary.
+pub fn text_confidence(output: &str) -> f64 {
+    let signals = [
+        (
+            0.4,
+            output.contains("http_req_duration") && output.contains("avg="),
+        ),
+        (0.2, output.contains("http_reqs")),
+        (0.2, output.contains("iteration_duration")),
+        (
+            0.2,
+            output.contains("vus_max") || output.contains("█ THRESHOLDS"),
+        ),
+    ];
+    signals
+        .iter()
+        .filter(|(_, present)| *present)
+        .map(|(weight, _)| weight)
+        .sum()
+}
+
+/// Confidence (0.0–1.0) that `output` is k6 `--summary-export` (or `handleSummary`) JSON.
+pub fn json_confidence(output: &str) -> f64 {
+    let trimmed = output.trim();
+    if !trimmed.starts_with('{') {
+        return 0.0;
+    }
+    let signals = [
+        (0.4, trimmed.contains("\"metrics\"")),
+        (0.2, trimmed.contains("\"root_group\"")),
+        (0.2, trimmed.contains("\"http_req_duration")),
+        (0.2, trimmed.contains("\"p(95)\"")),
+    ];
+    signals
+        .iter()
+        .filter(|(_, present)| *present)
+        .map(|(weight, _)| weight)
+        .sum()
+}
+
+/// Parses k6's end-of-test text summary, both the classic layout and the grouped
+/// one with a `█ THRESHOLDS` section. Values that can't be read are reported in
+/// `warnings`.
+pub fn parse_text(output: &str, warnings: &mut Vec<Diagnostic>) -> Option<K6Metrics> {
+    let mut metrics = K6Metrics::default();
+    let mut parent = String::new();
+    let mut threshold_metric: Option<String> = None;
+
+    for (number, line) in output.lines().map(str::trim).enumerate() {
+        if let Some(section) = line.strip_prefix('█') {
+            threshold_metric = (section.trim() == "THRESHOLDS").then(String::new);
+            continue;
+        }
+        if let Some(script) = line.strip_prefix("script:") {
+            metrics.script = script.trim().to_string();
+            continue;
+        }
+
+        let (marker, rest) = split_marker(line);
+
+        if let Some(current) = threshold_metric.as_mut() {
+            match marker {
+                Some(ok) => metrics.thresholds.push(K6Threshold {
+                    metric: current.clone(),
+                    expression: rest.split('\'').nth(1).unwrap_or_default().to_string(),
+                    ok,
+                }),
+                None if !line.is_empty() => *current = line.to_string(),
+                None => {}
+            }
+            continue;
+        }
+
+        if let Some((name, values)) = rest.split_once(".:") {
+            let name = name.trim_end_matches('.').trim();
+            let name = if name.starts_with('{') {
+                // sub-metrics follow their parent, e.g. `{ expected_response:true }`
+                format!("{parent}{{{}}}", name.trim_matches(['{', '}', ' ']))
+            } else {
+                parent = name.to_string();
+                name.to_string()
+            };
+            if let Some(ok) = marker {
+                metrics.thresholds.push(K6Threshold {
+                    metric: name.clone(),
+                    expression: String::new(),
+                    ok,
+                });
+            }
+            add_text_metric(&mut metrics, name, values.trim(), number + 1, warnings);
+        } else if let Some(counts) = line.strip_prefix('↳') {
+            if let Some(check) = metrics.checks.last_mut() {
+                let (passes, fails) = check_counts(counts);
+                check.passes = passes;
+                check.fails = fails;
+            }
+        } else if marker.is_some() && !rest.is_empty() {
+            metrics.checks.push(K6Check {
+                name: rest.to_string(),
+                ..Default::default()
+            });
+        }
+    }
+
+    (!metrics.is_empty()).then_some(metrics)
+}
+
+/// Splits off a leading `✓`/`✗`, returning whether it marked a pass.
+fn split_marker(line: &str) -> (Option<bool>, &str) {
+    if let Some(rest) = line.strip_prefix('✓') {
+        (Some(true), rest.trim_start())
+    } else if let Some(rest) = line.strip_prefix('✗') {
+        (Some(false), rest.trim_start())
+    } else {
+        (None, line)
+    }
+}
+
+/// Adds one summary line's values, telling the metric type from their shape:
+/// `avg=… min=…` is a trend, `98.50% …` a rate, `10 min=10 max=10` a gauge,
+/// anything else a counter such as `1.2 MB 40 kB/s`. A trend whose values have no
+/// time unit holds plain numbers.
+fn add_text_metric(
+    metrics: &mut K6Metrics,
+    name: String,
+    values: &str,
+    line: usize,
+    warnings: &mut Vec<Diagnostic>,
+) {
+    let tokens = values.split_whitespace().collect::<Vec<_>>();
+    let pairs = tokens
+        .iter()
+        .filter_map(|t| t.split_once('='))
+        .collect::<Vec<_>>();
+    let contains = if pairs.iter().all(|(_, v)| v.parse::<f64>().is_ok()) {
+        K6Contains::Number
+    } else {
+        K6Contains::Time
+    };
+    let mut pair = |key: &str| {
+        let (_, value) = pairs.iter().find(|(k, _)| *k == key)?;
+        let parsed = match contains {
+            K6Contains::Number => value
+                .parse::<f64>()
+                .map_err(|_| format!("`{value}` is not a number")),
+            _ => units::parse_go_duration(value),
+        };
+        parsed
+            .map_err(|err| {
+                warnings.push(Diagnostic::at_line(
+                    line,
+                    format!("`{name}` {key} skipped: {err}"),
+                ));
+            })
+            .ok()
+    };
+
+    if values.starts_with("avg=") {
+        let trend = K6Trend {
+            contains,
+            avg: pair("avg").unwrap_or(0.0),
+            min: pair("min").unwrap_or(0.0),
+            med: pair("med").unwrap_or(0.0),
+            max: pair("max").unwrap_or(0.0),
+            percentiles: pairs
+                .iter()
+                .filter(|(k, _)| k.starts_with("p("))
+                .filter_map(|(k, _)| Some(((*k).to_string(), pair(k)?)))
+                .collect(),
+        };
+        metrics.trends.insert(name, trend);
+    } else if let Some(percent) = tokens.first().and_then(|t| t.strip_suffix('%')) {
+        let (passes, fails) = check_counts(values);
+        let rate = percent.parse::<f64>().unwrap_or_else(|_| {
+            warnings.push(Diagnostic::at_line(
+                line,
+                format!("`{name}` rate skipped: `{percent}%` is not a percentage"),
+            ));
+            0.0
+        });
+        metrics.rates.insert(
+            name,
+            K6Rate {
+                rate: rate / 100.0,
+                passes,
+                fails,
+            },
+        );
+    } else if !pairs.is_empty() {
+        let gauge = K6Gauge {
+            value: quantity(&tokens).map_or(0.0, |(v, _)| v),
+            min: pair("min").unwrap_or(0.0),
+            max: pair("max").unwrap_or(0.0),
+        };
+        metrics.gauges.insert(name, gauge);
+    } else if let Some((count, used)) = quantity(&tokens) {
+        metrics.counters.insert(
+            name,
+            K6Counter {
+                count,
+                rate: quantity(&tokens[used..]).map_or(0.0, |(v, _)| v),
+            },
+        );
+    }
+}
+
+/// Reads a number and an optional SI byte unit (`1.2 MB`, `40 kB/s`, `33.2/s`) from
+/// the start of `tokens`, returning it and how many tokens it used.
+fn quantity(tokens: &[&str]) -> Option<(f64, usize)> {
+    let number = tokens.first()?.trim_end_matches("/s").parse::<f64>().ok()?;
+    let scale = match tokens.get(1).map(|u| u.trim_end_matches("/s")) {
+        Some("B") => 1.0,
+        Some("kB") => 1e3,
+        Some("MB") => 1e6,
+        Some("GB") => 1e9,
+        Some("TB") => 1e12,
+        _ => return Some((number, 1)),
+    };
+    Some((number * scale, 2))
+}
+
+/// Pass and fail counts from `✓ 1970 ✗ 30`, `97% — ✓ 970 / ✗ 30` or `1970 out of 2000`.
+fn check_counts(values: &str) -> (u64, u64) {
+    let after = |marker: char| {
+        values
+            .split_once(marker)
+            .and_then(|(_, rest)| rest.split_whitespace().next())
+            .and_then(|v| v.parse::<u64>().ok())
+    };
+    if let (Some(passes), Some(fails)) = (after('✓'), after('✗')) {
+        return (passes, fails);
+    }
+    let tokens = values.split_whitespace().collect::<Vec<_>>();
+    tokens
+        .windows(4)
+        .find(|w| w[2] == "of" && w[1] == "out")
+        .and_then(|w| {
+            let passes = w[0].parse::<u64>().ok()?;
+            let total = w[3].parse::<u64>().ok()?;
+            Some((passes, total.saturating_sub(passes)))
+        })
+        .unwrap_or((0, 0))
+}
+
+/// Parses `k6 run --summary-export` JSON, or the data passed to `handleSummary`
+/// where each metric's values sit under `values`. Trend values that can't be read are
+/// reported in `warnings`.
+pub fn parse_json(output: &str, warnings: &mut Vec<Diagnostic>) -> Option<K6Metrics> {
+    let value: Value = serde_json::from_str(output.trim()).ok()?;
+    let mut metrics = K6Metrics::default();
+
+    for (name, metric) in value.get("metrics")?.as_object()? {
+        let values = metric.get("values").unwrap_or(metric);
+        let number = |key: &str| values.get(key).and_then(Value::as_f64).unwrap_or(0.0);
+        let count = |key: &str| values.get(key).and_then(Value::as_u64).unwrap_or(0);
+
+        let kind =
+            metric
+                .get("type")
+                .and_then(Value::as_str)
+                .unwrap_or(if values.get("avg").is_some() {
+                    "trend"
+                } else if values.get("passes").is_some() {
+                    "rate"
+                } else if values.get("count").is_some() {
+                    "counter"
+                } else {
+                    "gauge"
+                });
+        match kind {
+            "trend" => {
+                let trend = parse_json_trend(name, metric, values, warnings);
+                metrics.trends.insert(name.clone(), trend);
+            }
+            "rate" => {
+                let rate = K6Rate {
+                    // `value` in --summary-export, `rate` in handleSummary data
+                    rate: values
+                        .get("value")
+                        .or_else(|| values.get("rate"))
+                        .and_then(Value::as_f64)
+                        .unwrap_or(0.0),
+                    passes: count("passes"),
+                    fails: count("fails"),
+                };
+                metrics.rates.insert(name.clone(), rate);
+            }
+            "counter" => {
+                let counter = K6Counter {
+                    count: number("count"),
+                    rate: number("rate"),
+                };
+                metrics.counters.insert(name.clone(), counter);
+            }
+            _ => {
+                let gauge = K6Gauge {
+                    value: number("value"),
+                    min: number("min"),
+                    max: number("max"),
+                };
+                metrics.gauges.insert(name.clone(), gauge);
+            }
+        }
+
+        for (expression, result) in metric
+            .get("thresholds")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+        {
+            let ok = match result {
+                // --summary-export records whether the threshold was crossed
+                Value::Bool(crossed) => !crossed,
+                _ => result.get("ok").and_then(Value::as_bool).unwrap_or(false),
+            };
+            metrics.thresholds.push(K6Threshold {
+                metric: name.clone(),
+                expression: expression.clone(),
+                ok,
+            });
+        }
+    }
+
+    if let Some(group) = value.get("root_group") {
+        collect_checks(group, &mut metrics.checks);
+    }
+
+    (!metrics.is_empty()).then_some(metrics)
+}
+
+/// One trend from the JSON summary, with what its values measure.
+fn parse_json_trend(
+    name: &str,
+    metric: &Value,
+    values: &Value,
+    warnings: &mut Vec<Diagnostic>,
+) -> K6Trend {
+    let number = |key: &str| values.get(key).and_then(Value::as_f64).unwrap_or(0.0);
+    // --summary-export leaves `contains` out; its trends are all times
+    let contains = metric
+        .get("contains")
+        .map_or(Ok(K6Contains::Time), |c| {
+            K6Contains::deserialize(c).map_err(|_| c.to_string())
+        })
+        .unwrap_or_else(|contains| {
+            warnings.push(Diagnostic::new(format!(
+                "`{name}` has unknown `contains` {contains}; read as time"
+            )));
+            K6Contains::Time
+        });
+    K6Trend {
+        contains,
+        avg: number("avg"),
+        min: number("min"),
+        med: number("med"),
+        max: number("max"),
+        percentiles: values
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter(|(k, _)| k.starts_with("p("))
+            .filter_map(|(k, v)| {
+                let Some(v) = v.as_f64() else {
+                    warnings.push(Diagnostic::new(format!(
+                        "`{name}` {k} skipped: {v} is not a number"
+                    )));
+                    return None;
+                };
+                Some((k.clone(), v))
+            })
+            .collect(),
+    }
+}
+
+/// Checks of `group` and its nested groups, in order.
+fn collect_checks(group: &Value, checks: &mut Vec<K6Check>) {
+    let list = |key: &str| {
+        group
+            .get(key)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+    };
+    for check in list("checks") {
+        let count = |key: &str| check.get(key).and_then(Value::as_u64).unwrap_or(0);
+        checks.push(K6Check {
+            name: check
+                .get("name")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            passes: count("passes"),
+            fails: count("fails"),
+        });
+    }
+    for nested in list("groups") {
+        collect_checks(nested, checks);
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn assert_float_eq(a: f64, b: f64) {
+        const EPSILON: f64 = 1e-6;
+        assert!(
+            (a - b).abs() < EPSILON,
+            "Expected {a} to be approximately equal to {b}"
+        );
+    }
+
+    const K6_TEXT: &str = include_str!("fixtures/k6_summary.txt");
+    const K6_TEXT_NEW: &str = include_str!("fixtures/k6_summary_new.txt");
+    const K6_JSON: &str = include_str!("fixtures/k6_summary_export.json");
+
+    #[test]
+    fn parse_text_metric_types() {
+        let metrics = parse_text(K6_TEXT, &mut Vec::new()).unwrap();
+        assert_eq!(metrics.script, "script.js");
+
+        let duration = &metrics.trends["http_req_duration"];
+        assert_float_eq(duration.avg, 25.11);
+        assert_float_eq(duration.max, 210.5);
+        assert_float_eq(duration.percentiles["p(95)"], 55.2);
+        assert_float_eq(metrics.trends["http_req_blocked"].min, 0.001);
+        assert_float_eq(metrics.trends["iteration_duration"].avg, 1020.0);
+        assert!(metrics
+            .trends
+            .contains_key("http_req_duration{expected_response:true}"));
+
+        assert_float_eq(metrics.counters["http_reqs"].count, 1000.0);
+        assert_float_eq(metrics.counters["http_reqs"].rate, 33.2);
+        assert_float_eq(metrics.counters["data_received"].count, 1_200_000.0);
+        assert_float_eq(metrics.counters["data_received"].rate, 40_000.0);
+
+        let failed = &metrics.rates["http_req_failed"];
+        assert_float_eq(failed.rate, 0.015);
+        assert_eq!((failed.passes, failed.fails), (15, 985));
+        assert_float_eq(metrics.check_pass_rate().unwrap(), 0.985);
+
+        assert_float_eq(metrics.gauges["vus_max"].max, 10.0);
+    }
+
+    #[test]
+    fn parse_text_thresholds_and_checks() {
+        let metrics = parse_text(K6_TEXT, &mut Vec::new()).unwrap();
+        let thresholds = metrics
+            .thresholds
+            .iter()
+            .map(|t| (t.metric.as_str(), t.ok))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            thresholds,
+            vec![("http_req_duration", true), ("http_req_failed", false)]
+        );
+
+        assert_eq!(metrics.checks.len(), 2);
+        assert_eq!(metrics.checks[0].name, "status is 200");
+        assert_eq!(metrics.checks[1].passes, 970);
+        assert_eq!(metrics.checks[1].fails, 30);
+    }
+
+    #[test]
+    fn parse_grouped_text_summary() {
+        let metrics = parse_text(K6_TEXT_NEW, &mut Vec::new()).unwrap();
+        assert_eq!(metrics.thresholds.len(), 2);
+        assert_eq!(metrics.thresholds[0].expression, "p(95)<500");
+        assert!(metrics.thresholds[0].ok);
+        assert_eq!(metrics.thresholds[1].metric, "http_req_failed");
+        assert!(!metrics.thresholds[1].ok);
+
+        let succeeded = &metrics.rates["checks_succeeded"];
+        assert_eq!((succeeded.passes, succeeded.fails), (1970, 30));
+        assert_float_eq(metrics.check_pass_rate().unwrap(), 0.985);
+        assert_eq!(metrics.checks.len(), 2);
+        assert_float_eq(metrics.trends["http_req_duration"].med, 22.3);
+    }
+
+    #[test]
+    fn parse_summary_export() {
+        let metrics = parse_json(K6_JSON, &mut Vec::new()).unwrap();
+        let text = parse_text(K6_TEXT, &mut Vec::new()).unwrap();
+
+        for name in ["http_req_duration", "iteration_duration"] {
+            let (json, text) = (&metrics.trends[name], &text.trends[name]);
+            assert_float_eq(json.avg, text.avg);
+            assert_float_eq(json.med, text.med);
+            assert_eq!(json.percentiles.len(), text.percentiles.len());
+        }
+        assert_float_eq(
+            metrics.counters["data_received"].count,
+            text.counters["data_received"].count,
+        );
+        assert_eq!(
+            metrics.rates["http_req_failed"],
+            text.rates["http_req_failed"]
+        );
+        assert_float_eq(metrics.check_pass_rate().unwrap(), 0.985);
+
+        assert_eq!(metrics.thresholds.len(), 2);
+        assert_eq!(metrics.thresholds[1].expression, "rate<0.01");
+        assert!(!metrics.thresholds[1].ok);
+        assert_eq!(metrics.checks[0].passes, 1000);
+    }
+
+    #[test]
+    fn parse_handle_summary_data() {
+        let input = r#"{"root_group": {"checks": []}, "metrics": {
+            "http_req_duration": {"type": "trend", "contains": "time",
+                "values": {"avg": 25.11, "med": 22.3, "p(95)": 55.2},
+                "thresholds": {"p(95)<500": {"ok": true}}},
+            "checks": {"type": "rate", "values": {"rate": 0.985, "passes": 1970, "fails": 30}}
+        }}"#;
+        let metrics = parse_json(input, &mut Vec::new()).unwrap();
+        assert_float_eq(metrics.trends["http_req_duration"].avg, 25.11);
+        assert_float_eq(metrics.check_pass_rate().unwrap(), 0.985);
+        assert!(metrics.thresholds[0].ok);
+    }
+
+    #[test]
+    fn parse_trend_contents() {
+        let metrics = parse_text(K6_TEXT, &mut Vec::new()).unwrap();
+        assert_eq!(
+            metrics.trends["http_req_duration"].contains,
+            K6Contains::Time
+        );
+
+        let mut warnings = Vec::new();
+        let metrics = parse_text(
+            "cart_size......: avg=3.5 min=1 med=3 max=12 p(90)=6 p(95)=8\n\
+             http_req_duration..: avg=25.11ms min=10.2ms med=22.3ms max=210.5xs",
+            &mut warnings,
+        )
+        .unwrap();
+        let cart = &metrics.trends["cart_size"];
+        assert_eq!(cart.contains, K6Contains::Number);
+        assert_float_eq(cart.avg, 3.5);
+        assert_float_eq(cart.percentiles["p(95)"], 8.0);
+        assert_float_eq(metrics.trends["http_req_duration"].max, 0.0);
+        assert_eq!(
+            warnings[0].to_string(),
+            "line 2: `http_req_duration` max skipped: unknown time unit in `210.5xs`"
+        );
+
+        let input = r#"{"metrics": {
+            "payload": {"type": "trend", "contains": "data", "values": {"avg": 2048, "p(95)": null}}
+        }}"#;
+        let mut warnings = Vec::new();
+        let metrics = parse_json(input, &mut warnings).unwrap();
+        let payload = &metrics.trends["payload"];
+        assert_eq!(payload.contains, K6Contains::Data);
+        assert!(payload.percentiles.is_empty());
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn trend_distribution_keys() {
+        let metrics = parse_text(K6_TEXT, &mut Vec::new()).unwrap();
+        let distribution = metrics.trends["http_req_duration"].distribution();
+        assert_eq!(distribution.len(), 3);
+        assert_float_eq(distribution["50%"], 22.3);
+        assert_float_eq(distribution["95%"], 55.2);
+    }
+
+    #[test]
+    fn confidence_by_format() {
+        assert!((text_confidence(K6_TEXT) - 1.0).abs() < 1e-9);
+        assert!((text_confidence(K6_TEXT_NEW) - 1.0).abs() < 1e-9);
+        assert!((json_confidence(K6_JSON) - 1.0).abs() < 1e-9);
+        assert!(text_confidence(include_str!("fixtures/wrk1_basic.txt")).abs() < 1e-9);
+        assert!(json_confidence(include_str!("fixtures/vegeta_report.json")).abs() < 1e-9);
+    }
+}
@@ -1,5 +1,4 @@
 use super::{is_empty, units};
-use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
@@ -25,20 +24,19 @@ impl Latency {
 
 /// parses a line of latency data from the WRK output.
 /// `Latency     6.60ms    1.92ms  12.50ms   68.46%`
-impl From<&str> for Latency {
-    fn from(line: &str) -> Self {
-        line.split_whitespace()
-            .skip(1)
-            .take(4)
-            .map(units::parse_to_milliseconds)
-            .collect_tuple()
-            .map(|(avg, stddev, max, stddev_percent)| Latency {
-                avg,
-                stddev,
-                max,
-                stddev_percent,
-            })
-            .unwrap_or_default()
+impl TryFrom<&str> for Latency {
+    type Error = String;
+    fn try_from(line: &str) -> Result<Self, Self::Error> {
+        let columns = line.split_whitespace().skip(1).collect::<Vec<_>>();
+        let [avg, stddev, max, stddev_percent, ..] = columns[..] else {
+            return Err(format!("expected 4 columns, found {}", columns.len()));
+        };
+        Ok(Latency {
+            avg: units::parse_to_milliseconds(avg)?,
+            stddev: units::parse_to_milliseconds(stddev)?,
+            max: units::parse_to_milliseconds(max)?,
+            stddev_percent: units::parse_percent(stddev_percent)?,
+        })
     }
 }
 
@@ -49,7 +47,7 @@ mod tests {
 
     #[test]
     fn parse_millisecond_line() {
-        let latency = Latency::from("Latency     6.60ms    1.92ms  12.50ms   68.46%");
+        let latency = Latency::try_from("Latency     6.60ms    1.92ms  12.50ms   68.46%").unwrap();
         assert!((latency.avg - 6.60).abs() < 0.01);
         assert!((latency.stddev - 1.92).abs() < 0.01);
         assert!((latency.max - 12.50).abs() < 0.01);
@@ -58,7 +56,7 @@ mod tests {
 
     #[test]
     fn parse_microsecond_line() {
-        let latency = Latency::from("Latency   350.00us   50.00us    1.20ms   75.00%");
+        let latency = Latency::try_from("Latency   350.00us   50.00us    1.20ms   75.00%").unwrap();
         assert!((latency.avg - 0.35).abs() < 0.01);
         assert!((latency.stddev - 0.05).abs() < 0.01);
         assert!((latency.max - 1.20).abs() < 0.01);
@@ -67,7 +65,7 @@ mod tests {
 
     #[test]
     fn parse_seconds_line() {
-        let latency = Latency::from("Latency     1.50s   200.00ms    3.00s   90.00%");
+        let latency = Latency::try_from("Latency     1.50s   200.00ms    3.00s   90.00%").unwrap();
         assert!((latency.avg - 1500.0).abs() < 0.01);
         assert!((latency.stddev - 200.0).abs() < 0.01);
         assert!((latency.max - 3000.0).abs() < 0.01);
@@ -76,15 +74,27 @@ mod tests {
 
     #[test]
     fn parse_empty_line() {
-        let latency = Latency::from("");
-        assert!(latency.is_empty());
+        assert_eq!(
+            Latency::try_from(""),
+            Err("expected 4 columns, found 0".to_string())
+        );
     }
 
     #[test]
     fn parse_invalid_line() {
-        let latency = Latency::from("something completely different");
-        // Fewer than 4 tokens after skip(1) → default
-        assert_eq!(latency, Latency::default());
+        // Fewer than 4 tokens after the label
+        assert_eq!(
+            Latency::try_from("something completely different"),
+            Err("expected 4 columns, found 2".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_unknown_unit() {
+        assert_eq!(
+            Latency::try_from("Latency   3.2ns   1.1ms   5.0ms   70.00%"),
+            Err("unknown time unit in `3.2ns`".to_string())
+        );
     }
 
     #[test]
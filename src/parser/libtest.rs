@@ -0,0 +1,139 @@
+//! Rust's built-in benchmark harness (`#[bench]` with `test::Bencher`), as printed
+//! by `cargo bench` on nightly:
+//!
+//! ```text
+//! test parse::bench_json ... bench:      12,345 ns/iter (+/- 678) = 331 MB/s
+//! ```
+//!
+//! Results become [`CriterionMetrics`] so they share the Criterion dashboard.
+
+use super::criterion::{ConfidenceInterval, CriterionMetrics, Throughput, TimeUnit};
+
+const NS_TO_MS: f64 = 0.000_001;
+
+/// Confidence (0.0–1.0) that `output` is libtest bench output.
+pub fn confidence(output: &str) -> f64 {
+    if !output.lines().any(|l| parse_line(l).is_some()) {
+        return 0.0;
+    }
+    let signals = [
+        (0.4, true),
+        (0.2, output.contains("ns/iter (+/-")),
+        (
+            0.2,
+            output
+                .lines()
+                .any(|l| l.starts_with("running ") && l.trim_end().ends_with(" tests")),
+        ),
+        (0.2, output.contains("test result:")),
+    ];
+    signals
+        .iter()
+        .filter(|(_, present)| *present)
+        .map(|(weight, _)| weight)
+        .sum()
+}
+
+/// Parses every `test <name> ... bench:` line; other tests and harness output are skipped.
+pub fn parse(output: &str) -> Vec<CriterionMetrics> {
+    output.lines().filter_map(parse_line).collect()
+}
+
+/// One bench line. libtest reports the median time per iteration, and its `+/-` is
+/// the range of the per-run medians, so the interval spans the median ± that range.
+fn parse_line(line: &str) -> Option<CriterionMetrics> {
+    let (name, result) = line
+        .trim()
+        .strip_prefix("test ")?
+        .split_once(" ... bench:")?;
+    let name = name.trim();
+    let (time, rest) = result.split_once("ns/iter")?;
+    let time = parse_number(time)?;
+    let spread = rest
+        .split_once("(+/-")
+        .and_then(|(_, s)| s.split_once(')'))
+        .and_then(|(s, _)| parse_number(s))
+        .unwrap_or(0.0);
+
+    // "= 331 MB/s" is bytes per iteration over the median time, in 10^6 bytes/s
+    let throughput = rest
+        .split_once(" = ")
+        .and_then(|(_, t)| t.trim().strip_suffix("MB/s"))
+        .and_then(parse_number)
+        .map(|mb_per_sec| Throughput {
+            per_iteration: (mb_per_sec * time / 1_000.0).round(),
+            unit: "bytes".to_string(),
+        });
+
+    let median = ConfidenceInterval {
+        lower_bound: (time - spread).max(0.0) * NS_TO_MS,
+        estimate: time * NS_TO_MS,
+        upper_bound: (time + spread) * NS_TO_MS,
+        unit: TimeUnit::Milliseconds,
+        ..Default::default()
+    };
+    Some(CriterionMetrics {
+        name: name.to_string(),
+        group: name.rsplit_once("::").map(|(module, _)| module.to_string()),
+        time: median.clone(),
+        median: Some(median),
+        throughput,
+        ..Default::default()
+    })
+}
+
+/// A number with optional thousands separators, e.g. "1,204,811" or "241.52".
+fn parse_number(text: &str) -> Option<f64> {
+    text.trim().replace(',', "").parse().ok()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = include_str!("fixtures/libtest_bench.txt");
+
+    fn assert_float_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "Expected {a} to be approximately {b}");
+    }
+
+    #[test]
+    fn parse_bench_lines() {
+        let results = parse(OUTPUT);
+        let names: Vec<_> = results.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "parse::bench_parse_json",
+                "parse::bench_parse_small",
+                "bench_fib_10",
+                "bench_tokenize_large"
+            ]
+        );
+
+        let json = &results[0];
+        assert_eq!(json.group.as_deref(), Some("parse"));
+        assert_float_eq(json.time.estimate, 0.012_345);
+        assert_float_eq(json.time.lower_bound, 0.011_667);
+        assert_float_eq(json.time.upper_bound, 0.013_023);
+        let throughput = json.throughput.as_ref().unwrap();
+        assert_eq!(throughput.unit, "bytes");
+        assert_float_eq(throughput.per_iteration, 4086.0);
+
+        let fib = &results[2];
+        assert!(fib.group.is_none());
+        assert_float_eq(fib.time.estimate, 0.000_241_52);
+        assert!(fib.throughput.is_none());
+
+        assert_float_eq(results[3].time.estimate, 1.204_811);
+        assert_float_eq(results[3].time.upper_bound, 1.229_941);
+    }
+
+    #[test]
+    fn confidence_needs_bench_lines() {
+        assert!((confidence(OUTPUT) - 1.0).abs() < 1e-9);
+        assert!(confidence("running 2 tests\ntest a ... ok\ntest result: ok.").abs() < 1e-9);
+        assert!(confidence("test x ... bench: 5 ns/iter (+/- 1)") > 0.5);
+    }
+}
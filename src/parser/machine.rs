@@ -0,0 +1,153 @@
+use super::is_empty;
+use serde::{Deserialize, Serialize};
+
+/// The machine a benchmark ran on, for tools that record it alongside their results.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MachineInfo {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub host: String,
+    /// Operating system, e.g. "Linux 6.5.0"
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub os: String,
+    /// CPU model name
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub cpu: String,
+    #[serde(default, skip_serializing_if = "is_empty::check_u64")]
+    pub num_cpus: u64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub mhz_per_cpu: f64,
+    /// One entry per cache level, e.g. "L1 Data 48 KiB (x8)"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub caches: Vec<String>,
+    /// Language runtime the benchmarks ran on, e.g. `CPython 3.11.4`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub runtime: String,
+    /// Build type of the benchmark library, e.g. "release" or "debug"
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub build_type: String,
+    /// When the run started, as the tool printed it
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub date: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub executable: String,
+    /// 1, 5 and 15 minute load averages at the start of the run
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub load_avg: Vec<f64>,
+}
+
+impl MachineInfo {
+    /// CPU model, count and clock speed in one line, e.g. "16 × 3600 MHz".
+    pub fn cpu_summary(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.cpu.is_empty() {
+            parts.push(self.cpu.clone());
+        }
+        match (self.num_cpus, self.mhz_per_cpu) {
+            (0, _) => {}
+            (n, mhz) if mhz > 0.0 => parts.push(format!("{n} \u{d7} {mhz:.0} MHz")),
+            (n, _) => parts.push(format!("{n} CPUs")),
+        }
+        parts.join(", ")
+    }
+
+    /// Fills in what this machine is missing from `other`, reported by another part of
+    /// the same input. Returns the fields where the two disagree; run details such as
+    /// the date are expected to differ and are never reported.
+    pub fn merge(&mut self, other: Self) -> Vec<&'static str> {
+        let mut conflicts = Vec::new();
+        merge_field("host", &mut self.host, other.host, &mut conflicts);
+        merge_field("os", &mut self.os, other.os, &mut conflicts);
+        merge_field("cpu", &mut self.cpu, other.cpu, &mut conflicts);
+        merge_field(
+            "num_cpus",
+            &mut self.num_cpus,
+            other.num_cpus,
+            &mut conflicts,
+        );
+        merge_field(
+            "mhz_per_cpu",
+            &mut self.mhz_per_cpu,
+            other.mhz_per_cpu,
+            &mut conflicts,
+        );
+        merge_field("caches", &mut self.caches, other.caches, &mut conflicts);
+        merge_field("runtime", &mut self.runtime, other.runtime, &mut conflicts);
+        merge_field(
+            "build_type",
+            &mut self.build_type,
+            other.build_type,
+            &mut conflicts,
+        );
+        merge_field("date", &mut self.date, other.date, &mut Vec::new());
+        merge_field(
+            "executable",
+            &mut self.executable,
+            other.executable,
+            &mut Vec::new(),
+        );
+        merge_field(
+            "load_avg",
+            &mut self.load_avg,
+            other.load_avg,
+            &mut Vec::new(),
+        );
+        conflicts
+    }
+}
+
+fn merge_field<T: Default + PartialEq>(
+    name: &'static str,
+    field: &mut T,
+    other: T,
+    conflicts: &mut Vec<&'static str>,
+) {
+    if *field == T::default() {
+        *field = other;
+    } else if other != T::default() && *field != other {
+        conflicts.push(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cpu_summary_combines_known_fields() {
+        let machine = MachineInfo {
+            num_cpus: 16,
+            mhz_per_cpu: 3600.0,
+            ..Default::default()
+        };
+        assert_eq!(machine.cpu_summary(), "16 \u{d7} 3600 MHz");
+
+        let machine = MachineInfo {
+            cpu: "AMD EPYC 7B13".to_string(),
+            num_cpus: 8,
+            ..Default::default()
+        };
+        assert_eq!(machine.cpu_summary(), "AMD EPYC 7B13, 8 CPUs");
+        assert_eq!(MachineInfo::default().cpu_summary(), "");
+    }
+
+    #[test]
+    fn merge_fills_gaps_and_reports_conflicts() {
+        let mut machine = MachineInfo {
+            os: "linux/amd64".to_string(),
+            cpu: "AMD EPYC 7B13".to_string(),
+            date: "2024-03-01".to_string(),
+            ..Default::default()
+        };
+        let conflicts = machine.merge(MachineInfo {
+            host: "ci-runner-12".to_string(),
+            os: "Linux 6.5.0".to_string(),
+            cpu: "AMD EPYC 7B13".to_string(),
+            date: "2024-03-02".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(conflicts, vec!["os"]);
+        assert_eq!(machine.host, "ci-runner-12");
+        assert_eq!(machine.os, "linux/amd64");
+        assert_eq!(machine.date, "2024-03-01");
+    }
+}
@@ -1,8 +1,18 @@
 use super::{
-    is_empty, latency::Latency, percentile::PercentileBucket, request_sec::RequestSec, units,
+    ab::ConnectionTime,
+    calibration::{self, ThreadCalibration},
+    diagnostics::Diagnostic,
+    errors::ErrorCounts,
+    hdr::LatencyInterval,
+    is_empty,
+    latency::Latency,
+    oha::ResponseTimeBin,
+    percentile::{HistogramSummary, PercentileBucket},
+    request_sec::RequestSec,
+    units,
 };
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, str::FromStr};
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 pub struct WrkMetrics {
@@ -28,15 +38,83 @@ pub struct WrkMetrics {
     pub latency_distribution: HashMap<String, f64>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub percentiles: Vec<PercentileBucket>,
+    #[serde(default, skip_serializing_if = "HistogramSummary::is_empty")]
+    pub histogram: HistogramSummary,
+    /// wrk2 `-U` latency measured without correcting for coordinated omission
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub uncorrected_latency_distribution: HashMap<String, f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub uncorrected_percentiles: Vec<PercentileBucket>,
+    #[serde(default, skip_serializing_if = "HistogramSummary::is_empty")]
+    pub uncorrected_histogram: HistogramSummary,
+    #[serde(default, skip_serializing_if = "ErrorCounts::is_empty")]
+    pub errors: ErrorCounts,
+    /// wrk2 per-thread calibration results
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub calibration: Vec<ThreadCalibration>,
+    /// Requested throughput (`-R`) in requests/sec, when the wrk2 command is included
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub target_rate: f64,
+    /// Responses per HTTP status code, for tools that report them (e.g. hey)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub status_codes: BTreeMap<u16, u64>,
+    /// Time spent in each phase of a request (hey's Details section, oha's `details`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub phases: Vec<PhaseTiming>,
+    /// Connect/processing/waiting/total breakdown (ab's Connection Times table)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub connection_times: Vec<ConnectionTime>,
+    /// Response time histogram (oha's `responseTimeHistogram`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub response_times: Vec<ResponseTimeBin>,
+    /// Latency over time, one entry per interval of an `HdrHistogram` interval log
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub intervals: Vec<LatencyInterval>,
 }
 
-impl From<&str> for WrkMetrics {
-    fn from(output: &str) -> Self {
-        let lines = output
+/// Average, fastest and slowest time spent in one phase of a request, in milliseconds.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct PhaseTiming {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub avg: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub min: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub max: f64,
+}
+
+/// Fraction of the target rate a run must reach to count as keeping up.
+const TARGET_RATE_TOLERANCE: f64 = 0.95;
+
+impl WrkMetrics {
+    /// Achieved requests/sec as a fraction of the wrk2 target rate, if one was set.
+    pub fn target_rate_ratio(&self) -> Option<f64> {
+        (self.target_rate > 0.0).then(|| self.requests_per_sec / self.target_rate)
+    }
+
+    /// True when wrk2 was given a target rate and fell noticeably short of it.
+    pub fn missed_target_rate(&self) -> bool {
+        self.target_rate_ratio()
+            .is_some_and(|ratio| ratio < TARGET_RATE_TOLERANCE)
+    }
+}
+
+impl WrkMetrics {
+    /// Parses the output of a single wrk run. Every value that can't be read and falls
+    /// back to its default is reported in `warnings`, numbered by line within `output`.
+    pub fn parse(output: &str, warnings: &mut Vec<Diagnostic>) -> Self {
+        let numbered = output
             .lines()
-            .map(str::trim)
-            .filter(|l| !l.is_empty())
+            .enumerate()
+            .map(|(i, l)| (i + 1, l.trim()))
+            .filter(|(_, l)| !l.is_empty())
             .collect::<Vec<_>>();
+        let lines = numbered.iter().map(|(_, l)| *l).collect::<Vec<_>>();
+        let running = numbered
+            .iter()
+            .find(|(_, l)| l.starts_with("Running"))
+            .map(|(number, _)| *number);
 
         let endpoint = lines
             .iter()
@@ -56,49 +134,41 @@ impl From<&str> for WrkMetrics {
             })
             .unwrap_or((0, 0));
 
-        let latency = lines
-            .iter()
-            .find(|l| l.starts_with("Latency"))
-            .map(|&l| Latency::from(l))
-            .unwrap_or_default();
+        let latency = parse_stats_row(&numbered, "Latency", warnings);
+        let req = parse_stats_row(&numbered, "Req/Sec", warnings);
 
-        let latency_distribution = parse_latency_distribution(&lines);
-        let percentiles = lines
-            .iter()
-            .position(|l| l.contains("Detailed Percentile spectrum"))
-            .map_or_else(Vec::new, |start_idx| {
-                lines
-                    .iter()
-                    .skip(start_idx + 2)
-                    .take_while(|l| !l.starts_with("#["))
-                    .filter_map(|l| {
-                        if l.is_empty() {
-                            None
-                        } else {
-                            PercentileBucket::try_from(*l).ok()
-                        }
-                    })
-                    .collect()
-            });
-
-        let req = lines
-            .iter()
-            .find(|l| l.starts_with("Req/Sec"))
-            .map(|&l| RequestSec::from(l))
-            .unwrap_or_default();
-
-        let (total_requests, duration) = lines
-            .iter()
-            .find(|l| l.contains("requests in"))
-            .and_then(|l| parse_requests_line(l))
-            .unwrap_or((0, 0.0));
-
-        let requests_per_sec = lines
-            .iter()
-            .find(|l| l.contains("Requests/sec:"))
-            .and_then(|l| l.split_whitespace().nth(1))
-            .and_then(|s| f64::from_str(s).ok())
-            .unwrap_or(0.0);
+        // wrk2 -U prints a second, uncorrected distribution after the recorded one;
+        // split there so neither section bleeds into the other.
+        let (corrected, uncorrected) = numbered.split_at(
+            lines
+                .iter()
+                .position(|l| l.contains("Uncorrected Latency"))
+                .unwrap_or(lines.len()),
+        );
+        let latency_distribution = parse_latency_distribution(corrected, warnings);
+        let (percentiles, histogram) = parse_percentile_spectrum(corrected, warnings);
+        let uncorrected_latency_distribution = parse_latency_distribution(uncorrected, warnings);
+        let (uncorrected_percentiles, uncorrected_histogram) =
+            parse_percentile_spectrum(uncorrected, warnings);
+
+        let (total_requests, duration) = summary_line(
+            &numbered,
+            running,
+            ("requests in", "N requests in ..."),
+            "total requests and duration are missing",
+            parse_requests_line,
+            warnings,
+        )
+        .unwrap_or((0, 0.0));
+        let requests_per_sec = summary_line(
+            &numbered,
+            running,
+            ("Requests/sec:", "Requests/sec:"),
+            "throughput is missing",
+            |l| l.split_whitespace().nth(1)?.parse().ok(),
+            warnings,
+        )
+        .unwrap_or(0.0);
 
         let transfer_per_sec = lines
             .iter()
@@ -107,6 +177,16 @@ impl From<&str> for WrkMetrics {
             .map(String::from)
             .unwrap_or_default();
 
+        let errors = ErrorCounts::parse(&numbered, warnings);
+
+        let calibration = parse_calibration(&numbered, warnings);
+
+        let target_rate = lines
+            .iter()
+            .find(|l| calibration::is_wrk_command(l))
+            .and_then(|l| calibration::parse_target_rate(l))
+            .unwrap_or(0.0);
+
         WrkMetrics {
             endpoint,
             threads,
@@ -119,33 +199,171 @@ impl From<&str> for WrkMetrics {
             transfer_per_sec,
             latency_distribution,
             percentiles,
+            histogram,
+            uncorrected_latency_distribution,
+            uncorrected_percentiles,
+            uncorrected_histogram,
+            errors,
+            calibration,
+            target_rate,
+            ..Default::default()
         }
     }
 }
 
-fn parse_latency_distribution(lines: &[&str]) -> HashMap<String, f64> {
-    match lines
+/// wrk2's `Thread calibration:` lines; one that can't be read is skipped with a warning.
+fn parse_calibration(
+    lines: &[(usize, &str)],
+    warnings: &mut Vec<Diagnostic>,
+) -> Vec<ThreadCalibration> {
+    lines
         .iter()
-        .position(|l| l.contains("Latency Distribution"))
-    {
-        Some(idx) => lines
-            .iter()
-            .skip(idx + 1)
-            .take_while(|l| !l.is_empty())
-            .filter(|l| l.contains('%'))
-            .filter_map(|l| {
-                let parts = l.split_whitespace().collect::<Vec<_>>();
-                if parts.len() >= 2 {
-                    let percent = parts[0].to_string();
-                    let value = units::parse_to_milliseconds(parts[1]);
-                    Some((percent, value))
-                } else {
+        .filter(|(_, l)| l.starts_with("Thread calibration:"))
+        .filter_map(|&(number, line)| {
+            ThreadCalibration::try_from(line)
+                .map_err(|err| {
+                    warnings.push(Diagnostic::at_line(
+                        number,
+                        format!("thread calibration skipped: {err}"),
+                    ));
+                })
+                .ok()
+        })
+        .collect()
+}
+
+/// Reads one of the summary lines after the stats table, found by `marker` and shown
+/// as `label`. A missing line is reported against the test's `Running` line.
+fn summary_line<T>(
+    lines: &[(usize, &str)],
+    running: Option<usize>,
+    (marker, label): (&str, &str),
+    missing: &str,
+    parse: impl Fn(&str) -> Option<T>,
+    warnings: &mut Vec<Diagnostic>,
+) -> Option<T> {
+    let Some(&(number, line)) = lines.iter().find(|(_, l)| l.contains(marker)) else {
+        let message = format!("test has no `{label}` line; {missing}");
+        warnings.push(match running {
+            Some(number) => Diagnostic::at_line(number, message),
+            None => Diagnostic::new(message),
+        });
+        return None;
+    };
+    let value = parse(line);
+    if value.is_none() {
+        warnings.push(Diagnostic::at_line(
+            number,
+            format!("can't read the `{label}` line; {missing}"),
+        ));
+    }
+    value
+}
+
+/// The `Latency` or `Req/Sec` row of the thread stats table, which carries Avg, Stdev,
+/// Max and +/- Stdev. A row that can't be read is skipped with a warning.
+fn parse_stats_row<T>(lines: &[(usize, &str)], label: &str, warnings: &mut Vec<Diagnostic>) -> T
+where
+    T: for<'a> TryFrom<&'a str, Error = String> + Default,
+{
+    let Some(&(number, line)) = lines
+        .iter()
+        .find(|(_, l)| l.split_whitespace().next() == Some(label) && !l.contains("Distribution"))
+    else {
+        return T::default();
+    };
+    T::try_from(line).unwrap_or_else(|err| {
+        warnings.push(Diagnostic::at_line(
+            number,
+            format!("`{label}` row skipped: {err}"),
+        ));
+        T::default()
+    })
+}
+
+fn parse_latency_distribution(
+    lines: &[(usize, &str)],
+    warnings: &mut Vec<Diagnostic>,
+) -> HashMap<String, f64> {
+    let Some(idx) = lines
+        .iter()
+        .position(|(_, l)| l.contains("Latency Distribution"))
+    else {
+        return HashMap::new();
+    };
+    lines
+        .iter()
+        .skip(idx + 1)
+        .filter_map(|&(number, line)| {
+            let mut parts = line.split_whitespace();
+            let percent = parts.next().filter(|p| p.ends_with('%'))?;
+            let value = parts.next()?;
+            match units::parse_to_milliseconds(value) {
+                Ok(value) => Some((percent.to_string(), value)),
+                Err(err) => {
+                    warnings.push(Diagnostic::at_line(
+                        number,
+                        format!("latency distribution row skipped: {err}"),
+                    ));
                     None
                 }
-            })
-            .collect::<HashMap<String, f64>>(),
-        None => HashMap::new(),
+            }
+        })
+        .collect()
+}
+
+/// Parses the wrk2 "Detailed Percentile spectrum" rows and the `#[...]` summary footer
+/// that follows them. A complete spectrum ends in a `#[Mean ...]` footer.
+fn parse_percentile_spectrum(
+    lines: &[(usize, &str)],
+    warnings: &mut Vec<Diagnostic>,
+) -> (Vec<PercentileBucket>, HistogramSummary) {
+    let Some(start_idx) = lines
+        .iter()
+        .position(|(_, l)| l.contains("Detailed Percentile spectrum"))
+    else {
+        return (Vec::new(), HistogramSummary::default());
+    };
+    let header = lines[start_idx].0;
+
+    let section = lines.iter().skip(start_idx + 2).copied();
+    let percentiles = section
+        .clone()
+        .take_while(|(_, l)| !l.starts_with("#["))
+        .filter_map(|(number, l)| {
+            PercentileBucket::try_from(l)
+                .map_err(|err| {
+                    warnings.push(Diagnostic::at_line(
+                        number,
+                        format!("percentile spectrum row skipped: {err}"),
+                    ));
+                })
+                .ok()
+        })
+        .collect::<Vec<PercentileBucket>>();
+    let footer = section
+        .skip_while(|(_, l)| !l.starts_with("#["))
+        .take_while(|(_, l)| l.starts_with("#["))
+        .collect::<Vec<_>>();
+
+    if percentiles.is_empty() {
+        warnings.push(Diagnostic::at_line(
+            header,
+            "percentile spectrum has no rows",
+        ));
+    } else if !footer.iter().any(|(_, l)| l.starts_with("#[Mean")) {
+        let last = percentiles.last().map_or(0.0, |b| b.percentile);
+        warnings.push(Diagnostic::at_line(
+            header,
+            format!(
+                "percentile spectrum is truncated at {:.3}% with no `#[Mean ...]` footer",
+                last * 100.0
+            ),
+        ));
     }
+
+    let histogram = HistogramSummary::parse(&footer, warnings);
+    (percentiles, histogram)
 }
 
 /// Parses the line containing the total requests and duration
@@ -176,28 +394,88 @@ fn parse_requests_line(line: &str) -> Option<(u64, f64)> {
     Some((requests, duration))
 }
 
-pub fn parse_tests(output: &str) -> Vec<WrkMetrics> {
+/// Parses every wrk run in `output`, with warnings numbered by line within all of it.
+pub fn parse_tests(output: &str, warnings: &mut Vec<Diagnostic>) -> Vec<WrkMetrics> {
     let mut tests = Vec::new();
     let mut current_test = String::new();
-
-    for line in output.lines() {
-        if line.trim().starts_with("Running") && !current_test.trim().is_empty() {
-            tests.push(WrkMetrics::from(current_test.as_str()));
+    let mut first_line = 1;
+
+    for (i, line) in output.lines().enumerate() {
+        let trimmed = line.trim();
+        // A pasted wrk command belongs to the test that follows it, so it starts a new
+        // test and the "Running" line directly after it does not.
+        let starts_test = calibration::is_wrk_command(trimmed)
+            || (trimmed.starts_with("Running") && has_running_line(&current_test));
+        if starts_test && !current_test.trim().is_empty() {
+            if has_running_line(&current_test) {
+                tests.push(parse_test(&current_test, first_line, warnings));
+            }
             current_test.clear();
         }
+        if current_test.is_empty() {
+            first_line = i + 1;
+        }
         current_test.push_str(line);
         current_test.push('\n');
     }
 
     // Parse the last test
-    if current_test.trim().starts_with("Running") && !current_test.trim().is_empty() {
-        tests.push(WrkMetrics::from(current_test.as_str()));
+    if has_running_line(&current_test) {
+        tests.push(parse_test(&current_test, first_line, warnings));
     }
 
     tests
 }
 
+/// Parses one test that starts at `first_line` of the whole input.
+fn parse_test(test: &str, first_line: usize, warnings: &mut Vec<Diagnostic>) -> WrkMetrics {
+    let mut test_warnings = Vec::new();
+    let metrics = WrkMetrics::parse(test, &mut test_warnings);
+    warnings.extend(test_warnings.into_iter().map(|mut warning| {
+        warning.line = warning.line.map(|l| l + first_line - 1);
+        warning
+    }));
+    metrics
+}
+
+fn has_running_line(test: &str) -> bool {
+    test.lines().any(|l| l.trim().starts_with("Running"))
+}
+
+/// Confidence (0.0–1.0) that `output` came from wrk or wrk2.
+pub fn confidence(output: &str) -> f64 {
+    let lines = output.lines().map(str::trim).collect::<Vec<_>>();
+    let signals = [
+        (
+            0.5,
+            lines
+                .iter()
+                .any(|l| l.starts_with("Running") && l.contains("test @")),
+        ),
+        (
+            0.2,
+            lines
+                .iter()
+                .any(|l| l.contains("threads and") && l.contains("connections")),
+        ),
+        (0.2, lines.iter().any(|l| l.starts_with("Requests/sec:"))),
+        (0.1, lines.iter().any(|l| l.starts_with("Latency"))),
+    ];
+    signals
+        .iter()
+        .filter(|(_, present)| *present)
+        .map(|(weight, _)| weight)
+        .sum()
+}
+
+/// Whether `output` contains sections only wrk2 prints: thread calibration or the
+/// `HdrHistogram` percentile spectrum.
+pub fn is_wrk2(output: &str) -> bool {
+    output.contains("Thread calibration:") || output.contains("Detailed Percentile spectrum")
+}
+
 #[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
     use super::*;
 
@@ -209,11 +487,21 @@ mod tests {
         );
     }
 
+    fn parse(output: &str) -> WrkMetrics {
+        WrkMetrics::parse(output, &mut Vec::new())
+    }
+
+    fn warnings(output: &str) -> Vec<String> {
+        let mut warnings = Vec::new();
+        parse_tests(output, &mut warnings);
+        warnings.iter().map(ToString::to_string).collect()
+    }
+
     const SAMPLE_OUTPUT: &str = include_str!("fixtures/wrk1_basic.txt");
 
     #[test]
     fn test_wrk_metrics_from() {
-        let metrics = WrkMetrics::from(SAMPLE_OUTPUT);
+        let metrics = parse(SAMPLE_OUTPUT);
         assert_eq!(metrics.endpoint, "http://localhost:8080");
         assert_eq!(metrics.threads, 2);
         assert_eq!(metrics.connections, 100);
@@ -240,7 +528,7 @@ mod tests {
 
     #[test]
     fn test_wrk_metrics_from_2() {
-        let metrics = WrkMetrics::from(SAMPLE_OUTPUT_2);
+        let metrics = parse(SAMPLE_OUTPUT_2);
         assert_eq!(metrics.endpoint, "http://localhost:8080/index.html");
         assert_eq!(metrics.threads, 12);
         assert_eq!(metrics.connections, 400);
@@ -267,7 +555,7 @@ mod tests {
 
     #[test]
     fn test_parse_wrk2_output() {
-        let metrics = WrkMetrics::from(WRK2_INPUT);
+        let metrics = parse(WRK2_INPUT);
         assert_eq!(metrics.endpoint, "http://127.0.0.1:8080/sys/ping");
         assert_eq!(metrics.threads, 2);
         assert_eq!(metrics.connections, 100);
@@ -292,13 +580,31 @@ mod tests {
         assert_float_eq(dist["99.990%"], 39.52);
         assert_float_eq(dist["99.999%"], 44.03);
         assert_float_eq(dist["100.000%"], 44.10);
+
+        // Test full percentile spectrum and footer
+        let first = &metrics.percentiles[0];
+        assert_float_eq(first.value, 0.111);
+        assert_eq!(first.total_count, 1);
+        assert_float_eq(first.inverse_percentile, 1.0);
+        let last = metrics.percentiles.last().unwrap();
+        assert_float_eq(last.percentile, 1.0);
+        assert_eq!(last.total_count, 99500);
+        assert!(last.inverse_percentile.is_infinite());
+
+        let histogram = &metrics.histogram;
+        assert_float_eq(histogram.mean, 1.458);
+        assert_float_eq(histogram.std_deviation, 2.240);
+        assert_float_eq(histogram.max, 44.064);
+        assert_eq!(histogram.total_count, 99500);
+        assert_eq!(histogram.buckets, 27);
+        assert_eq!(histogram.sub_buckets, 2048);
     }
 
     const WRK2_INPUT_2: &str = include_str!("fixtures/wrk2_short.txt");
 
     #[test]
     fn test_parse_wrk2_output_2() {
-        let metrics = WrkMetrics::from(WRK2_INPUT_2);
+        let metrics = parse(WRK2_INPUT_2);
         assert_eq!(metrics.endpoint, "http://127.0.0.1:80/index.html");
         assert_eq!(metrics.threads, 2);
         assert_eq!(metrics.connections, 100);
@@ -330,7 +636,7 @@ mod tests {
 
     #[test]
     fn test_parse_wrk_calibration_no_histogram() {
-        let metrics = WrkMetrics::from(WRK_CALIBRATION_NO_HISTOGRAM);
+        let metrics = parse(WRK_CALIBRATION_NO_HISTOGRAM);
         assert_eq!(metrics.endpoint, "http://127.0.0.1:80/index.html");
         assert_eq!(metrics.threads, 2);
         assert_eq!(metrics.connections, 100);
@@ -348,11 +654,67 @@ mod tests {
         // No latency distribution or percentile data
         assert!(metrics.latency_distribution.is_empty());
         assert!(metrics.percentiles.is_empty());
+        assert!(metrics.histogram.is_empty());
+    }
+
+    const WRK2_UNCORRECTED: &str = include_str!("fixtures/wrk2_uncorrected.txt");
+
+    #[test]
+    fn test_parse_wrk2_uncorrected() {
+        let metrics = parse(WRK2_UNCORRECTED);
+        assert_eq!(metrics.endpoint, "http://127.0.0.1:8080/");
+        assert_eq!(metrics.total_requests, 157_012);
+
+        // Corrected (recorded) latency only contains the first section
+        let dist = &metrics.latency_distribution;
+        assert_eq!(dist.len(), 8);
+        assert_float_eq(dist["50.000%"], 2.31);
+        assert_float_eq(dist["99.000%"], 215.04);
+        assert_eq!(metrics.percentiles.len(), 9);
+        assert_float_eq(metrics.percentiles[5].value, 215.039);
+        assert_float_eq(metrics.histogram.mean, 18.42);
+
+        // Uncorrected latency is kept separately
+        let uncorrected = &metrics.uncorrected_latency_distribution;
+        assert_eq!(uncorrected.len(), 8);
+        assert_float_eq(uncorrected["50.000%"], 1.97);
+        assert_float_eq(uncorrected["99.000%"], 12.10);
+        assert_eq!(metrics.uncorrected_percentiles.len(), 9);
+        assert_float_eq(metrics.uncorrected_percentiles[5].value, 12.103);
+        assert_eq!(metrics.uncorrected_percentiles[8].total_count, 149_577);
+        assert_float_eq(metrics.uncorrected_histogram.mean, 2.301);
+        assert_float_eq(metrics.uncorrected_histogram.max, 44.64);
+    }
+
+    #[test]
+    fn test_parse_wrk2_without_uncorrected() {
+        let metrics = parse(WRK2_INPUT);
+        assert!(metrics.uncorrected_latency_distribution.is_empty());
+        assert!(metrics.uncorrected_percentiles.is_empty());
+        assert!(metrics.uncorrected_histogram.is_empty());
+    }
+
+    const WRK_SOCKET_ERRORS: &str = include_str!("fixtures/wrk_socket_errors.txt");
+
+    #[test]
+    fn test_parse_wrk_socket_errors() {
+        let metrics = parse(WRK_SOCKET_ERRORS);
+        assert_eq!(metrics.endpoint, "http://127.0.0.1:8080/api/orders");
+        assert_eq!(metrics.total_requests, 126_540);
+        assert_float_eq(metrics.requests_per_sec, 4212.38);
+
+        let errors = &metrics.errors;
+        assert_eq!(errors.connect, 0);
+        assert_eq!(errors.read, 152);
+        assert_eq!(errors.write, 3);
+        assert_eq!(errors.timeout, 87);
+        assert_eq!(errors.non_2xx_3xx, 37962);
+        assert!((errors.error_rate(metrics.total_requests) - 30.19).abs() < 0.01);
     }
 
     #[test]
     fn test_error_handling() {
-        let empty = WrkMetrics::from("invalid output");
+        let empty = parse("invalid output");
         assert_eq!(empty.endpoint, "");
         assert_eq!(empty.threads, 0);
         assert_eq!(empty.connections, 0);
@@ -368,13 +730,14 @@ mod tests {
         assert_eq!(empty.transfer_per_sec, "");
         assert!(empty.latency_distribution.is_empty());
         assert_eq!(empty.percentiles.len(), 0);
+        assert!(empty.errors.is_empty());
     }
 
     #[test]
     fn test_parse_multiple_tests() {
         let input = include_str!("fixtures/wrk_multiple_tests.txt");
 
-        let collection = parse_tests(input);
+        let collection = parse_tests(input, &mut Vec::new());
         assert_eq!(collection.len(), 2);
 
         // Test first result
@@ -404,15 +767,145 @@ mod tests {
         assert_eq!(second.transfer_per_sec, "156.95KB");
     }
 
+    const WRK2_WITH_COMMAND: &str = include_str!("fixtures/wrk2_with_command.txt");
+
+    #[test]
+    fn test_parse_wrk2_calibration_and_target_rate() {
+        let collection = parse_tests(WRK2_WITH_COMMAND, &mut Vec::new());
+        assert_eq!(collection.len(), 2);
+
+        let first = &collection[0];
+        assert_eq!(first.endpoint, "http://127.0.0.1:8080/sys/ping");
+        assert_float_eq(first.target_rate, 2000.0);
+        assert_eq!(first.calibration.len(), 2);
+        assert_float_eq(first.calibration[0].mean_latency, 1.473);
+        assert_float_eq(first.calibration[0].sampling_interval, 10.0);
+        assert_float_eq(first.calibration[1].mean_latency, 1.496);
+        assert!(!first.missed_target_rate());
+
+        let second = &collection[1];
+        assert_float_eq(second.target_rate, 20000.0);
+        assert_float_eq(second.calibration[0].mean_latency, 9.747);
+        assert_float_eq(second.calibration[0].sampling_interval, 21.0);
+        assert!((second.target_rate_ratio().unwrap() - 0.5321).abs() < 0.0001);
+        assert!(second.missed_target_rate());
+    }
+
+    #[test]
+    fn test_calibration_without_command() {
+        let metrics = parse(WRK_CALIBRATION_NO_HISTOGRAM);
+        assert_eq!(metrics.calibration.len(), 2);
+        assert_float_eq(metrics.calibration[1].mean_latency, 9.631);
+        assert_float_eq(metrics.target_rate, 0.0);
+        assert!(metrics.target_rate_ratio().is_none());
+        assert!(!metrics.missed_target_rate());
+    }
+
+    #[test]
+    fn test_warnings_clean_output() {
+        assert!(warnings(SAMPLE_OUTPUT).is_empty());
+        assert!(warnings(WRK2_INPUT).is_empty());
+        assert!(warnings(WRK2_UNCORRECTED).is_empty());
+        assert!(warnings(WRK2_WITH_COMMAND).is_empty());
+    }
+
+    #[test]
+    fn test_warnings_unknown_unit_and_short_row() {
+        let input = "Running 10s test @ http://localhost\n  \
+            Latency   3.2ns   1.1ms\n  \
+            Req/Sec   1.2k   0.1k   2.0k   70.00%\n  \
+            Latency Distribution\n     50%   2.1ns\n     75%   3.0ms\n";
+        assert_eq!(
+            warnings(input),
+            vec![
+                "line 2: `Latency` row skipped: expected 4 columns, found 2",
+                "line 5: latency distribution row skipped: unknown time unit in `2.1ns`",
+                "line 1: test has no `N requests in ...` line; total requests and duration are missing",
+                "line 1: test has no `Requests/sec:` line; throughput is missing",
+            ]
+        );
+
+        // the default is applied only to what was reported
+        let metrics = parse(input);
+        assert!(metrics.latency.is_empty());
+        assert_float_eq(metrics.req.avg, 1200.0);
+        assert_eq!(metrics.latency_distribution.len(), 1);
+        assert_float_eq(metrics.latency_distribution["75%"], 3.0);
+    }
+
+    #[test]
+    fn test_warnings_numbered_across_tests() {
+        let input = include_str!("fixtures/wrk_multiple_tests.txt");
+        let second = input
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| l.trim().starts_with("Running"))
+            .nth(1)
+            .map(|(i, _)| i + 1)
+            .unwrap();
+        let broken = input.replacen("Requests/sec:", "Requests/min:", 2);
+        let rendered = warnings(&broken);
+        // wrk prints `-nan` for threads that saw no requests
+        assert_eq!(
+            rendered,
+            vec![
+                "line 6: `Req/Sec` row skipped: `-nan` is not a number".to_string(),
+                "line 2: test has no `Requests/sec:` line; throughput is missing".to_string(),
+                format!(
+                    "line {}: `Req/Sec` row skipped: `-nan` is not a number",
+                    second + 4
+                ),
+                format!("line {second}: test has no `Requests/sec:` line; throughput is missing"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_warnings_truncated_spectrum() {
+        let truncated = WRK2_INPUT
+            .lines()
+            .take_while(|l| !l.contains("#[Mean"))
+            .take(40)
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(warnings(&truncated)
+            .iter()
+            .any(|w| w.contains("percentile spectrum is truncated")));
+    }
+
+    #[test]
+    fn test_warnings_unreadable_spectrum_and_counts() {
+        let broken = WRK2_INPUT
+            .replacen("0.642     0.100000", "0.642     ten%", 1)
+            .replacen(
+                "Total count    =        99500",
+                "Total count    =        lots",
+                1,
+            );
+        assert_eq!(
+            warnings(&broken),
+            vec![
+                "line 22: percentile spectrum row skipped: Invalid percentile",
+                "line 108: can't read histogram `Total count` value `lots`",
+            ]
+        );
+
+        let broken = include_str!("fixtures/wrk_socket_errors.txt").replace("read 152", "read ?");
+        assert_eq!(
+            warnings(&broken),
+            vec!["line 12: can't read `read` socket error count `?`"]
+        );
+    }
+
     #[test]
     fn test_parse_empty_input() {
-        let collection = parse_tests("");
+        let collection = parse_tests("", &mut Vec::new());
         assert!(collection.is_empty());
     }
 
     #[test]
     fn test_parse_invalid_input() {
-        let collection = parse_tests("invalid output");
+        let collection = parse_tests("invalid output", &mut Vec::new());
         assert!(collection.is_empty());
     }
 }
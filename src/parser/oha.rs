@@ -0,0 +1,296 @@
+use super::{
+    errors::ErrorCounts,
+    is_empty,
+    latency::Latency,
+    metrics::{PhaseTiming, WrkMetrics},
+    percentile::PercentileBucket,
+    request_sec::RequestSec,
+    units, Diagnostic,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// One bar of oha's response time histogram: how many responses took about `latency` ms.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct ResponseTimeBin {
+    pub latency: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_u64")]
+    pub count: u64,
+}
+
+/// Confidence (0.0–1.0) that `output` is `oha --json` output.
+pub fn confidence(output: &str) -> f64 {
+    let trimmed = output.trim();
+    if !trimmed.starts_with('{') {
+        return 0.0;
+    }
+    let signals = [
+        (0.4, trimmed.contains("\"latencyPercentiles\"")),
+        (0.2, trimmed.contains("\"responseTimeHistogram\"")),
+        (0.2, trimmed.contains("\"statusCodeDistribution\"")),
+        (0.2, trimmed.contains("\"requestsPerSec\"")),
+    ];
+    signals
+        .iter()
+        .filter(|(_, present)| *present)
+        .map(|(weight, _)| weight)
+        .sum()
+}
+
+/// Parses `oha --json` output. oha reports every duration in seconds. Entries that can't
+/// be read are skipped and reported in `warnings`.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+pub fn parse(output: &str, warnings: &mut Vec<Diagnostic>) -> Option<WrkMetrics> {
+    let json: Value = serde_json::from_str(output.trim()).ok()?;
+    let summary = json.get("summary")?;
+    let secs = |value: &Value, key: &str| value.get(key).and_then(Value::as_f64).unwrap_or(0.0);
+    let ms = |value: &Value, key: &str| secs(value, key) * 1000.0;
+
+    let status_codes = entries(&json, "statusCodeDistribution")
+        .filter_map(|(code, count)| {
+            let entry = code.parse().ok().zip(count.as_u64());
+            if entry.is_none() {
+                warnings.push(skipped("statusCodeDistribution", code, count));
+            }
+            entry
+        })
+        .collect::<BTreeMap<u16, u64>>();
+    let responses = status_codes.values().sum::<u64>();
+
+    let mut errors = ErrorCounts {
+        non_2xx_3xx: status_codes
+            .iter()
+            .filter(|(code, _)| !(200..400).contains(*code))
+            .map(|(_, count)| count)
+            .sum(),
+        ..Default::default()
+    };
+    for (message, count) in entries(&json, "errorDistribution") {
+        match count.as_u64() {
+            Some(count) => errors.add_message(message, count),
+            None => warnings.push(skipped("errorDistribution", message, count)),
+        }
+    }
+
+    let latency_percentiles = entries(&json, "latencyPercentiles")
+        .filter_map(|(key, value)| {
+            let entry = key
+                .strip_prefix('p')
+                .filter(|p| p.parse::<f64>().is_ok())
+                .zip(value.as_f64());
+            if entry.is_none() {
+                warnings.push(skipped("latencyPercentiles", key, value));
+            }
+            entry
+        })
+        .collect::<Vec<_>>();
+
+    let mut percentiles = latency_percentiles
+        .iter()
+        .filter_map(|(percent, value)| {
+            let percentile = percent.parse::<f64>().ok()? / 100.0;
+            // oha doesn't count samples per percentile, so `total_count` stays unknown
+            Some(PercentileBucket {
+                value: value * 1000.0,
+                percentile,
+                total_count: 0,
+                inverse_percentile: 1.0 / (1.0 - percentile),
+            })
+        })
+        .collect::<Vec<_>>();
+    percentiles.sort_by(|a, b| a.percentile.total_cmp(&b.percentile));
+    // close the spectrum at the slowest response, like wrk2's final 100% row
+    percentiles.push(PercentileBucket {
+        value: ms(summary, "slowest"),
+        percentile: 1.0,
+        total_count: 0,
+        inverse_percentile: f64::INFINITY,
+    });
+
+    let rps = json.get("rps").unwrap_or(&Value::Null);
+
+    Some(WrkMetrics {
+        latency: Latency {
+            avg: ms(summary, "average"),
+            max: ms(summary, "slowest"),
+            ..Default::default()
+        },
+        req: RequestSec {
+            avg: secs(rps, "mean"),
+            stddev: secs(rps, "stddev"),
+            max: secs(rps, "max"),
+            ..Default::default()
+        },
+        total_requests: responses + errors.socket_errors(),
+        duration: secs(summary, "total"),
+        requests_per_sec: secs(summary, "requestsPerSec"),
+        transfer_per_sec: match secs(summary, "sizePerSec") {
+            bytes if bytes > 0.0 => units::format_bytes(bytes),
+            _ => String::new(),
+        },
+        latency_distribution: latency_percentiles
+            .iter()
+            .map(|(percent, value)| (format!("{percent}%"), value * 1000.0))
+            .collect(),
+        percentiles,
+        errors,
+        status_codes,
+        phases: request_phases(&json),
+        response_times: response_time_histogram(&json, warnings),
+        ..Default::default()
+    })
+}
+
+/// oha's `details`, named like hey's Details section.
+fn request_phases(json: &Value) -> Vec<PhaseTiming> {
+    let ms =
+        |value: &Value, key: &str| value.get(key).and_then(Value::as_f64).unwrap_or(0.0) * 1000.0;
+    entries(json, "details")
+        .map(|(name, timing)| PhaseTiming {
+            name: match name.as_str() {
+                "DNSDialup" => "DNS+dialup".to_string(),
+                "DNSLookup" => "DNS-lookup".to_string(),
+                _ => name.clone(),
+            },
+            avg: ms(timing, "average"),
+            min: ms(timing, "fastest"),
+            max: ms(timing, "slowest"),
+        })
+        .collect()
+}
+
+/// oha's `responseTimeHistogram`, keyed by latency in seconds, sorted by latency.
+fn response_time_histogram(json: &Value, warnings: &mut Vec<Diagnostic>) -> Vec<ResponseTimeBin> {
+    let mut response_times = entries(json, "responseTimeHistogram")
+        .filter_map(|(latency, count)| {
+            let Some((latency, count)) = latency.parse::<f64>().ok().zip(count.as_u64()) else {
+                warnings.push(skipped("responseTimeHistogram", latency, count));
+                return None;
+            };
+            Some(ResponseTimeBin {
+                latency: latency * 1000.0,
+                count,
+            })
+        })
+        .collect::<Vec<_>>();
+    response_times.sort_by(|a, b| a.latency.total_cmp(&b.latency));
+    response_times
+}
+
+/// A warning for an entry of the object at `key` that couldn't be read.
+fn skipped(key: &str, entry: &str, value: &Value) -> Diagnostic {
+    Diagnostic::new(format!("`{key}` entry `{entry}: {value}` skipped"))
+}
+
+/// Key/value pairs of the object at `key`, or nothing when it is missing.
+fn entries<'a>(json: &'a Value, key: &str) -> impl Iterator<Item = (&'a String, &'a Value)> {
+    json.get(key)
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn assert_float_eq(a: f64, b: f64) {
+        const EPSILON: f64 = 1e-6;
+        assert!(
+            (a - b).abs() < EPSILON,
+            "Expected {a} to be approximately equal to {b}"
+        );
+    }
+
+    const OHA_JSON: &str = include_str!("fixtures/oha_basic.json");
+
+    #[test]
+    fn parse_oha_summary() {
+        let metrics = parse(OHA_JSON, &mut Vec::new()).unwrap();
+        assert_float_eq(metrics.duration, 10.003_412);
+        assert_float_eq(metrics.requests_per_sec, 2000.8173);
+        assert_float_eq(metrics.latency.avg, 24.4);
+        assert_float_eq(metrics.latency.max, 205.1);
+        assert_float_eq(metrics.req.stddev, 84.3);
+        assert_eq!(metrics.transfer_per_sec, "212.82KB");
+        assert_eq!(metrics.total_requests, 20015);
+    }
+
+    #[test]
+    fn parse_oha_percentiles() {
+        let metrics = parse(OHA_JSON, &mut Vec::new()).unwrap();
+        assert_eq!(metrics.percentiles.len(), 10);
+
+        let p99 = &metrics.percentiles[6];
+        assert_float_eq(p99.percentile, 0.99);
+        assert_float_eq(p99.value, 71.3);
+        assert_eq!(p99.total_count, 0);
+        assert_float_eq(p99.inverse_percentile, 100.0);
+
+        let last = metrics.percentiles.last().unwrap();
+        assert!(last.inverse_percentile.is_infinite());
+        assert_eq!(last.total_count, 0);
+
+        assert_eq!(metrics.latency_distribution.len(), 9);
+        assert_float_eq(metrics.latency_distribution["99.9%"], 120.4);
+    }
+
+    #[test]
+    fn parse_oha_histogram_and_details() {
+        let metrics = parse(OHA_JSON, &mut Vec::new()).unwrap();
+        assert_eq!(metrics.response_times.len(), 11);
+        assert_float_eq(metrics.response_times[1].latency, 20.59);
+        assert_eq!(metrics.response_times[1].count, 9120);
+        assert!(metrics
+            .response_times
+            .windows(2)
+            .all(|w| w[0].latency < w[1].latency));
+
+        assert_eq!(metrics.phases.len(), 2);
+        assert_eq!(metrics.phases[0].name, "DNS+dialup");
+        assert_float_eq(metrics.phases[0].max, 15.1);
+    }
+
+    #[test]
+    fn parse_oha_status_codes_and_errors() {
+        let metrics = parse(OHA_JSON, &mut Vec::new()).unwrap();
+        assert_eq!(metrics.status_codes.get(&503), Some(&200));
+        assert_eq!(metrics.errors.non_2xx_3xx, 200);
+        assert_eq!(metrics.errors.read, 12);
+        assert_eq!(metrics.errors.timeout, 3);
+    }
+
+    #[test]
+    fn parse_oha_warnings() {
+        let mut warnings = Vec::new();
+        parse(OHA_JSON, &mut warnings).unwrap();
+        assert!(warnings.is_empty());
+
+        let broken = OHA_JSON
+            .replacen("\"p99\"", "\"pNaN%\"", 1)
+            .replacen("\"503\"", "\"5xx\"", 1);
+        let metrics = parse(&broken, &mut warnings).unwrap();
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+        assert!(warnings[0]
+            .message
+            .starts_with("`statusCodeDistribution` entry `5xx"));
+        assert!(warnings[1]
+            .message
+            .starts_with("`latencyPercentiles` entry `pNaN%"));
+        assert!(!metrics.latency_distribution.contains_key("99%"));
+        assert_eq!(metrics.status_codes.get(&503), None);
+    }
+
+    #[test]
+    fn confidence_prefers_oha() {
+        assert!((confidence(OHA_JSON) - 1.0).abs() < 1e-9);
+        assert!(confidence(include_str!("fixtures/vegeta_report.json")).abs() < 1e-9);
+        assert!(confidence(include_str!("fixtures/hey_basic.txt")).abs() < 1e-9);
+    }
+}
@@ -1,9 +1,26 @@
+use super::{is_empty, Diagnostic};
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// One row of an `HdrHistogram` "Detailed Percentile spectrum".
+/// `Value   Percentile   TotalCount 1/(1-Percentile)`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 pub struct PercentileBucket {
     pub value: f64,
     pub percentile: f64,
+    /// Number of samples at or below `value`
+    #[serde(default, skip_serializing_if = "is_empty::check_u64")]
+    pub total_count: u64,
+    /// `1/(1-Percentile)`; infinite for the final 100% row
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub inverse_percentile: f64,
+}
+
+impl PercentileBucket {
+    /// Position on the standard `HdrHistogram` log axis: `log10(1/(1-Percentile))`.
+    /// Computed from `percentile` so buckets without the inverse column still plot.
+    pub fn log_position(&self) -> f64 {
+        (1.0 / (1.0 - self.percentile)).log10()
+    }
 }
 
 impl TryFrom<&str> for PercentileBucket {
@@ -20,11 +37,91 @@ impl TryFrom<&str> for PercentileBucket {
             .ok_or("Invalid line format")?
             .parse::<f64>()
             .map_err(|_| "Invalid percentile".to_string())?;
-        Ok(Self { value, percentile })
+        let total_count = parts
+            .get(2)
+            .map(|s| s.parse::<u64>())
+            .transpose()
+            .map_err(|_| "Invalid total count".to_string())?
+            .unwrap_or(0);
+        let inverse_percentile = parts
+            .get(3)
+            .map(|s| s.parse::<f64>())
+            .transpose()
+            .map_err(|_| "Invalid inverse percentile".to_string())?
+            .unwrap_or(0.0);
+        Ok(Self {
+            value,
+            percentile,
+            total_count,
+            inverse_percentile,
+        })
+    }
+}
+
+/// Summary footer printed after an `HdrHistogram` percentile spectrum.
+/// ```text
+/// #[Mean    =        1.458, StdDeviation   =        2.240]
+/// #[Max     =       44.064, Total count    =        99500]
+/// #[Buckets =           27, SubBuckets     =         2048]
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct HistogramSummary {
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub mean: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub std_deviation: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub max: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_u64")]
+    pub total_count: u64,
+    #[serde(default, skip_serializing_if = "is_empty::check_u64")]
+    pub buckets: u64,
+    #[serde(default, skip_serializing_if = "is_empty::check_u64")]
+    pub sub_buckets: u64,
+}
+
+impl HistogramSummary {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl HistogramSummary {
+    /// Reads the `#[...]` footer lines among `lines`. Values that can't be read are
+    /// left at 0 and reported in `warnings`.
+    pub fn parse(lines: &[(usize, &str)], warnings: &mut Vec<Diagnostic>) -> Self {
+        let mut summary = HistogramSummary::default();
+        let pairs = lines
+            .iter()
+            .filter_map(|(number, l)| Some((*number, l.strip_prefix("#[")?.strip_suffix(']')?)))
+            .flat_map(|(number, l)| l.split(',').map(move |pair| (number, pair)))
+            .filter_map(|(number, pair)| Some((number, pair.split_once('=')?)));
+
+        for (number, (key, value)) in pairs {
+            let (key, value) = (key.trim(), value.trim());
+            let parsed = match key {
+                "Mean" => value.parse().map(|v| summary.mean = v).is_ok(),
+                "StdDeviation" => value.parse().map(|v| summary.std_deviation = v).is_ok(),
+                "Max" => value.parse().map(|v| summary.max = v).is_ok(),
+                "Total count" => value.parse().map(|v| summary.total_count = v).is_ok(),
+                "Buckets" => value.parse().map(|v| summary.buckets = v).is_ok(),
+                "SubBuckets" => value.parse().map(|v| summary.sub_buckets = v).is_ok(),
+                _ => true,
+            };
+            if !parsed {
+                warnings.push(Diagnostic::at_line(
+                    number,
+                    format!("can't read histogram `{key}` value `{value}`"),
+                ));
+            }
+        }
+
+        summary
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
     use super::*;
 
@@ -53,4 +150,81 @@ mod tests {
         assert_float_eq(percentiles[5].value, 6.671);
         assert_float_eq(percentiles[5].percentile, 0.5);
     }
+
+    #[test]
+    fn test_parse_spectrum_count_columns() {
+        let percentiles = SAMPLE_SPECTRUM
+            .lines()
+            .filter_map(|l| PercentileBucket::try_from(l).ok())
+            .collect::<Vec<PercentileBucket>>();
+
+        assert_eq!(percentiles[0].total_count, 1);
+        assert_float_eq(percentiles[0].inverse_percentile, 1.0);
+        assert_eq!(percentiles[1].total_count, 3951);
+        assert_float_eq(percentiles[1].inverse_percentile, 1.11);
+        assert!(percentiles[5].inverse_percentile.is_infinite());
+    }
+
+    #[test]
+    fn test_parse_two_column_line() {
+        let bucket = PercentileBucket::try_from("1.5 0.9").unwrap();
+        assert_float_eq(bucket.value, 1.5);
+        assert_float_eq(bucket.percentile, 0.9);
+        assert_eq!(bucket.total_count, 0);
+        assert_float_eq(bucket.inverse_percentile, 0.0);
+    }
+
+    #[test]
+    fn test_log_position() {
+        let bucket = PercentileBucket::try_from("12.0 0.999 99403 1000.00").unwrap();
+        assert_float_eq(bucket.log_position(), 3.0);
+        let bucket = PercentileBucket::try_from("0.1 0.0 1 1.00").unwrap();
+        assert_float_eq(bucket.log_position(), 0.0);
+    }
+
+    #[test]
+    fn test_parse_histogram_summary() {
+        let lines = [
+            "#[Mean    =        1.458, StdDeviation   =        2.240]",
+            "#[Max     =       44.064, Total count    =        99500]",
+            "#[Buckets =           27, SubBuckets     =         2048]",
+        ];
+        let numbered = lines.into_iter().enumerate().collect::<Vec<_>>();
+        let summary = HistogramSummary::parse(&numbered, &mut Vec::new());
+        assert_float_eq(summary.mean, 1.458);
+        assert_float_eq(summary.std_deviation, 2.240);
+        assert_float_eq(summary.max, 44.064);
+        assert_eq!(summary.total_count, 99500);
+        assert_eq!(summary.buckets, 27);
+        assert_eq!(summary.sub_buckets, 2048);
+    }
+
+    #[test]
+    fn test_parse_histogram_summary_missing() {
+        let summary = HistogramSummary::parse(&[(1, "0.921 0.000000 1 1.00")], &mut Vec::new());
+        assert!(summary.is_empty());
+    }
+
+    #[test]
+    fn test_parse_histogram_summary_warnings() {
+        let lines = [
+            (
+                4,
+                "#[Mean    =        1.458, StdDeviation   =        2.240]",
+            ),
+            (
+                5,
+                "#[Max     =       44.064, Total count    =          n/a]",
+            ),
+        ];
+        let mut warnings = Vec::new();
+        let summary = HistogramSummary::parse(&lines, &mut warnings);
+        assert_float_eq(summary.max, 44.064);
+        assert_eq!(summary.total_count, 0);
+        assert_eq!(
+            warnings[0].to_string(),
+            "line 5: can't read histogram `Total count` value `n/a`"
+        );
+        assert_eq!(warnings.len(), 1);
+    }
 }
@@ -0,0 +1,272 @@
+//! pytest-benchmark results written with `--benchmark-json` (or saved with
+//! `--benchmark-autosave`). Each benchmark becomes a [`CriterionMetrics`], and the file's
+//! `machine_info` and `commit_info` describe the run.
+
+use super::bootstrap;
+use super::commit::CommitInfo;
+use super::criterion::{ConfidenceInterval, CriterionMetrics, Outliers, TimeUnit, TukeyFences};
+use super::machine::MachineInfo;
+use serde_json::Value;
+
+/// z-score for a 95% confidence interval of the mean
+const Z_95: f64 = 1.96;
+const S_TO_MS: f64 = 1_000.0;
+const S_TO_NS: f64 = 1_000_000_000.0;
+
+/// Confidence (0.0–1.0) that `output` is pytest-benchmark JSON.
+pub fn confidence(output: &str) -> f64 {
+    let trimmed = output.trim();
+    if !trimmed.starts_with('{') {
+        return 0.0;
+    }
+    let signals = [
+        (
+            0.4,
+            trimmed.contains("\"benchmarks\"") && trimmed.contains("\"stats\""),
+        ),
+        (0.2, trimmed.contains("\"machine_info\"")),
+        (0.2, trimmed.contains("\"fullname\"")),
+        (0.2, trimmed.contains("\"rounds\"")),
+    ];
+    signals
+        .iter()
+        .filter(|(_, present)| *present)
+        .map(|(weight, _)| weight)
+        .sum()
+}
+
+/// Parses every benchmark plus the run's machine and commit.
+pub fn parse(
+    output: &str,
+) -> (
+    Option<MachineInfo>,
+    Option<CommitInfo>,
+    Vec<CriterionMetrics>,
+) {
+    let Ok(value) = serde_json::from_str::<Value>(output.trim()) else {
+        return (None, None, Vec::new());
+    };
+    let benchmarks = value
+        .get("benchmarks")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(parse_benchmark)
+        .collect();
+    (
+        value.get("machine_info").map(parse_machine_info),
+        value.get("commit_info").map(parse_commit_info),
+        benchmarks,
+    )
+}
+
+/// One benchmark. Its group is pytest-benchmark's `group`, or else the test module,
+/// which is how pytest-benchmark splits its own result tables.
+#[allow(clippy::cast_precision_loss)]
+fn parse_benchmark(bench: &Value) -> Option<CriterionMetrics> {
+    let name = bench.get("name").and_then(Value::as_str)?;
+    let stats = bench.get("stats")?;
+    let stat = |key: &str| stats.get(key).and_then(Value::as_f64);
+    let group = bench
+        .get("group")
+        .and_then(Value::as_str)
+        .or_else(|| {
+            bench
+                .get("fullname")
+                .and_then(Value::as_str)
+                .and_then(|full| full.split_once("::"))
+                .map(|(module, _)| module)
+        })
+        .map(str::to_string);
+
+    // round times are per iteration; older files keep them beside `stats`
+    let data: Vec<f64> = stats
+        .get("data")
+        .or_else(|| bench.get("data"))
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_f64)
+        .collect();
+    let iterations = stat("iterations").unwrap_or(1.0);
+    let iteration_count = vec![iterations; data.len()];
+    let measured_values: Vec<f64> = data.iter().map(|t| t * iterations * S_TO_NS).collect();
+    let per_iteration: Vec<f64> = data.iter().map(|t| t * S_TO_NS).collect();
+
+    let estimates = bootstrap::estimate(&iteration_count, &measured_values);
+    let mut metrics = if let Some(estimates) = estimates {
+        CriterionMetrics {
+            time: estimates.mean.clone(),
+            mean: Some(estimates.mean),
+            median: Some(estimates.median),
+            std_dev: Some(estimates.std_dev),
+            median_abs_dev: Some(estimates.median_abs_dev),
+            ..Default::default()
+        }
+    } else {
+        let mean = stat("mean")?;
+        let rounds = stat("rounds").unwrap_or(0.0);
+        let time = match stat("stddev") {
+            Some(sd) if rounds > 1.0 => {
+                let standard_error = sd / rounds.sqrt();
+                ConfidenceInterval {
+                    lower_bound: (mean - Z_95 * standard_error).max(0.0) * S_TO_MS,
+                    estimate: mean * S_TO_MS,
+                    upper_bound: (mean + Z_95 * standard_error) * S_TO_MS,
+                    unit: TimeUnit::Milliseconds,
+                    standard_error: standard_error * S_TO_MS,
+                    ..Default::default()
+                }
+            }
+            _ => point(mean),
+        };
+        CriterionMetrics {
+            time: time.clone(),
+            mean: Some(time),
+            median: stat("median").map(point),
+            std_dev: stat("stddev").map(point),
+            ..Default::default()
+        }
+    };
+    metrics.name = name.to_string();
+    metrics.group = group;
+    metrics.outliers = TukeyFences::from_samples(&per_iteration)
+        .map(|fences| Outliers::from_samples(fences, &per_iteration));
+    metrics.iteration_count = iteration_count;
+    metrics.measured_values = measured_values;
+    Some(metrics)
+}
+
+/// A point estimate in seconds, as an interval in milliseconds.
+fn point(seconds: f64) -> ConfidenceInterval {
+    ConfidenceInterval {
+        lower_bound: seconds * S_TO_MS,
+        estimate: seconds * S_TO_MS,
+        upper_bound: seconds * S_TO_MS,
+        unit: TimeUnit::Milliseconds,
+        ..Default::default()
+    }
+}
+
+/// `machine_info`: the host, OS and Python from `platform`, and CPU details from py-cpuinfo.
+fn parse_machine_info(info: &Value) -> MachineInfo {
+    let text = |value: &Value, key: &str| {
+        value
+            .get(key)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    let join = |parts: [String; 2]| {
+        parts
+            .into_iter()
+            .filter(|p| !p.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let cpu = info.get("cpu").cloned().unwrap_or_default();
+    MachineInfo {
+        host: text(info, "node"),
+        os: join([text(info, "system"), text(info, "release")]),
+        cpu: text(&cpu, "brand_raw"),
+        num_cpus: cpu.get("count").and_then(Value::as_u64).unwrap_or(0),
+        // [hz, exponent] as reported by py-cpuinfo
+        mhz_per_cpu: cpu
+            .get("hz_advertised")
+            .and_then(|hz| hz.get(0))
+            .and_then(Value::as_f64)
+            .map_or(0.0, |hz| hz / 1_000_000.0),
+        runtime: join([
+            text(info, "python_implementation"),
+            text(info, "python_version"),
+        ]),
+        ..Default::default()
+    }
+}
+
+fn parse_commit_info(info: &Value) -> CommitInfo {
+    let text = |key: &str| {
+        info.get(key)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    CommitInfo {
+        id: text("id"),
+        branch: text("branch"),
+        project: text("project"),
+        time: text("time"),
+        dirty: info.get("dirty").and_then(Value::as_bool).unwrap_or(false),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = include_str!("fixtures/pytest_benchmark.json");
+
+    fn assert_float_eq(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "Expected {a} to be approximately {b}");
+    }
+
+    #[test]
+    fn parse_benchmarks_with_data() {
+        let (_, _, results) = parse(OUTPUT);
+        let names: Vec<_> = results.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["test_sort[10]", "test_sort[1000]", "test_parse_config"]
+        );
+
+        let small = &results[0];
+        assert_eq!(small.group_name(), "sort");
+        assert_eq!(small.iteration_count, vec![100.0; 8]);
+        // 1.21 us per iteration over a round of 100 iterations
+        assert_float_eq(small.measured_values[0], 121_000.0);
+        assert_float_eq(small.time.estimate, 0.001_216_25);
+        assert!(small.time.lower_bound < small.time.estimate);
+        assert!(small.median_abs_dev.is_some());
+        // the 1.31 us round is an outlier
+        assert_eq!(small.outliers.as_ref().unwrap().outlier_count, 1);
+    }
+
+    #[test]
+    fn parse_benchmark_stats_without_data() {
+        let (_, _, results) = parse(OUTPUT);
+        let config = &results[2];
+        assert_eq!(config.group_name(), "tests/test_sorting.py");
+        assert!(config.iteration_count.is_empty());
+        assert!(config.outliers.is_none());
+        assert_float_eq(config.time.estimate, 15.1);
+        // mean ± 1.96 × stddev / √rounds
+        assert_float_eq(config.time.lower_bound, 14.961_407_070_887_436);
+        assert_float_eq(config.median.as_ref().unwrap().estimate, 15.1);
+    }
+
+    #[test]
+    fn parse_machine_and_commit_info() {
+        let (machine, commit, _) = parse(OUTPUT);
+        let machine = machine.unwrap();
+        assert_eq!(machine.host, "build-07");
+        assert_eq!(machine.os, "Linux 6.5.0-14-generic");
+        assert_eq!(machine.cpu, "AMD Ryzen 9 5950X 16-Core Processor");
+        assert_eq!(machine.num_cpus, 16);
+        assert_float_eq(machine.mhz_per_cpu, 3400.0);
+        assert_eq!(machine.runtime, "CPython 3.11.4");
+
+        let commit = commit.unwrap();
+        assert_eq!(commit.id, "9f2c1e7a4b3d5f6e8a0b1c2d3e4f5a6b7c8d9e0f");
+        assert_eq!(commit.branch, "main");
+        assert_eq!(commit.project, "sorting");
+        assert!(commit.dirty);
+    }
+
+    #[test]
+    fn test_confidence() {
+        assert!((confidence(OUTPUT) - 1.0).abs() < 1e-9);
+        assert!(confidence(include_str!("fixtures/google_benchmark.json")) < 0.5);
+        assert!(confidence(include_str!("fixtures/hyperfine.json")).abs() < 1e-9);
+    }
+}
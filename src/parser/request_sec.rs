@@ -1,5 +1,4 @@
 use super::{is_empty, units};
-use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
@@ -23,22 +22,21 @@ impl RequestSec {
     }
 }
 
-/// parses a line of req/sec from the WRK output.
+/// parses a line of req/sec data from the WRK output.
 /// `Req/Sec    56.20k     8.07k   62.00k    86.54%`
-impl From<&str> for RequestSec {
-    fn from(line: &str) -> Self {
-        line.split_whitespace()
-            .skip(1)
-            .take(4)
-            .map(units::parse_count)
-            .collect_tuple()
-            .map(|(avg, stddev, max, stddev_percent)| RequestSec {
-                avg,
-                stddev,
-                max,
-                stddev_percent,
-            })
-            .unwrap_or_default()
+impl TryFrom<&str> for RequestSec {
+    type Error = String;
+    fn try_from(line: &str) -> Result<Self, Self::Error> {
+        let columns = line.split_whitespace().skip(1).collect::<Vec<_>>();
+        let [avg, stddev, max, stddev_percent, ..] = columns[..] else {
+            return Err(format!("expected 4 columns, found {}", columns.len()));
+        };
+        Ok(RequestSec {
+            avg: units::parse_count(avg)?,
+            stddev: units::parse_count(stddev)?,
+            max: units::parse_count(max)?,
+            stddev_percent: units::parse_percent(stddev_percent)?,
+        })
     }
 }
 
@@ -49,7 +47,7 @@ mod tests {
 
     #[test]
     fn parse_thousands() {
-        let req = RequestSec::from("Req/Sec    56.20k     8.07k   62.00k    86.54%");
+        let req = RequestSec::try_from("Req/Sec    56.20k     8.07k   62.00k    86.54%").unwrap();
         assert!((req.avg - 56_200.0).abs() < 1.0);
         assert!((req.stddev - 8_070.0).abs() < 1.0);
         assert!((req.max - 62_000.0).abs() < 1.0);
@@ -58,7 +56,7 @@ mod tests {
 
     #[test]
     fn parse_plain_numbers() {
-        let req = RequestSec::from("Req/Sec   500.00    50.00   800.00    90.00%");
+        let req = RequestSec::try_from("Req/Sec   500.00    50.00   800.00    90.00%").unwrap();
         assert!((req.avg - 500.0).abs() < 0.01);
         assert!((req.stddev - 50.0).abs() < 0.01);
         assert!((req.max - 800.0).abs() < 0.01);
@@ -67,14 +65,19 @@ mod tests {
 
     #[test]
     fn parse_empty_line() {
-        let req = RequestSec::from("");
-        assert!(req.is_empty());
+        assert!(RequestSec::try_from("").is_err());
     }
 
     #[test]
     fn parse_invalid_line() {
-        let req = RequestSec::from("garbage data here");
-        assert_eq!(req, RequestSec::default());
+        assert_eq!(
+            RequestSec::try_from("garbage data here"),
+            Err("expected 4 columns, found 2".to_string())
+        );
+        assert_eq!(
+            RequestSec::try_from("Req/Sec   1.2x   0.1k   2.0k   80.00%"),
+            Err("unknown count suffix in `1.2x`".to_string())
+        );
     }
 
     #[test]
@@ -0,0 +1,116 @@
+use super::calibration;
+
+/// A run of consecutive input lines that came from a single tool.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Segment {
+    /// 1-based line number of the segment's first line in the original input
+    pub first_line: usize,
+    pub text: String,
+    /// Whether the segment was cut around a wrk run; other segments hold everything between them
+    pub is_wrk: bool,
+}
+
+/// Splits a paste such as a CI log into wrk and non-wrk segments so each can be
+/// detected and parsed on its own. A wrk segment starts at a pasted wrk command or
+/// its `Running ... test @` line and ends after `Transfer/sec:`; anything between
+/// wrk runs (e.g. `cargo bench` output) becomes its own segment. Blank segments are dropped.
+pub fn split(output: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut current = Segment {
+        first_line: 1,
+        text: String::new(),
+        is_wrk: false,
+    };
+
+    for (idx, line) in output.lines().enumerate() {
+        let trimmed = line.trim();
+        let starts_wrk = calibration::is_wrk_command(trimmed)
+            || (trimmed.starts_with("Running") && trimmed.contains("test @"));
+
+        // A command line already opened this wrk segment; its Running line stays with it.
+        let continues_command = current.is_wrk && !current.text.contains("test @");
+        if starts_wrk && !continues_command {
+            push_segment(&mut segments, &mut current, idx + 1, true);
+        }
+
+        current.text.push_str(line);
+        current.text.push('\n');
+
+        if current.is_wrk && trimmed.starts_with("Transfer/sec:") {
+            push_segment(&mut segments, &mut current, idx + 2, false);
+        }
+    }
+    push_segment(&mut segments, &mut current, 0, false);
+
+    segments
+}
+
+/// Finishes `current` and starts a new segment at `next_line`.
+fn push_segment(
+    segments: &mut Vec<Segment>,
+    current: &mut Segment,
+    next_line: usize,
+    is_wrk: bool,
+) {
+    let finished = std::mem::replace(
+        current,
+        Segment {
+            first_line: next_line,
+            text: String::new(),
+            is_wrk,
+        },
+    );
+    if !finished.text.trim().is_empty() {
+        segments.push(finished);
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    const MIXED: &str = include_str!("fixtures/mixed_criterion_wrk.txt");
+
+    #[test]
+    fn split_single_format() {
+        let input = include_str!("fixtures/wrk_multiple_tests.txt");
+        let segments = split(input);
+        assert!(segments.iter().all(|s| s.is_wrk));
+
+        let input = include_str!("fixtures/criterion_cli_simple.txt");
+        let segments = split(input);
+        assert_eq!(segments.len(), 1);
+        assert!(!segments[0].is_wrk);
+        assert_eq!(segments[0].first_line, 1);
+    }
+
+    #[test]
+    fn split_mixed_log() {
+        let segments = split(MIXED);
+        assert_eq!(segments.len(), 3);
+
+        assert!(!segments[0].is_wrk);
+        assert!(segments[0].text.contains("fib/20"));
+
+        assert!(segments[1].is_wrk);
+        assert!(segments[1].text.starts_with("$ wrk"));
+        assert!(segments[1]
+            .text
+            .trim_end()
+            .ends_with("Transfer/sec:    656.56KB"));
+        assert_eq!(
+            MIXED.lines().nth(segments[1].first_line - 1).unwrap(),
+            "$ wrk -t2 -c100 -d10s --latency http://localhost:8080"
+        );
+
+        assert!(!segments[2].is_wrk);
+        assert!(segments[2].text.contains("sort/1000"));
+    }
+
+    #[test]
+    fn split_empty() {
+        assert!(split("").is_empty());
+        assert!(split("\n  \n").is_empty());
+    }
+}
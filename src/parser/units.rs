@@ -1,32 +1,85 @@
-fn split_num_unit(value: &str) -> (f64, String) {
+fn split_num_unit(value: &str) -> Result<(f64, String), String> {
     let value = value.trim();
     let (num_str, unit): (String, String) =
         value.chars().partition(|c| c.is_ascii_digit() || *c == '.');
-    (
-        num_str.parse::<f64>().unwrap_or(0.0),
-        unit.trim().to_lowercase(),
-    )
+    let num = num_str
+        .parse::<f64>()
+        .map_err(|_| format!("`{value}` is not a number"))?;
+    Ok((num, unit.trim().to_lowercase()))
 }
 
-pub fn parse_to_milliseconds(value: &str) -> f64 {
-    let (num, unit) = split_num_unit(value);
+/// Parses a wrk duration (`6.60ms`, `350.00us`, `1.50m`) into milliseconds; a bare
+/// number is already in milliseconds.
+pub fn parse_to_milliseconds(value: &str) -> Result<f64, String> {
+    let (num, unit) = split_num_unit(value)?;
     match unit.as_str() {
-        "s" => num * 1000.0,
-        "us" => num / 1000.0,
-        _ => num,
+        "h" => Ok(num * 3_600_000.0),
+        "m" => Ok(num * 60_000.0),
+        "s" | "sec" => Ok(num * 1000.0),
+        "" | "ms" | "msec" => Ok(num),
+        "us" | "usec" => Ok(num / 1000.0),
+        _ => Err(format!("unknown time unit in `{}`", value.trim())),
     }
 }
 
-pub fn parse_count(value: &str) -> f64 {
-    let (num, unit) = split_num_unit(value);
+/// Parses Go `time.Duration` strings as printed by Vegeta and k6 (`1.921ms`, `30.001s`,
+/// `1m0.5s`, `812.3µs`) into milliseconds.
+pub fn parse_go_duration(value: &str) -> Result<f64, String> {
+    let value = value.trim();
+    for (unit, factor) in [('h', 3_600_000.0), ('m', 60_000.0)] {
+        if let Some((lead, rest)) = value
+            .split_once(unit)
+            .filter(|(_, rest)| !rest.is_empty() && !rest.starts_with('s'))
+        {
+            let lead = lead
+                .parse::<f64>()
+                .map_err(|_| format!("`{value}` is not a duration"))?;
+            return Ok(lead * factor + parse_go_duration(rest)?);
+        }
+    }
+    if let Some(ns) = value.strip_suffix("ns") {
+        return ns
+            .parse::<f64>()
+            .map(|ns| ns / 1_000_000.0)
+            .map_err(|_| format!("`{value}` is not a duration"));
+    }
+    parse_to_milliseconds(&value.replace('µ', "u"))
+}
+
+/// Parses a count with wrk's `k`/`M` suffixes, e.g. `56.20k`.
+pub fn parse_count(value: &str) -> Result<f64, String> {
+    let (num, unit) = split_num_unit(value)?;
     match unit.as_str() {
-        "m" => num * 1_000_000.0,
-        "k" => num * 1000.0,
-        _ => num,
+        "m" => Ok(num * 1_000_000.0),
+        "k" => Ok(num * 1000.0),
+        "" => Ok(num),
+        _ => Err(format!("unknown count suffix in `{}`", value.trim())),
     }
 }
 
+/// Parses a percentage such as wrk's `+/- Stdev` column (`68.46%`).
+pub fn parse_percent(value: &str) -> Result<f64, String> {
+    value
+        .trim()
+        .trim_end_matches('%')
+        .parse()
+        .map_err(|_| format!("`{}` is not a percentage", value.trim()))
+}
+
+/// Formats a byte count the way wrk prints transfer sizes, e.g. `656.56KB`.
+pub fn format_bytes(bytes: f64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.2}{}", UNITS[unit])
+}
+
 #[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
 mod tests {
     use super::*;
 
@@ -40,23 +93,68 @@ mod tests {
 
     #[test]
     fn test_parse_to_milliseconds() {
-        assert_float_eq(parse_to_milliseconds("2s"), 2000.0);
-        assert_float_eq(parse_to_milliseconds("500ms"), 500.0);
-        assert_float_eq(parse_to_milliseconds("1000us"), 1.0);
-        assert_float_eq(parse_to_milliseconds("42"), 42.0);
-        assert_float_eq(parse_to_milliseconds("bad"), 0.0);
-        assert_float_eq(parse_to_milliseconds("  3.5s  "), 3500.0);
-        assert_float_eq(parse_to_milliseconds("10MS"), 10.0); // case insensitive
-        assert_float_eq(parse_to_milliseconds("68.46%"), 68.46);
+        assert_float_eq(parse_to_milliseconds("2s").unwrap(), 2000.0);
+        assert_float_eq(parse_to_milliseconds("500ms").unwrap(), 500.0);
+        assert_float_eq(parse_to_milliseconds("1000us").unwrap(), 1.0);
+        assert_float_eq(parse_to_milliseconds("42").unwrap(), 42.0);
+        assert_float_eq(parse_to_milliseconds("  3.5s  ").unwrap(), 3500.0);
+        assert_float_eq(parse_to_milliseconds("10MS").unwrap(), 10.0); // case insensitive
+
+        // wrk2 calibration lines spell units out
+        assert_float_eq(parse_to_milliseconds("9747 usec").unwrap(), 9.747);
+        assert_float_eq(parse_to_milliseconds("21 msec").unwrap(), 21.0);
+        assert_float_eq(parse_to_milliseconds("2 sec").unwrap(), 2000.0);
+        // wrk switches to minutes and hours for very slow requests
+        assert_float_eq(parse_to_milliseconds("1.50m").unwrap(), 90_000.0);
+        assert_float_eq(parse_to_milliseconds("1h").unwrap(), 3_600_000.0);
+    }
+
+    #[test]
+    fn test_parse_to_milliseconds_errors() {
+        assert_eq!(
+            parse_to_milliseconds("bad"),
+            Err("`bad` is not a number".to_string())
+        );
+        assert_eq!(
+            parse_to_milliseconds("3.2ns"),
+            Err("unknown time unit in `3.2ns`".to_string())
+        );
+        assert!(parse_to_milliseconds("68.46%").is_err());
+    }
+
+    #[test]
+    fn test_parse_go_duration() {
+        assert_float_eq(parse_go_duration("1.921ms").unwrap(), 1.921);
+        assert_float_eq(parse_go_duration("30.001s").unwrap(), 30_001.0);
+        assert_float_eq(parse_go_duration("812.3µs").unwrap(), 0.8123);
+        assert_float_eq(parse_go_duration("1m0.5s").unwrap(), 60_500.0);
+        assert_float_eq(parse_go_duration("1h2m3s").unwrap(), 3_723_000.0);
+        assert_float_eq(parse_go_duration("250ns").unwrap(), 0.00025);
+        assert!(parse_go_duration("1.2MB").is_err());
+        assert!(parse_go_duration("xm3s").is_err());
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512.0), "512.00B");
+        assert_eq!(format_bytes(672_317.0), "656.56KB");
+        assert_eq!(format_bytes(1_688_207.0), "1.61MB");
     }
 
     #[test]
     fn test_parse_count() {
-        assert_float_eq(parse_count("56.20k"), 56200.0);
-        assert_float_eq(parse_count("8.07k"), 8070.0);
-        assert_float_eq(parse_count("62.00k"), 62000.0);
-        assert_float_eq(parse_count("86.54%"), 86.54);
-        assert_float_eq(parse_count("1.5M"), 1_500_000.0);
-        assert_float_eq(parse_count("2.5"), 2.5);
+        assert_float_eq(parse_count("56.20k").unwrap(), 56200.0);
+        assert_float_eq(parse_count("8.07k").unwrap(), 8070.0);
+        assert_float_eq(parse_count("62.00k").unwrap(), 62000.0);
+        assert_float_eq(parse_count("1.5M").unwrap(), 1_500_000.0);
+        assert_float_eq(parse_count("2.5").unwrap(), 2.5);
+        assert!(parse_count("86.54%").is_err());
+    }
+
+    #[test]
+    fn test_parse_percent() {
+        assert_float_eq(parse_percent("86.54%").unwrap(), 86.54);
+        assert_float_eq(parse_percent("75").unwrap(), 75.0);
+        assert!(parse_percent("n/a").is_err());
     }
 }
@@ -0,0 +1,473 @@
+use super::{is_empty, units, Diagnostic};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Latency summary from a Vegeta report, in milliseconds.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct VegetaLatencies {
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub min: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub mean: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub p50: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub p90: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub p95: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub p99: f64,
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub max: f64,
+}
+
+impl VegetaLatencies {
+    /// Percentiles keyed like wrk's latency distribution (`"50%"`), for the shared latency panel.
+    pub fn distribution(&self) -> HashMap<String, f64> {
+        [
+            ("50%", self.p50),
+            ("90%", self.p90),
+            ("95%", self.p95),
+            ("99%", self.p99),
+        ]
+        .into_iter()
+        .filter(|(_, v)| *v > 0.0)
+        .map(|(k, v)| (k.to_string(), v))
+        .collect()
+    }
+}
+
+/// Results of one `vegeta attack`, from `vegeta report` text or `-type=json` output.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct VegetaMetrics {
+    #[serde(default, skip_serializing_if = "is_empty::check_u64")]
+    pub requests: u64,
+    /// Rate requests were sent at, per second
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub rate: f64,
+    /// Successful requests per second
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub throughput: f64,
+    /// Total duration including the final wait, in seconds
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub duration: f64,
+    /// Time spent waiting for the last responses after the attack ended, in milliseconds
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub wait: f64,
+    #[serde(default)]
+    pub latencies: VegetaLatencies,
+    #[serde(default, skip_serializing_if = "is_empty::check_u64")]
+    pub bytes_in: u64,
+    #[serde(default, skip_serializing_if = "is_empty::check_u64")]
+    pub bytes_out: u64,
+    /// Fraction of requests with a 2xx/3xx response, 0.0–1.0
+    #[serde(default, skip_serializing_if = "is_empty::check_f64")]
+    pub success: f64,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub status_codes: BTreeMap<u16, u64>,
+    /// Distinct error messages (Vegeta's error set)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+impl VegetaMetrics {
+    /// Received bytes per second, formatted like wrk's `Transfer/sec`.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn transfer_per_sec(&self) -> String {
+        if self.duration > 0.0 && self.bytes_in > 0 {
+            units::format_bytes(self.bytes_in as f64 / self.duration)
+        } else {
+            String::new()
+        }
+    }
+}
+
+/// Confidence (0.0–1.0) that `output` is a `vegeta report` text report.
+pub fn text_confidence(output: &str) -> f64 {
+    let signals = [
+        (
+            0.4,
+            output.contains("Requests") && output.contains("[total, rate"),
+        ),
+        (
+            0.2,
+            output.contains("Latencies") && output.contains("[min, mean")
+                || output.contains("Latencies") && output.contains("[mean, 50"),
+        ),
+        (
+            0.2,
+            output.contains("Success") && output.contains("[ratio]"),
+        ),
+        (
+            0.2,
+            output.contains("Status Codes") && output.contains("[code:count]"),
+        ),
+    ];
+    signals
+        .iter()
+        .filter(|(_, present)| *present)
+        .map(|(weight, _)| weight)
+        .sum()
+}
+
+/// Confidence (0.0–1.0) that `output` is `vegeta report -type=json` output.
+pub fn json_confidence(output: &str) -> f64 {
+    let trimmed = output.trim();
+    if !trimmed.starts_with('{') {
+        return 0.0;
+    }
+    let signals = [
+        (0.4, trimmed.contains("\"latencies\"")),
+        (0.2, trimmed.contains("\"bytes_in\"")),
+        (0.2, trimmed.contains("\"success\"")),
+        (0.2, trimmed.contains("\"throughput\"")),
+    ];
+    signals
+        .iter()
+        .filter(|(_, present)| *present)
+        .map(|(weight, _)| weight)
+        .sum()
+}
+
+/// Parses one or more `vegeta report` text reports, each starting at its `Requests` line.
+/// Values that can't be read are reported in `warnings`.
+pub fn parse_text(output: &str, warnings: &mut Vec<Diagnostic>) -> Vec<VegetaMetrics> {
+    let mut reports: Vec<VegetaMetrics> = Vec::new();
+    let mut in_error_set = false;
+
+    for (number, line) in output.lines().map(str::trim).enumerate() {
+        if line.starts_with("Requests") && line.contains('[') {
+            reports.push(VegetaMetrics::default());
+            in_error_set = false;
+        }
+        let Some(report) = reports.last_mut() else {
+            continue;
+        };
+
+        if in_error_set {
+            if !line.is_empty() {
+                report.errors.push(line.to_string());
+            }
+            continue;
+        }
+        if line.starts_with("Error Set:") {
+            in_error_set = true;
+            continue;
+        }
+
+        let Some(row) = ReportRow::parse(number + 1, line) else {
+            continue;
+        };
+        let number = |field: &str, warnings: &mut Vec<Diagnostic>| {
+            row.read(field, |v| v.parse::<f64>().ok(), warnings)
+                .unwrap_or(0.0)
+        };
+        let millis = |field: &str, warnings: &mut Vec<Diagnostic>| {
+            row.read(field, |v| units::parse_go_duration(v).ok(), warnings)
+                .unwrap_or(0.0)
+        };
+
+        match row.label {
+            "Requests" => {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let requests = number("total", warnings) as u64;
+                report.requests = requests;
+                report.rate = number("rate", warnings);
+                report.throughput = number("throughput", warnings);
+            }
+            "Duration" => {
+                report.duration = millis("total", warnings) / 1000.0;
+                report.wait = millis("wait", warnings);
+            }
+            "Latencies" => {
+                report.latencies = VegetaLatencies {
+                    min: millis("min", warnings),
+                    mean: millis("mean", warnings),
+                    p50: millis("50", warnings),
+                    p90: millis("90", warnings),
+                    p95: millis("95", warnings),
+                    p99: millis("99", warnings),
+                    max: millis("max", warnings),
+                };
+            }
+            "Bytes In" => {
+                report.bytes_in = row.read("total", |v| v.parse().ok(), warnings).unwrap_or(0);
+            }
+            "Bytes Out" => {
+                report.bytes_out = row.read("total", |v| v.parse().ok(), warnings).unwrap_or(0);
+            }
+            "Success" => {
+                report.success = row
+                    .read("ratio", |v| units::parse_percent(v).ok(), warnings)
+                    .map_or(0.0, |p| p / 100.0);
+            }
+            "Status Codes" => {
+                for pair in row.values.iter().flat_map(|v| v.split_whitespace()) {
+                    let Some((code, count)) = pair
+                        .split_once(':')
+                        .and_then(|(code, count)| Some((code.parse().ok()?, count.parse().ok()?)))
+                    else {
+                        warnings.push(Diagnostic::at_line(
+                            row.number,
+                            format!("status code `{pair}` skipped: expected `code:count`"),
+                        ));
+                        continue;
+                    };
+                    report.status_codes.insert(code, count);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    reports
+}
+
+/// One `Latencies     [min, mean, 50]  1.2ms, 2.3ms, 2.0ms` line of a text report: its
+/// label, bracketed field names and comma separated values.
+struct ReportRow<'a> {
+    number: usize,
+    label: &'a str,
+    fields: Vec<&'a str>,
+    values: Vec<&'a str>,
+}
+
+impl<'a> ReportRow<'a> {
+    fn parse(number: usize, line: &'a str) -> Option<Self> {
+        let (label, rest) = line.split_once('[')?;
+        let (fields, values) = rest.split_once(']')?;
+        let fields = fields.split(',').map(str::trim).collect();
+        let values = if fields_are_pairs(values) {
+            vec![values.trim()]
+        } else {
+            values.split(',').map(str::trim).collect()
+        };
+        Some(Self {
+            number,
+            label: label.trim(),
+            fields,
+            values,
+        })
+    }
+
+    /// The value of `field` read by `parse`. A field the report doesn't have is `None`;
+    /// one whose value can't be read is also reported in `warnings`.
+    fn read<T>(
+        &self,
+        field: &str,
+        parse: impl FnOnce(&str) -> Option<T>,
+        warnings: &mut Vec<Diagnostic>,
+    ) -> Option<T> {
+        let value = self
+            .fields
+            .iter()
+            .position(|f| *f == field)
+            .and_then(|i| self.values.get(i))?;
+        let parsed = parse(value);
+        if parsed.is_none() {
+            warnings.push(Diagnostic::at_line(
+                self.number,
+                format!("can't read {} `{field}` value `{value}`", self.label),
+            ));
+        }
+        parsed
+    }
+}
+
+/// Status codes are space separated `code:count` pairs rather than a comma separated list.
+fn fields_are_pairs(values: &str) -> bool {
+    values.contains(':') && !values.contains(',')
+}
+
+/// Parses `vegeta report -type=json` output. Several reports may be pasted one per line;
+/// lines that aren't a report are reported in `warnings`.
+pub fn parse_json(output: &str, warnings: &mut Vec<Diagnostic>) -> Vec<VegetaMetrics> {
+    let trimmed = output.trim();
+    if let Some(report) = parse_json_report(trimmed) {
+        return vec![report];
+    }
+    output
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .filter_map(|(number, line)| {
+            let report = parse_json_report(line);
+            if report.is_none() {
+                warnings.push(Diagnostic::at_line(
+                    number + 1,
+                    "line skipped: not a `vegeta report -type=json` report",
+                ));
+            }
+            report
+        })
+        .collect()
+}
+
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn parse_json_report(text: &str) -> Option<VegetaMetrics> {
+    let value: serde_json::Value = serde_json::from_str(text.trim()).ok()?;
+    let obj = value.as_object()?;
+    let latencies = obj.get("latencies")?.as_object()?;
+
+    let f64_of =
+        |v: Option<&serde_json::Value>| v.and_then(serde_json::Value::as_f64).unwrap_or(0.0);
+    // Vegeta encodes durations as nanoseconds
+    let ns_to_ms = |key: &str| f64_of(latencies.get(key)) / 1_000_000.0;
+    let bytes_total = |key: &str| {
+        obj.get(key)
+            .and_then(|b| b.get("total"))
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(0)
+    };
+
+    Some(VegetaMetrics {
+        requests: obj
+            .get("requests")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or(0),
+        rate: f64_of(obj.get("rate")),
+        throughput: f64_of(obj.get("throughput")),
+        duration: (f64_of(obj.get("duration")) + f64_of(obj.get("wait"))) / 1_000_000_000.0,
+        wait: f64_of(obj.get("wait")) / 1_000_000.0,
+        latencies: VegetaLatencies {
+            min: ns_to_ms("min"),
+            mean: ns_to_ms("mean"),
+            p50: ns_to_ms("50th"),
+            p90: ns_to_ms("90th"),
+            p95: ns_to_ms("95th"),
+            p99: ns_to_ms("99th"),
+            max: ns_to_ms("max"),
+        },
+        bytes_in: bytes_total("bytes_in"),
+        bytes_out: bytes_total("bytes_out"),
+        success: f64_of(obj.get("success")),
+        status_codes: obj
+            .get("status_codes")
+            .and_then(serde_json::Value::as_object)
+            .map(|codes| {
+                codes
+                    .iter()
+                    .filter_map(|(code, count)| Some((code.parse().ok()?, count.as_u64()?)))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        errors: obj
+            .get("errors")
+            .and_then(serde_json::Value::as_array)
+            .map(|errors| {
+                errors
+                    .iter()
+                    .filter_map(|e| e.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::expect_used)]
+mod tests {
+    use super::*;
+
+    fn assert_float_eq(a: f64, b: f64) {
+        const EPSILON: f64 = 1e-6;
+        assert!(
+            (a - b).abs() < EPSILON,
+            "Expected {a} to be approximately equal to {b}"
+        );
+    }
+
+    const VEGETA_TEXT: &str = include_str!("fixtures/vegeta_report.txt");
+    const VEGETA_JSON: &str = include_str!("fixtures/vegeta_report.json");
+
+    #[test]
+    fn parse_text_report() {
+        let reports = parse_text(VEGETA_TEXT, &mut Vec::new());
+        assert_eq!(reports.len(), 1);
+        let report = &reports[0];
+
+        assert_eq!(report.requests, 30000);
+        assert_float_eq(report.rate, 1000.03);
+        assert_float_eq(report.throughput, 999.71);
+        assert_float_eq(report.duration, 30.001);
+        assert_float_eq(report.wait, 1.921);
+        assert_float_eq(report.latencies.min, 1.218);
+        assert_float_eq(report.latencies.p99, 6.208);
+        assert_float_eq(report.latencies.max, 48.351);
+        assert_eq!(report.bytes_in, 3_420_000);
+        assert_float_eq(report.success, 0.9997);
+        assert_eq!(report.status_codes.get(&200), Some(&29990));
+        assert_eq!(report.status_codes.get(&503), Some(&10));
+        assert_eq!(report.errors, vec!["503 Service Unavailable"]);
+    }
+
+    #[test]
+    fn parse_json_report_matches_text() {
+        let reports = parse_json(VEGETA_JSON, &mut Vec::new());
+        assert_eq!(reports.len(), 1);
+        let json = &reports[0];
+        let text = &parse_text(VEGETA_TEXT, &mut Vec::new())[0];
+
+        assert_eq!(json.requests, text.requests);
+        assert_eq!(json.status_codes, text.status_codes);
+        assert_eq!(json.errors, text.errors);
+        assert_float_eq(json.latencies.p50, text.latencies.p50);
+        assert_float_eq(json.latencies.max, text.latencies.max);
+        assert_float_eq(json.success, text.success);
+        // the text report rounds durations
+        assert_float_eq(json.duration, 30.000_921);
+        assert_float_eq(json.wait, text.wait);
+    }
+
+    #[test]
+    fn parse_text_warnings() {
+        let mut warnings = Vec::new();
+        parse_text(VEGETA_TEXT, &mut warnings);
+        assert!(warnings.is_empty());
+
+        let broken = VEGETA_TEXT
+            .replace("6.208ms", "6.208ps")
+            .replace("503:10", "503=10");
+        let report = &parse_text(&broken, &mut warnings)[0];
+        let rendered: Vec<_> = warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "line 4: can't read Latencies `99` value `6.208ps`",
+                "line 8: status code `503=10` skipped: expected `code:count`",
+            ]
+        );
+        assert_float_eq(report.latencies.p99, 0.0);
+        assert_eq!(report.status_codes.len(), 1);
+    }
+
+    #[test]
+    fn parse_json_warns_on_skipped_lines() {
+        let line = VEGETA_JSON.lines().map(str::trim).collect::<String>();
+        let mut warnings = Vec::new();
+        let reports = parse_json(&format!("{line}\nnot json\n{line}"), &mut warnings);
+        assert_eq!(reports.len(), 2);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].line, Some(2));
+    }
+
+    #[test]
+    fn latency_distribution_keys() {
+        let report = &parse_text(VEGETA_TEXT, &mut Vec::new())[0];
+        let distribution = report.latencies.distribution();
+        assert_eq!(distribution.len(), 4);
+        assert_float_eq(distribution["95%"], 3.587);
+    }
+
+    #[test]
+    fn confidence_by_format() {
+        assert!((text_confidence(VEGETA_TEXT) - 1.0).abs() < 1e-9);
+        assert!((json_confidence(VEGETA_JSON) - 1.0).abs() < 1e-9);
+        assert!(json_confidence(VEGETA_TEXT).abs() < 1e-9);
+        assert!(text_confidence(include_str!("fixtures/wrk1_basic.txt")).abs() < 1e-9);
+    }
+}
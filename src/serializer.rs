@@ -1,4 +1,5 @@
-use crate::parser::{self, CriterionMetrics, PercentileBucket};
+use crate::parser::criterion::ConfidenceInterval;
+use crate::parser::{self, CriterionMetrics, LatencyInterval, PercentileBucket};
 use base64::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::io::Read;
@@ -33,6 +34,14 @@ pub struct Loadtest {
     pub tests: Vec<parser::WrkMetrics>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub benchmarks: Vec<parser::CriterionMetrics>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub vegeta: Vec<parser::VegetaMetrics>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub k6: Vec<parser::K6Metrics>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub machine: Option<parser::MachineInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<parser::CommitInfo>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -63,6 +72,13 @@ const MAX_PERCENTILE_BUCKETS: usize = 25;
 /// Maximum number of criterion sample points to keep when encoding for URL sharing.
 const MAX_CRITERION_SAMPLES: usize = 50;
 
+/// Maximum number of `HdrHistogram` log intervals to keep when encoding for URL sharing.
+const MAX_LATENCY_INTERVALS: usize = 60;
+
+/// Maximum number of bootstrap quantiles to keep per statistic when encoding for URL
+/// sharing. The distribution chart smooths them with a KDE, so a few keep the shape.
+const MAX_DISTRIBUTION_POINTS: usize = 9;
+
 /// Downsample percentile buckets using logarithmic spacing.
 ///
 /// Percentile data is log-distributed (most interesting detail is in the tail:
@@ -103,6 +119,27 @@ fn downsample_percentiles(buckets: &[PercentileBucket]) -> Vec<PercentileBucket>
     indices.iter().map(|&i| buckets[i].clone()).collect()
 }
 
+/// Merge runs of adjacent log intervals. Percentiles can't be combined exactly, so each
+/// merged interval keeps the worst value seen, which keeps latency spikes visible.
+fn downsample_intervals(intervals: &[LatencyInterval]) -> Vec<LatencyInterval> {
+    if intervals.len() <= MAX_LATENCY_INTERVALS {
+        return intervals.to_vec();
+    }
+
+    intervals
+        .chunks(intervals.len().div_ceil(MAX_LATENCY_INTERVALS))
+        .map(|chunk| LatencyInterval {
+            start: chunk[0].start,
+            length: chunk.iter().map(|i| i.length).sum(),
+            count: chunk.iter().map(|i| i.count).sum(),
+            p50: chunk.iter().map(|i| i.p50).fold(0.0, f64::max),
+            p90: chunk.iter().map(|i| i.p90).fold(0.0, f64::max),
+            p99: chunk.iter().map(|i| i.p99).fold(0.0, f64::max),
+            max: chunk.iter().map(|i| i.max).fold(0.0, f64::max),
+        })
+        .collect()
+}
+
 fn downsample_samples(iteration_count: &[f64], measured_values: &[f64]) -> (Vec<f64>, Vec<f64>) {
     let n = iteration_count.len();
     if n <= MAX_CRITERION_SAMPLES {
@@ -118,47 +155,118 @@ fn downsample_samples(iteration_count: &[f64], measured_values: &[f64]) -> (Vec<
     (iters, values)
 }
 
+/// Evenly spaced quantiles picked from a sorted bootstrap distribution.
+fn thin_distribution(distribution: &[f64]) -> Vec<f64> {
+    let n = distribution.len();
+    if n <= MAX_DISTRIBUTION_POINTS {
+        return distribution.to_vec();
+    }
+    (0..MAX_DISTRIBUTION_POINTS)
+        .map(|i| distribution[i * (n - 1) / (MAX_DISTRIBUTION_POINTS - 1)])
+        .collect()
+}
+
+/// The statistics drawn as bootstrap distributions on the benchmark card.
+fn stat_intervals(m: &mut CriterionMetrics) -> impl Iterator<Item = &mut ConfidenceInterval> {
+    [
+        &mut m.slope,
+        &mut m.mean,
+        &mut m.median,
+        &mut m.std_dev,
+        &mut m.median_abs_dev,
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// Drops every bootstrap distribution, including the baseline's; the charts then draw
+/// a normal curve from each interval.
+fn drop_distributions(m: &mut CriterionMetrics) {
+    for ci in stat_intervals(m) {
+        ci.distribution.clear();
+    }
+    if let Some(baseline) = &mut m.baseline {
+        drop_distributions(baseline);
+    }
+}
+
 fn compact_criterion(mut m: CriterionMetrics) -> CriterionMetrics {
     if m.iteration_count.len() > MAX_CRITERION_SAMPLES {
         let (iters, values) = downsample_samples(&m.iteration_count, &m.measured_values);
         m.iteration_count = iters;
         m.measured_values = values;
     }
-    if let Some(ref mut baseline) = m.baseline {
-        if baseline.iteration_count.len() > MAX_CRITERION_SAMPLES {
-            let (iters, values) =
-                downsample_samples(&baseline.iteration_count, &baseline.measured_values);
-            baseline.iteration_count = iters;
-            baseline.measured_values = values;
-        }
+    // `time` repeats the slope or mean, and only the statistics get a distribution chart
+    m.time.distribution.clear();
+    for ci in stat_intervals(&mut m) {
+        ci.distribution = thin_distribution(&ci.distribution);
     }
+    m.baseline = m
+        .baseline
+        .map(|baseline| Box::new(compact_criterion(*baseline)));
     m
 }
 
-pub fn encode_dashboard(data: &str, desc: String, tags: Vec<String>) -> Result<String, Error> {
-    let results = parser::parse_input(data);
-
+/// Parses `data` (as `format`, or auto-detected when `None`) and encodes it for the URL hash.
+pub fn encode_dashboard(
+    data: &str,
+    format: Option<parser::InputFormat>,
+    desc: String,
+    tags: Vec<String>,
+) -> Result<String, Error> {
+    let (results, machine, commit) = parser::parse_report(data, format)
+        .map(|report| (report.results, report.machine, report.commit))
+        .unwrap_or_default();
     let mut tests = Vec::new();
     let mut benchmarks = Vec::new();
+    let mut vegeta = Vec::new();
+    let mut k6 = Vec::new();
 
     for result in results {
         match result {
             parser::BenchmarkResult::Wrk(mut m) => {
                 m.percentiles = downsample_percentiles(&m.percentiles);
+                m.uncorrected_percentiles = downsample_percentiles(&m.uncorrected_percentiles);
+                m.intervals = downsample_intervals(&m.intervals);
                 tests.push(*m);
             }
             parser::BenchmarkResult::Criterion(m) => benchmarks.push(compact_criterion(*m)),
+            parser::BenchmarkResult::Vegeta(m) => vegeta.push(*m),
+            parser::BenchmarkResult::K6(m) => k6.push(*m),
         }
     }
 
     let description = if desc.is_empty() { None } else { Some(desc) };
-    let data_obj = Loadtest {
+    encode_fitting(Loadtest {
         tests,
         benchmarks,
+        vegeta,
+        k6,
+        machine,
+        commit,
         description,
         tags,
-    };
+    })
+}
+
+/// Encodes `data_obj`, dropping the bootstrap distributions when they would push the
+/// hash past [`MAX_HASH_LENGTH`].
+fn encode_fitting(mut data_obj: Loadtest) -> Result<String, Error> {
+    match encode(&data_obj) {
+        Err(Error::UrlTooLong { .. })
+            if data_obj
+                .benchmarks
+                .iter_mut()
+                .any(|m| stat_intervals(m).any(|ci| !ci.distribution.is_empty())) =>
+        {
+            data_obj.benchmarks.iter_mut().for_each(drop_distributions);
+            encode(&data_obj)
+        }
+        result => result,
+    }
+}
 
+fn encode(data_obj: &Loadtest) -> Result<String, Error> {
     let mut buf = Vec::new();
     data_obj.serialize(&mut rmp_serde::Serializer::new(&mut buf).with_struct_map())?;
 
@@ -195,7 +303,7 @@ mod test {
     fn test_encode_decode() {
         let description = "Test description".to_string();
         let tags = vec!["tag1".to_string(), "tag2".to_string()];
-        let hash = encode_dashboard(SAMPLE_INPUT, description.clone(), tags.clone()).unwrap();
+        let hash = encode_dashboard(SAMPLE_INPUT, None, description.clone(), tags.clone()).unwrap();
         let decoded = decode_dashboard(&hash).unwrap();
         assert_eq!(decoded.tests[0].endpoint, "http://localhost:8080");
         assert!(decoded.benchmarks.is_empty());
@@ -203,16 +311,264 @@ mod test {
         assert_eq!(decoded.tags, tags);
     }
 
+    #[test]
+    fn test_encode_decode_errors() {
+        let input = include_str!("parser/fixtures/wrk_socket_errors.txt");
+        let hash = encode_dashboard(input, None, String::new(), vec![]).unwrap();
+        let decoded = decode_dashboard(&hash).unwrap();
+        let errors = &decoded.tests[0].errors;
+        assert_eq!(errors.read, 152);
+        assert_eq!(errors.timeout, 87);
+        assert_eq!(errors.non_2xx_3xx, 37962);
+    }
+
+    #[test]
+    fn test_encode_decode_target_rate() {
+        let input = include_str!("parser/fixtures/wrk2_with_command.txt");
+        let hash = encode_dashboard(input, None, String::new(), vec![]).unwrap();
+        let decoded = decode_dashboard(&hash).unwrap();
+        assert_eq!(decoded.tests.len(), 2);
+        let test = &decoded.tests[1];
+        assert!((test.target_rate - 20000.0).abs() < f64::EPSILON);
+        assert_eq!(test.calibration.len(), 2);
+        assert!((test.calibration[0].sampling_interval - 21.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_encode_decode_mixed_input() {
+        let input = include_str!("parser/fixtures/mixed_criterion_wrk.txt");
+        let hash = encode_dashboard(input, None, String::new(), vec![]).unwrap();
+        let decoded = decode_dashboard(&hash).unwrap();
+        assert_eq!(decoded.tests.len(), 1);
+        assert_eq!(decoded.tests[0].endpoint, "http://localhost:8080");
+        assert_eq!(decoded.benchmarks.len(), 2);
+    }
+
+    #[test]
+    fn test_encode_decode_hey() {
+        let input = include_str!("parser/fixtures/hey_basic.txt");
+        let hash = encode_dashboard(input, None, String::new(), vec![]).unwrap();
+        let decoded = decode_dashboard(&hash).unwrap();
+        let test = &decoded.tests[0];
+        assert_eq!(test.status_codes.get(&503), Some(&431));
+        assert_eq!(test.phases.len(), 5);
+        assert_eq!(test.phases[0].name, "DNS+dialup");
+        assert_eq!(test.errors.connect, 12);
+    }
+
+    #[test]
+    fn test_encode_decode_ab() {
+        let input = include_str!("parser/fixtures/ab_basic.txt");
+        let hash = encode_dashboard(input, None, String::new(), vec![]).unwrap();
+        let decoded = decode_dashboard(&hash).unwrap();
+        let test = &decoded.tests[0];
+        assert_eq!(test.connection_times.len(), 4);
+        assert_eq!(test.connection_times[3].name, "Total");
+        assert_eq!(test.latency_distribution.len(), 9);
+    }
+
+    #[test]
+    fn test_encode_decode_oha() {
+        let input = include_str!("parser/fixtures/oha_basic.json");
+        let hash = encode_dashboard(input, None, String::new(), vec![]).unwrap();
+        let decoded = decode_dashboard(&hash).unwrap();
+        let test = &decoded.tests[0];
+        assert_eq!(test.percentiles.len(), 10);
+        assert!(test
+            .percentiles
+            .last()
+            .unwrap()
+            .inverse_percentile
+            .is_infinite());
+        assert_eq!(test.response_times.len(), 11);
+        assert_eq!(test.status_codes.get(&200), Some(&19800));
+    }
+
+    #[test]
+    fn test_encode_decode_hlog() {
+        let input = include_str!("parser/fixtures/hdr_latency.hlog");
+        let hash = encode_dashboard(input, None, String::new(), vec![]).unwrap();
+        assert!(hash.len() <= MAX_HASH_LENGTH);
+        let decoded = decode_dashboard(&hash).unwrap();
+        let test = &decoded.tests[0];
+        assert_eq!(test.intervals.len(), 6);
+        assert_eq!(test.intervals[3].count, 2000);
+        assert!(test.percentiles.len() <= MAX_PERCENTILE_BUCKETS);
+    }
+
+    #[test]
+    fn test_downsample_intervals_keeps_spikes() {
+        let intervals = (0..150)
+            .map(|i| LatencyInterval {
+                start: f64::from(i),
+                length: 1.0,
+                count: 10,
+                p99: if i == 77 { 500.0 } else { 5.0 },
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let merged = downsample_intervals(&intervals);
+        assert_eq!(merged.len(), 50);
+        assert_eq!(merged.iter().map(|i| i.count).sum::<u64>(), 1500);
+        assert!((merged[25].p99 - 500.0).abs() < 1e-9);
+        assert!((merged[25].length - 3.0).abs() < 1e-9);
+        assert!(downsample_intervals(&intervals[..10]).len() == 10);
+    }
+
+    #[test]
+    fn test_encode_decode_bombardier() {
+        let input = include_str!("parser/fixtures/bombardier_result.json");
+        let hash = encode_dashboard(input, None, String::new(), vec![]).unwrap();
+        let decoded = decode_dashboard(&hash).unwrap();
+        let test = &decoded.tests[0];
+        assert_eq!(test.endpoint, "http://localhost:8080/api/users");
+        assert_eq!(test.errors.connect, 27);
+        assert_eq!(test.latency_distribution.len(), 5);
+    }
+
+    #[test]
+    fn test_encode_decode_vegeta() {
+        let input = include_str!("parser/fixtures/vegeta_report.json");
+        let hash = encode_dashboard(input, None, String::new(), vec![]).unwrap();
+        let decoded = decode_dashboard(&hash).unwrap();
+        assert!(decoded.tests.is_empty());
+        let attack = &decoded.vegeta[0];
+        assert_eq!(attack.requests, 30000);
+        assert_eq!(attack.status_codes.get(&503), Some(&10));
+        assert_eq!(attack.errors, vec!["503 Service Unavailable"]);
+    }
+
+    #[test]
+    fn test_encode_decode_k6() {
+        let input = include_str!("parser/fixtures/k6_summary.txt");
+        let hash = encode_dashboard(input, None, String::new(), vec![]).unwrap();
+        let decoded = decode_dashboard(&hash).unwrap();
+        let summary = &decoded.k6[0];
+        assert_eq!(summary.trends.len(), 4);
+        assert_eq!(summary.thresholds.len(), 2);
+        assert!(!summary.thresholds[1].ok);
+        assert_eq!(summary.checks[1].fails, 30);
+    }
+
+    #[test]
+    fn test_encode_decode_percentile_spectrum() {
+        let input = include_str!("parser/fixtures/wrk2_full.txt");
+        let hash = encode_dashboard(input, None, String::new(), vec![]).unwrap();
+        let decoded = decode_dashboard(&hash).unwrap();
+        let test = &decoded.tests[0];
+        assert!(test.percentiles.len() <= MAX_PERCENTILE_BUCKETS);
+        let last = test.percentiles.last().unwrap();
+        assert_eq!(last.total_count, 99500);
+        assert!(last.inverse_percentile.is_infinite());
+        assert_eq!(test.histogram.total_count, 99500);
+        assert_eq!(test.histogram.sub_buckets, 2048);
+    }
+
     #[test]
     fn test_encode_decode_criterion() {
         let criterion_input = include_str!("parser/fixtures/criterion_cli_simple.txt");
-        let hash = encode_dashboard(criterion_input, String::new(), vec![]).unwrap();
+        let hash = encode_dashboard(criterion_input, None, String::new(), vec![]).unwrap();
         let decoded = decode_dashboard(&hash).unwrap();
         assert!(decoded.tests.is_empty());
         assert_eq!(decoded.benchmarks.len(), 1);
         assert_eq!(decoded.benchmarks[0].name, "fib/20");
     }
 
+    #[test]
+    fn test_encode_decode_criterion_sample_distributions() {
+        let input = include_str!("parser/fixtures/criterion_sample.json");
+        let hash = encode_dashboard(input, None, String::new(), vec![]).unwrap();
+        let decoded = decode_dashboard(&hash).unwrap();
+        let bench = &decoded.benchmarks[0];
+        let mean = bench.mean.as_ref().unwrap();
+        assert_eq!(mean.distribution.len(), MAX_DISTRIBUTION_POINTS);
+        assert!(mean.distribution.windows(2).all(|w| w[0] <= w[1]));
+        assert!(bench.time.distribution.is_empty());
+        assert!(mean.standard_error > 0.0);
+        assert!(bench.outliers.as_ref().unwrap().fences.is_some());
+    }
+
+    #[test]
+    fn test_encode_many_bootstrapped_benchmarks_fits() {
+        let sample: serde_json::Value =
+            serde_json::from_str(include_str!("parser/fixtures/criterion_sample.json")).unwrap();
+        let benchmarks = (1..=10)
+            .map(|i| {
+                let mut sample = sample.clone();
+                for time in sample["times"].as_array_mut().unwrap() {
+                    *time = (time.as_f64().unwrap() * (1.0 + f64::from(i) / 10.0)).into();
+                }
+                let mut bench = parser::criterion::parse_sample_json(&sample.to_string()).unwrap();
+                bench.name = format!("bench/{i}");
+                compact_criterion(bench)
+            })
+            .collect();
+        let hash = encode_fitting(Loadtest {
+            tests: vec![],
+            benchmarks,
+            vegeta: vec![],
+            k6: vec![],
+            machine: None,
+            commit: None,
+            description: None,
+            tags: vec![],
+        })
+        .unwrap();
+        assert!(hash.len() <= MAX_HASH_LENGTH);
+        let decoded = decode_dashboard(&hash).unwrap();
+        assert_eq!(decoded.benchmarks.len(), 10);
+        assert!(decoded.benchmarks[7]
+            .mean
+            .as_ref()
+            .unwrap()
+            .distribution
+            .is_empty());
+    }
+
+    #[test]
+    fn test_encode_decode_google_benchmark_machine() {
+        let input = include_str!("parser/fixtures/google_benchmark.json");
+        let hash = encode_dashboard(input, None, String::new(), vec![]).unwrap();
+        let decoded = decode_dashboard(&hash).unwrap();
+        assert_eq!(decoded.benchmarks.len(), 3);
+        let machine = decoded.machine.unwrap();
+        assert_eq!(machine.host, "ci-runner-12");
+        assert_eq!(machine.caches.len(), 4);
+    }
+
+    #[test]
+    fn test_encode_decode_go_bench_extra_metrics() {
+        let input = include_str!("parser/fixtures/go_bench.txt");
+        let hash = encode_dashboard(input, None, String::new(), vec![]).unwrap();
+        let decoded = decode_dashboard(&hash).unwrap();
+        let sort = &decoded.benchmarks[1];
+        assert_eq!(sort.extra_metrics.len(), 2);
+        assert_eq!(sort.extra_metrics[0].unit, "B/op");
+        assert_eq!(decoded.machine.unwrap().os, "linux/amd64");
+    }
+
+    #[test]
+    fn test_encode_decode_jmh_throughput() {
+        let input = include_str!("parser/fixtures/jmh.json");
+        let hash = encode_dashboard(input, None, String::new(), vec![]).unwrap();
+        let decoded = decode_dashboard(&hash).unwrap();
+        assert!(decoded.benchmarks[0].ops_per_sec.is_none());
+        assert!(decoded.benchmarks[3].ops_per_sec.is_some());
+        assert_eq!(decoded.benchmarks[0].extra_metrics[0].name, "gc.alloc.rate");
+    }
+
+    #[test]
+    fn test_encode_decode_pytest_benchmark_commit() {
+        let input = include_str!("parser/fixtures/pytest_benchmark.json");
+        let hash = encode_dashboard(input, None, String::new(), vec![]).unwrap();
+        let decoded = decode_dashboard(&hash).unwrap();
+        assert_eq!(decoded.benchmarks.len(), 3);
+        assert_eq!(decoded.machine.unwrap().runtime, "CPython 3.11.4");
+        let commit = decoded.commit.unwrap();
+        assert_eq!(commit.branch, "main");
+        assert!(commit.dirty);
+    }
+
     #[test]
     fn test_invalid_hash() {
         let invalid_hash = "invalid_base64";
@@ -227,6 +583,10 @@ mod test {
         let data_obj = Loadtest {
             tests: vec![],
             benchmarks: vec![],
+            vegeta: vec![],
+            k6: vec![],
+            machine: None,
+            commit: None,
             description: Some("legacy".to_string()),
             tags: vec![],
         };
@@ -246,7 +606,7 @@ mod test {
 
     #[test]
     fn test_brotli_encoding_has_version_byte() {
-        let hash = encode_dashboard(SAMPLE_INPUT, String::new(), vec![]).unwrap();
+        let hash = encode_dashboard(SAMPLE_INPUT, None, String::new(), vec![]).unwrap();
         let raw = BASE64_URL_SAFE_NO_PAD.decode(&hash).unwrap();
         assert_eq!(raw[0], BROTLI_VERSION_BYTE);
     }
@@ -257,6 +617,7 @@ mod test {
             .map(|i| PercentileBucket {
                 value: f64::from(i),
                 percentile: f64::from(i) / 10.0,
+                ..Default::default()
             })
             .collect();
         let result = downsample_percentiles(&buckets);
@@ -269,6 +630,7 @@ mod test {
             .map(|i| PercentileBucket {
                 value: f64::from(i),
                 percentile: f64::from(i) / 100.0,
+                ..Default::default()
             })
             .collect();
         let result = downsample_percentiles(&buckets);
@@ -305,7 +667,7 @@ mod test {
             write!(acc, "{:08x}", i.wrapping_mul(2_654_435_761)).unwrap();
             acc
         });
-        let result = encode_dashboard(SAMPLE_INPUT, big_desc, vec![]);
+        let result = encode_dashboard(SAMPLE_INPUT, None, big_desc, vec![]);
         assert!(result.is_err());
         let err = result.unwrap_err();
         assert!(
@@ -316,7 +678,7 @@ mod test {
 
     #[test]
     fn test_empty_input_produces_valid_hash() {
-        let hash = encode_dashboard("", String::new(), vec![]).unwrap();
+        let hash = encode_dashboard("", None, String::new(), vec![]).unwrap();
         let decoded = decode_dashboard(&hash).unwrap();
         assert!(decoded.tests.is_empty());
         assert!(decoded.benchmarks.is_empty());
@@ -367,7 +729,7 @@ mod test {
     fn test_roundtrip_with_special_characters() {
         let desc = "Ünïcödé 🚀 <script>alert('xss')</script> \"quotes\" & ampersand".to_string();
         let tags = vec!["tag/with/slashes".to_string(), "spaced tag".to_string()];
-        let hash = encode_dashboard(SAMPLE_INPUT, desc.clone(), tags.clone()).unwrap();
+        let hash = encode_dashboard(SAMPLE_INPUT, None, desc.clone(), tags.clone()).unwrap();
         let decoded = decode_dashboard(&hash).unwrap();
         assert_eq!(decoded.description, Some(desc));
         assert_eq!(decoded.tags, tags);